@@ -18,3 +18,61 @@ gstreamer-use-shared-libraries-description = Set GST_PLUGIN_PATH variable to loa
 dxvk-version = DXVK version
 dxvk-selection-disabled = DXVK selection is disabled by your wine group preferences
 dxvk-recommended-description = Show only recommended dxvk versions
+
+vkd3d-version = VKD3D-Proton version
+vkd3d-version-description = Translates DirectX 12 to Vulkan. Selecting a version makes the game run with DirectX 12
+nvapi-version = DXVK-NVAPI version
+nvapi-version-description = Enables NVIDIA specific features like DLSS. Requires DXVK and an NVIDIA GPU
+
+components-bundle = Offline bundle
+components-bundle-description = Move wine and DXVK builds to computers without internet connection
+export-components-bundle = Export bundle
+export-components-bundle-description = Save selected wine and DXVK builds with the components index to an archive
+import-components-bundle = Import bundle
+import-components-bundle-description = Install wine and DXVK builds from the exported archive
+components-bundle-exported = Components bundle was exported
+components-bundle-imported = Components bundle was imported
+
+custom-wine = Custom builds
+custom-wine-description = Wine builds from local folders. Removing a build doesn't delete its folder
+custom-wine-name = Build name, then choose its folder
+custom-wine-remove = Remove build
+custom-wine-added = Custom wine build was added
+custom-wine-removed = Custom wine build was removed
+
+verify-component = Verify files
+component-verified = Component files are not changed
+component-files-broken = Component files are changed or missing
+component-files-unverified = Component files are not changed, but it was installed without archive checksum
+component-checksum-unavailable = Checksum unavailable, component archive won't be verified
+
+disk-usage = Disk usage
+disk-usage-description = Downloaded wine and DXVK builds and archives left by interrupted downloads
+remove-unused-builds = Remove unused builds
+remove-unused-builds-description = Free {$size} by removing not selected builds
+selected-build = selected
+rollback-build = kept for rollback
+custom-build = custom
+temp-leftover = Interrupted download
+unused-builds-removed = Removed unused builds, freed {$size}
+temp-leftover-removed = Interrupted download was removed
+
+components-index-description = Manage servers the wine and DXVK versions lists are downloaded from
+index-servers = Servers
+index-servers-description = Servers are tried in the listed order. Local mirrors can be added as file:// links or folder paths
+add-index-server = Add server
+index-server-synced = Synced {$time}, {$changes} changes
+index-server-not-synced = Wasn't synced yet
+index-server-no-changes = No changes
+sync-components-index = Sync now
+sync-components-index-description = Download the latest components index from the first available server
+
+components-upgrade = Upgrades
+components-upgrade-policy = New recommended builds
+components-upgrade-policy-description = What to do on the launcher start when the components index has newer recommended wine or DXVK
+components-upgrade-disabled = Do nothing
+components-upgrade-notify = Notify
+components-upgrade-install = Download and select
+components-rollback = Roll back
+components-rollback-description = Select wine and DXVK builds used before the last upgrade
+components-rolled-back = Previous wine and DXVK builds were selected
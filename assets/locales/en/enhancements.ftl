@@ -34,11 +34,9 @@ performance = Performance
 gamemode = Gamemode
 gamemode-description = Prioritize the game over the rest of the processes
 
-fix-launch-dialog = Fix blank launch dialog
-fix-launch-dialog-description = Applies a workaround for the blank launch dialog issue
-
-run-with-dx11 = Run with DX11
-run-with-dx11-description = Force the game to run with DirectX 11 (For compatibility)
+directx-version = DirectX version
+directx-version-description = DirectX 11 is more compatible. DirectX 12 works better with VKD3D-Proton installed in the components settings
+directx-version-vkd3d = DirectX 12 is translated by VKD3D-Proton {$version}
 
 gamescope = Gamescope
 gamescope-description = Gamescope is a tool from Valve that allows for games to run in an isolated Xwayland instance and supports AMD, Intel, and Nvidia GPUs
@@ -19,11 +19,37 @@ game-file-repairing-error = Failed to repair game file
 integrity-files-getting-error = Failed to get integrity files
 
 background-downloading-failed = Failed to download background picture
+background-processing-failed = Failed to process background picture
+background-video-failed = Failed to play background video
 components-index-sync-failed = Failed to sync components index
 components-index-verify-failed = Failed to verify components index
 config-update-error = Failed to save config
+migration-failed = Failed to migrate installation
+migration-revert-failed = Failed to revert installation migration
+launcher-restart-failed = Failed to restart launcher
+path-check-failed = Some of the chosen folders can't be used
+import-failed = Failed to import installation
+import-not-game-folder = Chosen folder doesn't contain the game
+import-target-not-empty = Folder {$path} is not empty. Choose another folder in the settings or import the installation without moving files
+components-bundle-failed = Failed to process components bundle
+components-upgrade-failed = Failed to upgrade wine and DXVK builds
+components-rollback-failed = Failed to roll wine and DXVK builds back
+prefix-snapshot-failed = Failed to process wine prefix snapshot
+automatic-prefix-snapshot-failed = Failed to make automatic wine prefix snapshot
+wine-prefix-check-failed = Failed to check wine prefix
+wine-prefix-rebuild-failed = Failed to rebuild wine prefix
+winetricks-failed = Failed to install winetricks verb
+executable-run-failed = Failed to run executable
+registry-tweaks-failed = Failed to apply registry tweaks
+registry-invalid-dword = Dword value must be a decimal or 0x prefixed hex number
+custom-wine-failed = Failed to update custom wine builds
+component-checksum-mismatch = Downloaded archive is corrupted or was tampered with
+component-verification-failed = Failed to verify component files
+components-cleanup-failed = Failed to clean up components
+index-server-invalid = Invalid components index server address
 wine-prefix-update-failed = Failed to update wine prefix
 dxvk-install-failed = Failed to install DXVK
+directx-install-failed = Failed to update {$component}
 voice-package-deletion-error = Failed to delete voice package
 
 game-diff-finding-error = Failed to find game diff
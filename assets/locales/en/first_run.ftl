@@ -33,6 +33,22 @@ tos-dialog-message =
 dependencies = Dependencies
 missing-dependencies-title = You're missing some dependencies!
 missing-dependencies-message = You must install some packages to your system before continue installation process
+missing-dependencies = Missing dependencies
+distribution = Distribution
+install-required-dependencies = Install required dependencies
+install-optional-dependencies = Install optional dependencies
+dependency-required = Required
+dependency-optional = Optional
+
+dependency-components-index = Downloading components index
+dependency-unpacking = Unpacking game files and components
+dependency-game-patches = Applying game update patches
+dependency-telemetry = Disabling telemetry servers
+dependency-sandbox = Game sandboxing
+dependency-gamescope = Gamescope
+dependency-gamemode = Gamemode
+dependency-winetricks = Installing winetricks verbs
+dependency-mangohud = MangoHud
 
 
 default-paths = Default paths
@@ -41,6 +57,8 @@ show-all-folders = I know what I'm doing
 show-all-folders-subtitle = Show additional path selection settings. Do as I say...
 runners-folder = Runners folder
 dxvks-folder = DXVKs folder
+vkd3d-folder = VKD3D-Proton builds folder
+nvapi-folder = DXVK-NVAPI builds folder
 wine-prefix-folder = Wine prefix folder
 global-game-installation-folder = Global game version installation folder
 chinese-game-installation-folder = Chinese game version installation folder
@@ -49,6 +67,12 @@ components-index = Components index
 patch-folder = Patch folder
 temp-folder = Temp folder
 
+path-not-writable = Folder is not writable
+path-not-enough-space = Not enough free space: {$required} GB required, {$available} GB available
+path-unsupported-filesystem = {$filesystem} filesystem doesn't support file permissions and symlinks needed by wine
+path-case-insensitive = Filesystem is case-insensitive which breaks wine prefixes
+path-noexec = Folder is mounted with noexec option so files from it can't be run
+
 migrate = Migrate
 
 
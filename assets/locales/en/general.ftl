@@ -4,6 +4,34 @@ classic = Classic
 update-background = Update background picture
 update-background-description = Download official background picture for the launcher. You can disable it to use your custom image instead
 
+background-source = Background picture
+background-official = Official
+background-file = Local image
+background-folder = Images folder
+background-path = Background path
+images = Images
+
+background-scaling = Scaling
+background-scaling-description = How the picture should fill the launcher window
+background-scaling-cover = Cover
+background-scaling-contain = Contain
+background-scaling-stretch = Stretch
+
+background-crop = Crop
+background-crop-description = Use only a part of the picture. Values are in the picture's pixels
+
+background-blur = Blur
+background-blur-description = Blur the picture to make launcher controls more legible
+background-dim = Dimming
+background-dim-description = Darken the picture by the given percent
+
+background-video = Background video
+background-video-description = Play looping muted video or animated image behind the launcher controls. Background picture is shown while it's loading
+background-video-file = Video file
+videos = Videos
+background-video-battery = Disable on battery
+background-video-battery-description = Show background picture instead of the video when running on battery power
+
 launcher-language = Launcher language
 launcher-language-description = Applies after restart
 
@@ -23,6 +51,19 @@ chinese = Chinese
 
 migrate-installation = Migrate installation
 migrate-installation-description = Open special window where you can change your game installation folder
+import-installation = Import installation
+import-installation-description = Use the game installed by Lutris, Heroic, Bottles or Steam instead of downloading it again
+found-installations = Found installations
+found-installations-description = Game installations made by other launchers. You can also choose the game folder manually
+search-again = Search again
+choose-folder = Choose folder
+import-not-verified = game version is unknown
+import-prefix = Import wine prefix
+import-prefix-description = Use the wine prefix of the found installation as well
+import-move-files = Move files
+import-move-files-description = Move the game and prefix to the launcher folders instead of using them in place. Other launcher won't be able to run the game anymore
+import = Import
+import-cancelled = Installation import was cancelled
 repair-game = Repair game
 
 status = Status
@@ -68,3 +109,72 @@ explorer = Explorer
 task-manager = Task manager
 configuration = Configuration
 debugger = Debugger
+winetricks-description = Install fonts, runtime libraries and DirectX components to the wine prefix
+winetricks-verbs = Verbs
+winetricks-verbs-description = Installed verbs are reapplied when the wine prefix is rebuilt
+winetricks-verb-name = Verb name
+winetricks-fonts = Fonts
+winetricks-runtime = Runtime libraries
+winetricks-directx = DirectX
+winetricks-custom = Custom verb
+install-winetricks-verb = Install
+reapply-winetricks-verbs = Reapply installed verbs
+reapply-winetricks-verbs-description = Install all the recorded verbs again with the selected wine build
+winetricks-output = Output
+installing-winetricks-verb = Installing {$verb}
+winetricks-verb-installed = {$verb} is installed
+winetricks-verbs-reapplied = Installed verbs were reapplied
+
+run-executable = Run executable…
+run-executable-description = Run windows program in the game's wine prefix with the same wine build and environment
+executable = Executable
+executable-not-chosen = Not chosen
+executable-arguments = Arguments
+working-directory = Working directory
+working-directory-default = Executable's folder
+windows-executables = Windows executables
+run = Run
+executable-output = Output
+executable-finished = {$executable} exited with code {$code}
+
+registry-tweaks = Registry tweaks
+registry-tweaks-description = Registry values set in the wine prefix before launching the game
+registry-tweaks-list-description = Tweaks are applied only when the prefix differs from them, disabled tweaks are removed from the prefix. krsdkexternal.exe override fixes the blank launch dialog, Windows 7 version fixes the black in-game browser
+registry-default-value = (Default)
+registry-tweak-not-applied = not applied yet
+remove-registry-tweak = Remove tweak
+new-registry-tweak = New tweak
+registry-key = Key
+registry-value-name = Value name
+registry-value-type = Type
+registry-value-data = Data
+
+wine-prefix = Wine prefix
+wine-prefix-description = Check, rebuild or restore the wine prefix from snapshots
+wine-prefix-health = Health
+check-wine-prefix = Check again
+wine-prefix-healthy = No problems found
+wine-prefix-not-exists = Prefix doesn't exist
+wine-prefix-wine-mismatch = Prefix was made by another wine build
+wine-prefix-dxvk-missing = DXVK is not installed
+wine-prefix-registry-broken = Registry is broken
+wine-prefix-drive-mapping = Drive is mapped incorrectly
+rebuild-wine-prefix = Rebuild prefix
+rebuild-wine-prefix-description = Create new prefix with the selected components. Game settings from AppData and Documents folders are kept
+rebuild = Rebuild
+wine-prefix-rebuilt = Wine prefix was rebuilt
+
+prefix-snapshots = Snapshots
+prefix-snapshots-description = Save the wine prefix state to restore it if it gets broken
+create-prefix-snapshot = Snapshot name
+prefix-snapshot-automatic = Automatic, before changing {$component}
+prefix-snapshot-copy = copy
+prefix-snapshot-archive = archive
+restore-prefix-snapshot = Restore snapshot
+remove-prefix-snapshot = Remove snapshot
+prefix-snapshot-created = Wine prefix snapshot was created
+prefix-snapshot-restored = Wine prefix snapshot was restored
+prefix-snapshot-removed = Wine prefix snapshot was removed
+automatic-prefix-snapshots = Automatic snapshots
+automatic-prefix-snapshots-description = Make a snapshot before switching wine, DXVK, VKD3D-Proton or DXVK-NVAPI
+keep-automatic-prefix-snapshots = Automatic snapshots to keep
@@ -3,6 +3,7 @@ none = None
 default = Default
 details = Details
 options = Options
+apply = Apply
 
 width = Width
 height = Height
@@ -23,6 +24,8 @@ nothing = Nothing
 save = Save
 continue = Continue
 resume = Resume
+revert = Revert
+cancel = Cancel
 exit = Exit
 check = Check
 restart = Restart
@@ -47,11 +50,21 @@ unpacking = Unpacking
 verifying-files = Verifying files
 repairing-files = Repairing files
 migrating-folders = Migrating folders
+cancelling = Cancelling
 applying-hdiff = Applying hdiff patches
 removing-outdated = Removing outdated files
 
 
 components-index-updated = Components index was updated
+components-upgrade-available = New recommended wine and DXVK builds are available
+components-upgraded = Wine and DXVK builds were upgraded
+wine-prefix-issues-found = Wine prefix has problems. Check it in the preferences
+importing-components-bundle = Importing components bundle
+
+migration-finished = Installation was migrated
+installation-imported = Installation was imported
+migration-interrupted = Installation migration was interrupted
+migration-interrupted-description = Some files were already moved to the new folders. You can resume the migration, or move these files back
 
 
 launch = Launch
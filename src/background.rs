@@ -1,87 +1,478 @@
-use std::process::Command;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
 
+use gtk::prelude::*;
+
+use image::{ImageReader, ImageFormat, DynamicImage, RgbaImage, Rgba, imageops};
+use image::imageops::FilterType;
+
+use anime_launcher_sdk::anime_game_core::minreq;
 use anime_launcher_sdk::anime_game_core::installer::downloader::Downloader;
 
 use md5::{Md5, Digest};
 
-#[derive(Debug, Clone)]
-pub struct Background {
-    pub uri: String,
-    pub hash: String
+use crate::settings::Settings;
+use crate::settings::background::*;
+
+/// Official background picture won't be checked for changes more often than this (in seconds)
+pub const BACKGROUND_CHECK_TTL: u64 = 6 * 60 * 60;
+
+/// Timeout of the background picture requests (in seconds)
+///
+/// Background video is streamed to the disk without it
+pub const REQUEST_TIMEOUT: u64 = 10;
+
+/// Default window size of the classic launcher style
+pub const CLASSIC_WINDOW_SIZE: (i32, i32) = (1094, 624);
+
+/// Extensions of the files which can be used as background pictures
+pub const IMAGE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "webp", "bmp"];
+
+/// Extensions of the files which can be used as background videos
+pub const VIDEO_EXTENSIONS: &[&str] = &["mp4", "webm", "mkv", "mov", "gif"];
+
+thread_local! {
+    /// CSS provider which overrides the background picture set in the global CSS
+    static BACKGROUND_CSS_PROVIDER: gtk::CssProvider = {
+        let provider = gtk::CssProvider::new();
+
+        if let Some(display) = gtk::gdk::Display::default() {
+            gtk::style_context_add_provider_for_display(&display, &provider, gtk::STYLE_PROVIDER_PRIORITY_APPLICATION + 1);
+        }
+
+        provider
+    };
 }
 
 pub fn get_uri() -> String {
     concat!("https://wuther", "ingwav", "es.kur", "ogames.com/website-preface/video/bg/bg-poster.png").to_owned()
 }
 
-#[cached::proc_macro::cached(result)]
-pub fn get_background_info() -> anyhow::Result<Background> {
-    let uri = get_uri();
+/// Official background video, stored next to the poster picture
+pub fn get_video_uri() -> String {
+    concat!("https://wuther", "ingwav", "es.kur", "ogames.com/website-preface/video/bg/bg.mp4").to_owned()
+}
 
-    let hash = uri.split('/')
-        .last()
-        .unwrap_or_default()
-        .split('_')
-        .next()
-        .unwrap_or_default()
-        .to_owned();
+/// Get md5 hash of the file without reading it to memory
+fn file_hash(file: &Path) -> anyhow::Result<String> {
+    let mut hasher = Md5::new();
 
-    Ok(Background {
-        uri,
-        hash
-    })
+    std::io::copy(&mut std::fs::File::open(file)?, &mut hasher)?;
+
+    Ok(format!("{:x}", hasher.finalize()))
 }
 
+/// Ask server to send the file only if it differs from the cached one
+fn conditional_request(mut request: minreq::Request, official: &OfficialBackground, cached: bool) -> minreq::Request {
+    if cached {
+        if let Some(etag) = &official.etag {
+            request = request.with_header("If-None-Match", etag);
+        }
+
+        if let Some(last_modified) = &official.last_modified {
+            request = request.with_header("If-Modified-Since", last_modified);
+        }
+    }
+
+    request
+}
+
+/// Download the file from the given uri if it was changed on the server
+///
+/// Returns `true` if the file was downloaded
+fn fetch_official(uri: &str, file: &Path, official: &mut OfficialBackground, cached: bool) -> anyhow::Result<bool> {
+    let request = minreq::get(uri)
+        .with_timeout(REQUEST_TIMEOUT);
+
+    let response = conditional_request(request, official, cached).send()?;
+
+    match response.status_code {
+        304 => {
+            tracing::debug!("Background file wasn't changed: {uri}");
+
+            Ok(false)
+        }
+
+        200..=299 => {
+            let content = response.as_bytes();
+
+            // Write to a temp file first so we won't corrupt cached file
+            let temp = file.with_extension("part");
+
+            std::fs::write(&temp, content)?;
+            std::fs::rename(&temp, file)?;
+
+            official.etag = response.headers.get("etag").cloned();
+            official.last_modified = response.headers.get("last-modified").cloned();
+            official.hash = Some(format!("{:x}", Md5::digest(content)));
+
+            Ok(true)
+        }
+
+        code => anyhow::bail!("Failed to download background file: {code} {}", response.reason_phrase)
+    }
+}
+
+/// Stream the file from the given uri to the disk if it was changed on the server
+///
+/// Unlike `fetch_official` it doesn't keep the file in memory,
+/// so it's used for the large files like the background video
+///
+/// Returns `true` if the file was downloaded
+fn stream_official(uri: &str, file: &Path, official: &mut OfficialBackground, cached: bool) -> anyhow::Result<bool> {
+    let request = minreq::head(uri)
+        .with_timeout(REQUEST_TIMEOUT);
+
+    let response = conditional_request(request, official, cached).send()?;
+
+    match response.status_code {
+        304 => {
+            tracing::debug!("Background file wasn't changed: {uri}");
+
+            Ok(false)
+        }
+
+        200..=299 => {
+            // Download to a temp file first so we won't corrupt cached file
+            let temp = file.with_extension("part");
+
+            if temp.exists() {
+                std::fs::remove_file(&temp)?;
+            }
+
+            Downloader::new(uri)?
+                .with_continue_downloading(false)
+                .download(&temp, |_, _| {})?;
+
+            std::fs::rename(&temp, file)?;
+
+            official.etag = response.headers.get("etag").cloned();
+            official.last_modified = response.headers.get("last-modified").cloned();
+            official.hash = Some(file_hash(file)?);
+
+            Ok(true)
+        }
+
+        code => anyhow::bail!("Failed to download background file: {code} {}", response.reason_phrase)
+    }
+}
+
+/// Download the official file from the given uri if it was changed
+/// or wasn't checked for a while
+///
+/// Returns `true` if the cached state was changed and should be saved
+fn update_official(uri: &str, file: &Path, official: &mut OfficialBackground, stream: bool) -> anyhow::Result<bool> {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)?
+        .as_secs();
+
+    // Cached file can be used only if it wasn't modified since downloading
+    let cached = match &official.hash {
+        Some(hash) if file.exists() => &file_hash(file)? == hash,
+
+        _ => false
+    };
+
+    if cached && now.saturating_sub(official.checked_at) < BACKGROUND_CHECK_TTL {
+        tracing::debug!("Background file was checked recently. Skipping: {uri}");
+
+        return Ok(false);
+    }
+
+    let fetched = if stream {
+        stream_official(uri, file, official, cached)
+    } else {
+        fetch_official(uri, file, official, cached)
+    };
+
+    match fetched {
+        Ok(_) => official.checked_at = now,
+
+        // Fallback to the cached file if we're offline
+        Err(err) if file.exists() => {
+            tracing::warn!("Failed to check background file changes, using cached one: {err}");
+        }
+
+        Err(err) => return Err(err)
+    }
+
+    Ok(true)
+}
+
+/// Download the official background picture to the `BACKGROUND_FILE`
+/// if it was changed or wasn't checked for a while
 pub fn download_background() -> anyhow::Result<()> {
-    tracing::debug!("Downloading background picture");
+    tracing::debug!("Updating official background picture");
+
+    let mut official = Settings::get()?.background.official;
+
+    // Settings are not locked while downloading, only the changed state is saved
+    if update_official(&get_uri(), &crate::BACKGROUND_FILE, &mut official, false)? {
+        Settings::modify(|settings| settings.background.official = official)?;
+    }
 
-    let info = get_background_info()?;
+    Ok(())
+}
 
-    let mut download_image = true;
-    let mut replace_cached_image = false;
+/// Download the official background video to the `BACKGROUND_VIDEO_FILE`
+/// if it was changed or wasn't checked for a while
+pub fn download_video() -> anyhow::Result<()> {
+    tracing::debug!("Updating official background video");
 
-    if crate::BACKGROUND_FILE.exists() {
-        let hash = Md5::digest(std::fs::read(crate::BACKGROUND_FILE.as_path())?);
+    let mut official = Settings::get()?.background.video.official;
 
-        if format!("{:x}", hash).to_lowercase() == info.hash {
-            tracing::debug!("Background picture is already downloaded. Skipping");
+    // Video is too large to be kept in memory, and can take longer than the request timeout
+    if update_official(&get_video_uri(), &crate::BACKGROUND_VIDEO_FILE, &mut official, true)? {
+        Settings::modify(|settings| settings.background.video.official = official)?;
+    }
 
-            download_image = false;
+    Ok(())
+}
+
+/// Check if the system is running on battery power
+///
+/// Batteries of peripheral devices (mice, gamepads, etc.) are ignored
+pub fn on_battery() -> bool {
+    let Ok(supplies) = std::fs::read_dir("/sys/class/power_supply") else {
+        return false;
+    };
+
+    let read = |path: &Path, name: &str| {
+        std::fs::read_to_string(path.join(name))
+            .map(|value| value.trim().to_string())
+            .unwrap_or_default()
+    };
+
+    let mut has_battery = false;
+
+    for supply in supplies.flatten() {
+        let path = supply.path();
+
+        match read(&path, "type").as_str() {
+            "Mains" | "USB" if read(&path, "online") == "1" => return false,
+
+            "Battery" if read(&path, "scope") != "Device" => {
+                if read(&path, "status") != "Discharging" {
+                    return false;
+                }
+
+                has_battery = true;
+            }
+
+            _ => ()
+        }
+    }
+
+    has_battery
+}
+
+/// Get the background video file which should be played, downloading the official one if needed
+///
+/// Returns `None` if the video is disabled, or the system is running on battery
+/// and the video shouldn't be played then
+pub fn prepare_video() -> anyhow::Result<Option<PathBuf>> {
+    let video = Settings::get()?.background.video;
+
+    if !video.enabled {
+        return Ok(None);
+    }
+
+    if video.disable_on_battery && on_battery() {
+        tracing::info!("Running on battery. Background video is disabled");
+
+        return Ok(None);
+    }
+
+    match video.path {
+        Some(path) if path.exists() => Ok(Some(path)),
+        Some(path) => anyhow::bail!("Background video file doesn't exist: {:?}", path),
+
+        None => {
+            download_video()?;
+
+            Ok(Some(crate::BACKGROUND_VIDEO_FILE.clone()))
+        }
+    }
+}
+
+/// List images from the given folder which can be used as background pictures
+pub fn list_images(folder: impl AsRef<Path>) -> anyhow::Result<Vec<PathBuf>> {
+    let mut images = folder.as_ref().read_dir()?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file())
+        .filter(|path| {
+            path.extension()
+                .and_then(|ext| ext.to_str())
+                .map(|ext| IMAGE_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str()))
+                .unwrap_or(false)
+        })
+        .collect::<Vec<_>>();
+
+    images.sort();
+
+    Ok(images)
+}
+
+/// Get size of the biggest window the launcher can have, in physical pixels
+///
+/// Must be called from the main thread
+pub fn target_size() -> (u32, u32) {
+    let (mut width, mut height) = (CLASSIC_WINDOW_SIZE.0 as u32, CLASSIC_WINDOW_SIZE.1 as u32);
+
+    if let Some(display) = gtk::gdk::Display::default() {
+        let monitors = display.monitors();
+
+        for i in 0..monitors.n_items() {
+            if let Some(monitor) = monitors.item(i).and_downcast::<gtk::gdk::Monitor>() {
+                let geometry = monitor.geometry();
+                let scale = monitor.scale_factor();
+
+                width = width.max((geometry.width() * scale) as u32);
+                height = height.max((geometry.height() * scale) as u32);
+            }
         }
     }
 
-    if download_image {
-        let mut downloader = Downloader::new(&info.uri)?;
+    (width, height)
+}
+
+/// Decode, crop, scale and apply effects to the image according to the given parameters,
+/// and save it to the `PROCESSED_BACKGROUND_FILE` as PNG
+///
+/// `size` is the biggest window size the picture should cover (see `target_size`)
+pub fn process_image(path: impl AsRef<Path>, params: BackgroundImage, effects: BackgroundEffects, size: (u32, u32)) -> anyhow::Result<()> {
+    tracing::debug!("Processing background picture: {:?}", path.as_ref());
+
+    // Guess format from the content because some pictures
+    // have wrong extensions (e.g. JPEG named as ".webp")
+    let mut image = ImageReader::open(path.as_ref())?
+        .with_guessed_format()?
+        .decode()?;
 
-        downloader.continue_downloading = false;
+    if let Some(crop) = params.crop {
+        let x = crop.x.min(image.width() - 1);
+        let y = crop.y.min(image.height() - 1);
 
-        if let Err(err) = downloader.download(crate::BACKGROUND_FILE.as_path(), |_, _| {}) {
-            anyhow::bail!(err);
+        let width = crop.width.min(image.width() - x);
+        let height = crop.height.min(image.height() - y);
+
+        if width > 0 && height > 0 {
+            image = image.crop_imm(x, y, width, height);
         }
+    }
+
+    let aspect = CLASSIC_WINDOW_SIZE.0 as f64 / CLASSIC_WINDOW_SIZE.1 as f64;
+
+    // Size with window's aspect ratio which covers the given size
+    let (width, height) = if size.0 as f64 / size.1 as f64 > aspect {
+        (size.0, (size.0 as f64 / aspect).round() as u32)
+    } else {
+        ((size.1 as f64 * aspect).round() as u32, size.1)
+    };
+
+    let mut image = match params.scaling {
+        BackgroundScaling::Cover => image.resize_to_fill(width, height, FilterType::Triangle),
+
+        BackgroundScaling::Contain => {
+            let image = image.resize(width, height, FilterType::Triangle);
 
-        replace_cached_image = true;
+            let mut canvas = RgbaImage::from_pixel(width, height, Rgba([0, 0, 0, 255]));
+
+            imageops::overlay(
+                &mut canvas,
+                &image,
+                (width - image.width()) as i64 / 2,
+                (height - image.height()) as i64 / 2
+            );
+
+            DynamicImage::ImageRgba8(canvas)
+        }
+
+        BackgroundScaling::Stretch => image.resize_exact(width, height, FilterType::Triangle)
+    };
+
+    if effects.blur > 0.0 {
+        image = image.fast_blur(effects.blur);
     }
 
-    // Workaround for GTK weakness
-    if info.uri.ends_with(".webp") {
-        Command::new("dwebp")
-            .arg(crate::BACKGROUND_FILE.as_path())
-            .arg("-o")
-            .arg(crate::PROCESSED_BACKGROUND_FILE.as_path())
-            .spawn()?
-            .wait()?;
+    if effects.dim > 0.0 {
+        let brightness = 1.0 - effects.dim.clamp(0.0, 1.0);
+
+        let mut rgba = image.into_rgba8();
 
-        // If it failed to re-code the file - just copy it
-        // Will happen with HSR because devs apparently named
-        // their background image ".webp" while it's JPEG
-        if replace_cached_image || !crate::PROCESSED_BACKGROUND_FILE.exists() {
-            std::fs::copy(crate::BACKGROUND_FILE.as_path(), crate::PROCESSED_BACKGROUND_FILE.as_path())?;
+        for pixel in rgba.pixels_mut() {
+            for channel in &mut pixel.0[..3] {
+                *channel = (*channel as f32 * brightness).round() as u8;
+            }
         }
+
+        image = DynamicImage::ImageRgba8(rgba);
     }
 
-    else {
-        std::fs::copy(crate::BACKGROUND_FILE.as_path(), crate::PROCESSED_BACKGROUND_FILE.as_path())?;
+    image.save_with_format(crate::PROCESSED_BACKGROUND_FILE.as_path(), ImageFormat::Png)?;
+
+    Ok(())
+}
+
+/// Update `PROCESSED_BACKGROUND_FILE` using the background source from the launcher settings
+///
+/// If `rotate` is `true` and background pictures are taken from a folder,
+/// then the next image from this folder will be used. `size` is the biggest
+/// window size the picture should cover (see `target_size`)
+pub fn update_background(rotate: bool, size: (u32, u32)) -> anyhow::Result<()> {
+    let settings = Settings::get()?;
+
+    let effects = settings.background.effects;
+
+    match settings.background.source.clone() {
+        // Processed picture can be replaced by user manually
+        // when the official picture is not updated
+        BackgroundSource::Official => {
+            if !crate::KEEP_BACKGROUND_FILE.exists() {
+                download_background()?;
+
+                process_image(crate::BACKGROUND_FILE.as_path(), BackgroundImage::default(), effects, size)?;
+            }
+        }
+
+        BackgroundSource::File(path) => process_image(&path, settings.background.image(&path), effects, size)?,
+
+        BackgroundSource::Folder(folder) => {
+            let images = list_images(&folder)?;
+
+            if images.is_empty() {
+                anyhow::bail!("No images found in the background folder: {:?}", folder);
+            }
+
+            let current = settings.background.current.as_ref()
+                .and_then(|current| images.iter().position(|image| image == current));
+
+            let image = match current {
+                Some(current) if rotate => &images[(current + 1) % images.len()],
+                Some(current) => &images[current],
+                None => &images[0]
+            };
+
+            process_image(image, settings.background.image(image), effects, size)?;
+
+            if settings.background.current.as_ref() != Some(image) {
+                Settings::modify(|settings| settings.background.current = Some(image.to_path_buf()))?;
+            }
+        }
     }
 
     Ok(())
 }
+
+/// Make GTK re-read the `PROCESSED_BACKGROUND_FILE`
+///
+/// Must be called from the main thread
+pub fn reload_css() {
+    BACKGROUND_CSS_PROVIDER.with(|provider| {
+        provider.load_from_string(&format!(
+            "window.classic-style {{ background-image: url(\"file://{}\"); }}",
+            crate::PROCESSED_BACKGROUND_FILE.to_string_lossy()
+        ));
+    });
+}
@@ -0,0 +1,249 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use serde::{Serialize, Deserialize};
+
+use anime_launcher_sdk::components::*;
+
+use anime_launcher_sdk::config::ConfigExt;
+use anime_launcher_sdk::wuwa::config::Config;
+
+/// Name of the manifest file in the bundle archive
+pub const BUNDLE_MANIFEST: &str = "bundle.json";
+
+/// Offline components bundle description
+///
+/// Bundle is a `tar.gz` archive with the manifest, wine and DXVK builds
+/// and the components index folder, all placed in the archive's root
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BundleManifest {
+    pub launcher_version: String,
+
+    /// Name of the components index folder
+    pub components: Option<String>,
+
+    /// Names of the wine builds
+    pub wine: Vec<String>,
+
+    /// Names of the DXVK builds
+    pub dxvk: Vec<String>
+}
+
+fn tar(args: &[&std::ffi::OsStr]) -> anyhow::Result<Vec<u8>> {
+    let output = Command::new("tar").args(args).output()?;
+
+    if !output.status.success() {
+        anyhow::bail!("tar failed: {}", String::from_utf8_lossy(&output.stderr).trim());
+    }
+
+    Ok(output.stdout)
+}
+
+/// Export selected wine and DXVK builds with the components index to the given file
+pub fn export(path: impl AsRef<Path>) -> anyhow::Result<BundleManifest> {
+    let config = Config::get()?;
+
+    let mut manifest = BundleManifest {
+        launcher_version: crate::APP_VERSION.to_string(),
+        ..BundleManifest::default()
+    };
+
+    let mut args: Vec<&std::ffi::OsStr> = vec![
+        "-czf".as_ref(),
+        path.as_ref().as_os_str()
+    ];
+
+    // Manifest is written to a temp folder to be placed in the archive's root
+    let manifest_folder = std::env::temp_dir().join(format!(".wavey-launcher-bundle-{}", std::process::id()));
+    let manifest_path = manifest_folder.join(BUNDLE_MANIFEST);
+
+    let mut folders = Vec::<(PathBuf, String)>::new();
+
+    if let Some(name) = config.components.path.file_name() {
+        let name = name.to_string_lossy().to_string();
+
+        if let Some(parent) = config.components.path.parent() {
+            folders.push((parent.to_path_buf(), name.clone()));
+
+            manifest.components = Some(name);
+        }
+    }
+
+    if let Some(wine) = config.get_selected_wine()? {
+        if wine.is_downloaded_in(&config.game.wine.builds) {
+            folders.push((config.game.wine.builds.clone(), wine.name.clone()));
+
+            manifest.wine.push(wine.name);
+        }
+    }
+
+    if let Some(dxvk) = config.get_selected_dxvk()? {
+        if dxvk.is_downloaded_in(&config.game.dxvk.builds) {
+            folders.push((config.game.dxvk.builds.clone(), dxvk.name.clone()));
+
+            manifest.dxvk.push(dxvk.name);
+        }
+    }
+
+    if manifest.wine.is_empty() && manifest.dxvk.is_empty() {
+        anyhow::bail!("There are no downloaded wine or DXVK builds selected");
+    }
+
+    std::fs::create_dir_all(&manifest_folder)?;
+    std::fs::write(&manifest_path, serde_json::to_string_pretty(&manifest)?)?;
+
+    args.extend(["-C".as_ref(), manifest_folder.as_os_str(), BUNDLE_MANIFEST.as_ref()]);
+
+    for (parent, name) in &folders {
+        args.extend(["-C".as_ref(), parent.as_os_str(), name.as_ref()]);
+    }
+
+    tracing::info!("Exporting components bundle: {manifest:?}");
+
+    let result = tar(&args);
+
+    if let Err(err) = std::fs::remove_dir_all(&manifest_folder) {
+        tracing::warn!("Failed to remove temp bundle folder: {err}");
+    }
+
+    result?;
+
+    Ok(manifest)
+}
+
+/// Check that the name from the manifest is a folder in the archive's root,
+/// so extracting it can't write outside of the target folder
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty() && !name.starts_with('.') && !name.contains('/')
+}
+
+/// Read manifest of the bundle archive
+pub fn read_manifest(path: impl AsRef<Path>) -> anyhow::Result<BundleManifest> {
+    let manifest = tar(&[
+        "-xzOf".as_ref(),
+        path.as_ref().as_os_str(),
+        BUNDLE_MANIFEST.as_ref()
+    ])?;
+
+    let manifest = serde_json::from_slice::<BundleManifest>(&manifest)?;
+
+    let names = manifest.components.iter()
+        .chain(&manifest.wine)
+        .chain(&manifest.dxvk);
+
+    for name in names {
+        if !is_valid_name(name) {
+            anyhow::bail!("Invalid folder name in the bundle manifest: {name:?}");
+        }
+    }
+
+    Ok(manifest)
+}
+
+/// Extract one folder from the bundle archive to the given parent folder
+fn extract(bundle: &Path, name: &str, parent: &Path) -> anyhow::Result<()> {
+    std::fs::create_dir_all(parent)?;
+
+    tar(&[
+        "-xzf".as_ref(),
+        bundle.as_os_str(),
+        "--no-absolute-names".as_ref(),
+        "-C".as_ref(),
+        parent.as_os_str(),
+        name.as_ref()
+    ])?;
+
+    Ok(())
+}
+
+/// Import bundle, placing its builds to the configured folders as if they were downloaded
+///
+/// Components index is replaced only if the current one doesn't list the bundled builds
+pub fn import(path: impl AsRef<Path>) -> anyhow::Result<BundleManifest> {
+    let path = path.as_ref();
+    let config = Config::get()?;
+
+    let manifest = read_manifest(path)?;
+
+    tracing::info!("Importing components bundle: {manifest:?}");
+
+    if let Some(components) = &manifest.components {
+        let wine_indexed = manifest.wine.iter()
+            .all(|name| matches!(wine::Group::find_in(&config.components.path, name), Ok(Some(_))));
+
+        let dxvk_groups = dxvk::get_groups(&config.components.path).unwrap_or_default();
+
+        let dxvk_indexed = manifest.dxvk.iter()
+            .all(|name| dxvk_groups.iter().any(|group| group.versions.iter().any(|version| &version.name == name)));
+
+        if !wine_indexed || !dxvk_indexed {
+            if let Some(parent) = config.components.path.parent() {
+                // Index is extracted next to the current one which is replaced
+                // only on success, so broken bundle doesn't leave the launcher without index
+                let temp = parent.join(".components-bundle");
+
+                if temp.exists() {
+                    std::fs::remove_dir_all(&temp)?;
+                }
+
+                let bundled = temp.join(components);
+
+                let backup = parent.join(".components-backup");
+
+                let result = extract(path, components, &temp).and_then(|_| {
+                    if !bundled.is_dir() {
+                        anyhow::bail!("Bundle doesn't contain components index: {components}");
+                    }
+
+                    if backup.exists() {
+                        std::fs::remove_dir_all(&backup)?;
+                    }
+
+                    if config.components.path.exists() {
+                        std::fs::rename(&config.components.path, &backup)?;
+                    }
+
+                    // Bundled index could have another folder name
+                    if let Err(err) = std::fs::rename(&bundled, &config.components.path) {
+                        if backup.exists() {
+                            std::fs::rename(&backup, &config.components.path)?;
+                        }
+
+                        return Err(err.into());
+                    }
+
+                    Ok(())
+                });
+
+                for folder in [&temp, &backup] {
+                    if folder.exists() {
+                        if let Err(err) = std::fs::remove_dir_all(folder) {
+                            tracing::warn!("Failed to remove temp bundle folder: {err}");
+                        }
+                    }
+                }
+
+                result?;
+            }
+        }
+    }
+
+    for name in &manifest.wine {
+        if config.game.wine.builds.join(name).exists() {
+            tracing::info!("Wine is already installed: {name}");
+        } else {
+            extract(path, name, &config.game.wine.builds)?;
+        }
+    }
+
+    for name in &manifest.dxvk {
+        if config.game.dxvk.builds.join(name).exists() {
+            tracing::info!("DXVK is already installed: {name}");
+        } else {
+            extract(path, name, &config.game.dxvk.builds)?;
+        }
+    }
+
+    Ok(manifest)
+}
@@ -0,0 +1,321 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use serde::{Serialize, Deserialize};
+use sha2::{Sha256, Digest};
+
+use anime_launcher_sdk::anime_game_core::prelude::{Installer, InstallerUpdate};
+use anime_launcher_sdk::anime_game_core::installer::downloader::Downloader;
+
+use anime_launcher_sdk::wuwa::config::Schema;
+
+/// Name of the file with checksums of the unpacked component's files
+pub const MANIFEST_FILE: &str = ".manifest.json";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ComponentKind {
+    Wine,
+    Dxvk,
+    Vkd3d,
+    Nvapi
+}
+
+impl ComponentKind {
+    /// Name of the components index folder with versions of this kind
+    #[inline]
+    pub fn folder(&self) -> &'static str {
+        match self {
+            Self::Wine => "wine",
+            Self::Dxvk => "dxvk",
+            Self::Vkd3d => "vkd3d",
+            Self::Nvapi => "dxvk-nvapi"
+        }
+    }
+
+    #[inline]
+    pub fn title(&self) -> &'static str {
+        match self {
+            Self::Wine => "Wine",
+            Self::Dxvk => "DXVK",
+            Self::Vkd3d => "VKD3D-Proton",
+            Self::Nvapi => "DXVK-NVAPI"
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum VerifyError {
+    Download(anyhow::Error),
+
+    /// Downloaded archive has different checksum
+    Mismatch {
+        expected: String,
+        actual: String
+    }
+}
+
+impl std::fmt::Display for VerifyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Download(err) => write!(f, "{err}"),
+
+            Self::Mismatch { expected, actual } => {
+                write!(f, "Archive checksum mismatch: expected {expected}, got {actual}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for VerifyError {}
+
+/// Checksums of the unpacked component's files
+///
+/// Stored in the component's folder as `.manifest.json`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Manifest {
+    /// Checksum of the archive the component was unpacked from
+    pub archive: Option<String>,
+
+    /// Archive was checked against the checksum from the components index.
+    /// Components index can have no checksum for the component, then it's installed unverified
+    pub verified: bool,
+
+    /// Relative file path -> checksum, or `symlink:<target>` for symlinks
+    pub files: BTreeMap<String, String>
+}
+
+/// Get SHA-256 checksum of the file
+pub fn file_checksum(path: impl AsRef<Path>) -> std::io::Result<String> {
+    let mut hasher = Sha256::new();
+
+    std::io::copy(&mut std::fs::File::open(path)?, &mut hasher)?;
+
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Get archive checksum of the component's version from the components index
+///
+/// Index stores it in the optional `sha256` field of the version
+pub fn get(components: impl AsRef<Path>, kind: ComponentKind, name: impl AsRef<str>) -> Option<String> {
+    let name = name.as_ref();
+
+    components.as_ref()
+        .join(kind.folder())
+        .read_dir().ok()?
+        .flatten()
+        .filter(|entry| entry.path().extension().map(|ext| ext == "json").unwrap_or(false))
+        .filter_map(|entry| std::fs::read(entry.path()).ok())
+        .filter_map(|versions| serde_json::from_slice::<Vec<serde_json::Value>>(&versions).ok())
+        .flatten()
+        .find(|version| version.get("name").and_then(|value| value.as_str()) == Some(name))
+        .and_then(|version| version.get("sha256")?.as_str().map(str::to_lowercase))
+}
+
+/// Download the component's archive to the temp folder and verify its checksum
+///
+/// Returns name of the verified archive file. Installer continues downloading
+/// of the existing file, so with this name it will only unpack the archive.
+/// Corrupted archive is deleted
+pub fn download(
+    uri: &str,
+    filename: Option<String>,
+    temp: &Path,
+    checksum: &str,
+    progress: impl Fn(u64, u64) + Send + 'static
+) -> Result<String, VerifyError> {
+    let filename = filename.unwrap_or_else(|| {
+        uri.rsplit('/').next().unwrap_or(uri).to_string()
+    });
+
+    let path = temp.join(&filename);
+
+    tracing::info!("Downloading component archive for verification: {uri}");
+
+    Downloader::new(uri)
+        .map_err(|err| VerifyError::Download(err.into()))?
+        .download(&path, progress)
+        .map_err(|err| VerifyError::Download(err.into()))?;
+
+    let actual = file_checksum(&path).map_err(|err| VerifyError::Download(err.into()))?;
+
+    if !actual.eq_ignore_ascii_case(checksum) {
+        tracing::error!("Component archive checksum mismatch: {path:?}");
+
+        if let Err(err) = std::fs::remove_file(&path) {
+            tracing::warn!("Failed to delete corrupted archive: {err}");
+        }
+
+        return Err(VerifyError::Mismatch {
+            expected: checksum.to_lowercase(),
+            actual
+        });
+    }
+
+    Ok(filename)
+}
+
+/// Verify the component's archive before it's unpacked by the installer
+///
+/// Does nothing if the components index has no checksum for the component.
+/// Returns installer which will only unpack the verified archive, and the archive's checksum
+pub fn verify_archive(
+    installer: Installer,
+    components: &Path,
+    kind: ComponentKind,
+    name: &str,
+    uri: &str,
+    temp: &Path,
+    progress: impl Fn(u64, u64) + Send + 'static
+) -> Result<(Installer, Option<String>), VerifyError> {
+    let Some(checksum) = get(components, kind, name) else {
+        tracing::warn!("Components index has no checksum for {name}, archive won't be verified");
+
+        return Ok((installer, None));
+    };
+
+    let filename = download(uri, None, temp, &checksum, progress)?;
+
+    Ok((installer.with_filename(filename), Some(checksum)))
+}
+
+/// Download, verify and unpack the component's archive to the builds folder
+///
+/// Archive verification progress is reported as `InstallerUpdate::DownloadingProgress`.
+/// Files manifest is written after unpacking
+pub fn install(
+    kind: ComponentKind,
+    name: &str,
+    uri: &str,
+    folder: &Path,
+    config: &Schema,
+    updater: impl Fn(InstallerUpdate) + Clone + Send + 'static
+) -> anyhow::Result<()> {
+    std::fs::create_dir_all(folder)?;
+
+    let temp = config.launcher.temp.clone().unwrap_or_else(std::env::temp_dir);
+
+    let installer = Installer::new(uri)?
+        .with_temp_folder(temp.clone());
+
+    let progress_updater = updater.clone();
+
+    let (mut installer, checksum) = verify_archive(installer, &config.components.path, kind, name, uri, &temp, move |current, total| {
+        progress_updater(InstallerUpdate::DownloadingProgress(current, total));
+    })?;
+
+    let error = Arc::new(Mutex::new(None));
+
+    installer.install(folder, {
+        let error = error.clone();
+
+        move |update| {
+            match &update {
+                InstallerUpdate::DownloadingError(err) => {
+                    *error.lock().unwrap() = Some(err.to_string());
+                }
+
+                InstallerUpdate::UnpackingError(err) => {
+                    *error.lock().unwrap() = Some(err.clone());
+                }
+
+                _ => ()
+            }
+
+            updater(update);
+        }
+    });
+
+    if let Some(err) = error.lock().unwrap().take() {
+        anyhow::bail!(err);
+    }
+
+    write_manifest(folder.join(name), checksum)?;
+
+    Ok(())
+}
+
+fn collect_files(root: &Path, folder: &Path, files: &mut BTreeMap<String, String>) -> anyhow::Result<()> {
+    for entry in folder.read_dir()?.flatten() {
+        let path = entry.path();
+        let kind = entry.file_type()?;
+
+        let relative = path.strip_prefix(root)?.to_string_lossy().to_string();
+
+        if relative == MANIFEST_FILE {
+            continue;
+        }
+
+        if kind.is_symlink() {
+            files.insert(relative, format!("symlink:{}", std::fs::read_link(&path)?.to_string_lossy()));
+        }
+
+        else if kind.is_dir() {
+            collect_files(root, &path, files)?;
+        }
+
+        else {
+            files.insert(relative, file_checksum(&path)?);
+        }
+    }
+
+    Ok(())
+}
+
+/// Store checksums of the unpacked component's files in its folder
+pub fn write_manifest(folder: impl AsRef<Path>, archive: Option<String>) -> anyhow::Result<()> {
+    let folder = folder.as_ref();
+
+    let mut manifest = Manifest {
+        verified: archive.is_some(),
+        archive,
+        ..Manifest::default()
+    };
+
+    collect_files(folder, folder, &mut manifest.files)?;
+
+    std::fs::write(folder.join(MANIFEST_FILE), serde_json::to_string_pretty(&manifest)?)?;
+
+    Ok(())
+}
+
+/// Result of the unpacked component's files check
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Verification {
+    /// Changed, missing and unexpected files
+    pub broken: Vec<PathBuf>,
+
+    /// Component was unpacked from the verified archive
+    pub verified: bool
+}
+
+/// Check unpacked component's files against its manifest
+pub fn verify_installed(folder: impl AsRef<Path>) -> anyhow::Result<Verification> {
+    let folder = folder.as_ref();
+    let manifest_path = folder.join(MANIFEST_FILE);
+
+    if !manifest_path.exists() {
+        anyhow::bail!("Component has no files manifest. Reinstall it to verify its files later");
+    }
+
+    let manifest = serde_json::from_slice::<Manifest>(&std::fs::read(manifest_path)?)?;
+
+    let mut files = BTreeMap::new();
+
+    collect_files(folder, folder, &mut files)?;
+
+    let mut broken = manifest.files.iter()
+        .filter(|(path, checksum)| files.get(*path) != Some(checksum))
+        .map(|(path, _)| PathBuf::from(path))
+        .collect::<Vec<_>>();
+
+    broken.extend(files.keys()
+        .filter(|path| !manifest.files.contains_key(*path))
+        .map(PathBuf::from));
+
+    Ok(Verification {
+        broken,
+        verified: manifest.verified
+    })
+}
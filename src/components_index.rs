@@ -0,0 +1,88 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anime_launcher_sdk::components::loader::ComponentsLoader;
+
+use anime_launcher_sdk::config::ConfigExt;
+use anime_launcher_sdk::wuwa::config::Config;
+
+use crate::settings::prelude::*;
+
+/// Sync components index from the given server
+///
+/// Custom wine builds are removed from the index before syncing
+/// and the sync result is remembered to be shown in the preferences
+pub fn sync(components: &ComponentsLoader, host: impl AsRef<str>) -> anyhow::Result<Vec<String>> {
+    let host = host.as_ref();
+
+    crate::custom_wine::unregister()?;
+
+    let result = components.sync(host);
+
+    crate::custom_wine::reregister();
+
+    if let Ok(changes) = &result {
+        let synced_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|time| time.as_secs())
+            .unwrap_or_default();
+
+        let result = Settings::modify(|settings| {
+            settings.index_syncs.insert(host.to_string(), IndexSync {
+                synced_at,
+                changes: changes.clone()
+            });
+        });
+
+        if let Err(err) = result {
+            tracing::error!("Failed to save components index sync: {err}");
+        }
+    }
+
+    result
+}
+
+/// Sync components index from the first available server, even if it's already synced
+///
+/// Returns the server used and the changes list
+pub fn sync_now() -> anyhow::Result<(String, Vec<String>)> {
+    let config = Config::get()?;
+
+    let components = ComponentsLoader::new(&config.components.path);
+
+    let mut result = Err(anyhow::anyhow!("No components index servers set"));
+
+    for host in &config.components.servers {
+        match sync(&components, host) {
+            Ok(changes) => return Ok((host.clone(), changes)),
+
+            Err(err) => {
+                tracing::error!("Failed to sync components index from {host}: {err}");
+
+                result = Err(err);
+            }
+        }
+    }
+
+    result
+}
+
+/// Check and normalize components index server address
+///
+/// Local folders are converted to `file://` URIs
+pub fn normalize_server(server: impl AsRef<str>) -> anyhow::Result<String> {
+    let server = server.as_ref().trim();
+
+    if server.starts_with('/') {
+        return Ok(format!("file://{server}"));
+    }
+
+    let supported = ["https://", "http://", "file://", "ssh://", "git@"]
+        .iter()
+        .any(|prefix| server.starts_with(prefix));
+
+    if !supported || server.len() < 8 {
+        anyhow::bail!("Unsupported components index server address: {server:?}");
+    }
+
+    Ok(server.to_string())
+}
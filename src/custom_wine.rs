@@ -0,0 +1,265 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::Mutex;
+
+use anime_launcher_sdk::components::*;
+
+use anime_launcher_sdk::config::ConfigExt;
+use anime_launcher_sdk::wuwa::config::{Config, Schema};
+
+use crate::settings::prelude::*;
+
+/// Name of the components index group with custom wine builds
+pub const GROUP_NAME: &str = "custom";
+
+/// Amount of the SDK calls which need custom builds registered in the components index
+static INDEX_USERS: Mutex<usize> = Mutex::new(0);
+
+/// Get version of the wine build from the given folder by running `bin/wine --version`
+pub fn probe(path: impl AsRef<Path>) -> anyhow::Result<String> {
+    let binary = path.as_ref().join("bin/wine");
+
+    if !binary.exists() {
+        anyhow::bail!("Wine binary not found: {binary:?}");
+    }
+
+    let output = Command::new(&binary).arg("--version").output()?;
+    let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
+
+    if !output.status.success() || version.is_empty() {
+        anyhow::bail!("Failed to get wine version: {}", String::from_utf8_lossy(&output.stderr).trim());
+    }
+
+    Ok(version)
+}
+
+/// Add wine build from the local folder under the given name
+///
+/// Build is linked to the wine builds folder and remembered in the launcher settings,
+/// so it can be selected and used like the downloaded ones
+pub fn add(name: impl AsRef<str>, path: impl Into<PathBuf>) -> anyhow::Result<CustomWine> {
+    let name = name.as_ref().trim().to_string();
+    let path = path.into().canonicalize()?;
+
+    if name.is_empty() || name.starts_with('.') || name.contains('/') {
+        anyhow::bail!("Invalid wine build name: {name:?}");
+    }
+
+    let config = Config::get()?;
+
+    let link = config.game.wine.builds.join(&name);
+
+    let indexed = wine::get_groups(&config.components.path)
+        .unwrap_or_default()
+        .into_iter()
+        .any(|group| group.versions.iter().any(|version| version.name == name));
+
+    if indexed || link.symlink_metadata().is_ok() {
+        anyhow::bail!("Wine build with name {name:?} already exists");
+    }
+
+    let wine = CustomWine {
+        version: probe(&path)?,
+        name,
+        path
+    };
+
+    tracing::info!("Adding custom wine build: {wine:?}");
+
+    std::fs::create_dir_all(&config.game.wine.builds)?;
+    std::os::unix::fs::symlink(&wine.path, &link)?;
+
+    Settings::modify(|settings| settings.custom_wine.push(wine.clone()))?;
+
+    Ok(wine)
+}
+
+/// Remove custom wine build
+///
+/// Only the link in the wine builds folder is deleted, the build's folder stays untouched
+pub fn remove(name: impl AsRef<str>) -> anyhow::Result<()> {
+    let name = name.as_ref();
+
+    let mut config = Config::get()?;
+
+    tracing::info!("Removing custom wine build: {name}");
+
+    let link = config.game.wine.builds.join(name);
+
+    if link.symlink_metadata().map(|meta| meta.file_type().is_symlink()).unwrap_or(false) {
+        std::fs::remove_file(&link)?;
+    }
+
+    Settings::modify(|settings| settings.custom_wine.retain(|wine| wine.name != name))?;
+
+    if config.game.wine.selected.as_deref() == Some(name) {
+        config.game.wine.selected = None;
+
+        Config::update_raw(config)?;
+    }
+
+    Ok(())
+}
+
+/// Get components index version of the custom wine build
+pub fn to_version(wine: &CustomWine) -> anyhow::Result<wine::Version> {
+    Ok(serde_json::from_value(serde_json::json!({
+        "name": wine.name,
+        "title": wine.name,
+        "uri": wine.path.to_string_lossy()
+    }))?)
+}
+
+/// Get versions of the custom wine builds which are still linked to the wine builds folder
+pub fn versions(builds: &Path) -> Vec<wine::Version> {
+    Settings::get()
+        .map(|settings| settings.custom_wine)
+        .unwrap_or_default()
+        .iter()
+        .filter(|wine| builds.join(&wine.name).exists())
+        .flat_map(to_version)
+        .collect()
+}
+
+/// Get selected wine version from the components index or the custom builds
+pub fn get_selected(config: &Schema) -> anyhow::Result<Option<wine::Version>> {
+    if let Some(wine) = config.get_selected_wine()? {
+        return Ok(Some(wine));
+    }
+
+    let Some(selected) = &config.game.wine.selected else {
+        return Ok(None);
+    };
+
+    Settings::get()?.custom_wine.iter()
+        .find(|wine| &wine.name == selected)
+        .map(to_version)
+        .transpose()
+}
+
+/// Remove custom wine builds group from the components index
+fn unregister_index(components: &Path) -> anyhow::Result<()> {
+    let groups_path = components.join("wine.json");
+
+    if !groups_path.exists() {
+        return Ok(());
+    }
+
+    let mut groups: Vec<serde_json::Value> = serde_json::from_slice(&std::fs::read(&groups_path)?)?;
+
+    let registered = groups.iter()
+        .any(|group| group.get("name").and_then(|name| name.as_str()) == Some(GROUP_NAME));
+
+    if registered {
+        // Restore the original file so it won't conflict with the index updates
+        let restored = Command::new("git")
+            .args(["checkout", "--", "wine.json"])
+            .current_dir(components)
+            .output()
+            .map(|output| output.status.success())
+            .unwrap_or(false);
+
+        if !restored {
+            groups.retain(|group| group.get("name").and_then(|name| name.as_str()) != Some(GROUP_NAME));
+
+            std::fs::write(&groups_path, serde_json::to_string_pretty(&groups)?)?;
+        }
+    }
+
+    let versions_path = components.join("wine").join(format!("{GROUP_NAME}.json"));
+
+    if versions_path.exists() {
+        std::fs::remove_file(versions_path)?;
+    }
+
+    Ok(())
+}
+
+/// Write custom wine builds from the launcher settings to the components index
+fn register_index() -> anyhow::Result<()> {
+    let config = Config::get()?;
+    let builds = Settings::get()?.custom_wine;
+
+    let components = config.components.path.as_path();
+    let groups_path = components.join("wine.json");
+
+    unregister_index(components)?;
+
+    // Index is not synced yet, builds will be registered on the next SDK call
+    if builds.is_empty() || !groups_path.exists() {
+        return Ok(());
+    }
+
+    let mut groups: Vec<serde_json::Value> = serde_json::from_slice(&std::fs::read(&groups_path)?)?;
+
+    groups.push(serde_json::json!({
+        "name": GROUP_NAME,
+        "title": "Custom"
+    }));
+
+    let versions = builds.iter()
+        .map(|wine| serde_json::json!({
+            "name": wine.name,
+            "title": wine.name,
+            "uri": wine.path.to_string_lossy()
+        }))
+        .collect::<Vec<_>>();
+
+    std::fs::write(&groups_path, serde_json::to_string_pretty(&groups)?)?;
+    std::fs::write(components.join("wine").join(format!("{GROUP_NAME}.json")), serde_json::to_string_pretty(&versions)?)?;
+
+    Ok(())
+}
+
+/// Keeps custom wine builds registered in the components index until dropped
+pub struct IndexRegistration(());
+
+impl Drop for IndexRegistration {
+    fn drop(&mut self) {
+        let mut users = INDEX_USERS.lock().unwrap_or_else(|err| err.into_inner());
+
+        *users = users.saturating_sub(1);
+
+        if *users == 0 {
+            if let Err(err) = unregister() {
+                tracing::error!("Failed to remove custom wine builds from the components index: {err}");
+            }
+        }
+    }
+}
+
+/// Register custom wine builds in the components index for the SDK calls
+/// like the game launching, which look the selected wine up only there
+///
+/// Builds are stored in the launcher settings, so the index repository
+/// is changed only while the returned value is alive
+pub fn register() -> IndexRegistration {
+    let mut users = INDEX_USERS.lock().unwrap_or_else(|err| err.into_inner());
+
+    if *users == 0 {
+        if let Err(err) = register_index() {
+            tracing::error!("Failed to register custom wine builds in the components index: {err}");
+        }
+    }
+
+    *users += 1;
+
+    IndexRegistration(())
+}
+
+/// Register custom wine builds again after the components index was synced,
+/// if any SDK call still needs them
+pub fn reregister() {
+    let users = INDEX_USERS.lock().unwrap_or_else(|err| err.into_inner());
+
+    if *users > 0 {
+        if let Err(err) = register_index() {
+            tracing::error!("Failed to register custom wine builds in the components index: {err}");
+        }
+    }
+}
+
+/// Remove custom wine builds from the components index, restoring its original state
+pub fn unregister() -> anyhow::Result<()> {
+    unregister_index(&Config::get()?.components.path)
+}
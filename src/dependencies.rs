@@ -0,0 +1,274 @@
+use anime_launcher_sdk::is_available;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Distro {
+    Arch,
+    Debian,
+    Fedora,
+    OpenSuse,
+    Void,
+    NixOS,
+    Gentoo
+}
+
+impl Distro {
+    #[inline]
+    pub fn list() -> &'static [Self] {
+        &[
+            Self::Arch,
+            Self::Debian,
+            Self::Fedora,
+            Self::OpenSuse,
+            Self::Void,
+            Self::NixOS,
+            Self::Gentoo
+        ]
+    }
+
+    /// Identify current distro or its parent (e.g. Debian for Ubuntu)
+    pub fn detect() -> Option<Self> {
+        let distro = whatadistro::identify()?;
+
+        Self::list().iter().copied().find(|known| {
+            distro.is_similar(match known {
+                Self::Arch     => "arch",
+                Self::Debian   => "debian",
+                Self::Fedora   => "fedora",
+                Self::OpenSuse => "opensuse",
+                Self::Void     => "void",
+                Self::NixOS    => "nixos",
+                Self::Gentoo   => "gentoo"
+            })
+        })
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            Self::Arch     => "Arch (pacman)",
+            Self::Debian   => "Debian / Ubuntu (apt)",
+            Self::Fedora   => "Fedora (dnf)",
+            Self::OpenSuse => "openSUSE (zypper)",
+            Self::Void     => "Void (xbps)",
+            Self::NixOS    => "NixOS (nix-env)",
+            Self::Gentoo   => "Gentoo (emerge)"
+        }
+    }
+
+    /// Get command which installs given packages
+    pub fn install_command(&self, packages: &[&str]) -> String {
+        let packages = packages.join(" ");
+
+        match self {
+            Self::Arch     => format!("sudo pacman -S --needed {packages}"),
+            Self::Debian   => format!("sudo apt install {packages}"),
+            Self::Fedora   => format!("sudo dnf install {packages}"),
+            Self::OpenSuse => format!("sudo zypper install {packages}"),
+            Self::Void     => format!("sudo xbps-install -S {packages}"),
+            Self::Gentoo   => format!("sudo emerge --ask {packages}"),
+
+            Self::NixOS => {
+                let packages = packages.split(' ')
+                    .map(|package| format!("nixos.{package}"))
+                    .collect::<Vec<_>>()
+                    .join(" ");
+
+                format!("nix-env -iA {packages}")
+            }
+        }
+    }
+}
+
+/// Names of the package which provides a dependency in different distros
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Packages {
+    pub arch: &'static str,
+    pub debian: &'static str,
+    pub fedora: &'static str,
+    pub opensuse: &'static str,
+    pub void: &'static str,
+    pub nixos: &'static str,
+    pub gentoo: &'static str
+}
+
+impl Packages {
+    /// Use the same package name for all the distros
+    pub const fn same(name: &'static str, gentoo: &'static str) -> Self {
+        Self {
+            arch: name,
+            debian: name,
+            fedora: name,
+            opensuse: name,
+            void: name,
+            nixos: name,
+            gentoo
+        }
+    }
+
+    pub fn get(&self, distro: Distro) -> &'static str {
+        match distro {
+            Distro::Arch     => self.arch,
+            Distro::Debian   => self.debian,
+            Distro::Fedora   => self.fedora,
+            Distro::OpenSuse => self.opensuse,
+            Distro::Void     => self.void,
+            Distro::NixOS    => self.nixos,
+            Distro::Gentoo   => self.gentoo
+        }
+    }
+}
+
+/// External program the launcher runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Dependency {
+    /// Executables which can be used, in order of preference
+    pub executables: &'static [&'static str],
+
+    /// Launcher can't work without required dependencies
+    pub required: bool,
+
+    /// Translation key of the feature provided by this dependency
+    pub feature: &'static str,
+
+    pub packages: Packages
+}
+
+impl Dependency {
+    #[inline]
+    pub fn name(&self) -> &'static str {
+        self.executables[0]
+    }
+
+    #[inline]
+    pub fn is_available(&self) -> bool {
+        self.executables.iter().any(|executable| is_available(executable))
+    }
+}
+
+pub const DEPENDENCIES: &[Dependency] = &[
+    Dependency {
+        executables: &["git"],
+        required: true,
+        feature: "dependency-components-index",
+        packages: Packages::same("git", "dev-vcs/git")
+    },
+
+    Dependency {
+        executables: &["7z", "7za"],
+        required: true,
+        feature: "dependency-unpacking",
+        packages: Packages {
+            arch: "p7zip",
+            debian: "p7zip-full",
+            fedora: "p7zip-plugins",
+            opensuse: "p7zip-full",
+            void: "p7zip",
+            nixos: "p7zip",
+            gentoo: "app-arch/p7zip"
+        }
+    },
+
+    Dependency {
+        executables: &["tar"],
+        required: true,
+        feature: "dependency-unpacking",
+        packages: Packages::same("tar", "app-arch/tar")
+    },
+
+    Dependency {
+        executables: &["unzip"],
+        required: true,
+        feature: "dependency-unpacking",
+        packages: Packages::same("unzip", "app-arch/unzip")
+    },
+
+    Dependency {
+        executables: &["xdelta3"],
+        required: false,
+        feature: "dependency-game-patches",
+        packages: Packages {
+            arch: "xdelta3",
+            debian: "xdelta3",
+            fedora: "xdelta",
+            opensuse: "xdelta3",
+            void: "xdelta3",
+            nixos: "xdelta",
+            gentoo: "dev-util/xdelta"
+        }
+    },
+
+    Dependency {
+        executables: &["pkexec"],
+        required: false,
+        feature: "dependency-telemetry",
+        packages: Packages {
+            arch: "polkit",
+            debian: "pkexec",
+            fedora: "polkit",
+            opensuse: "polkit",
+            void: "polkit",
+            nixos: "polkit",
+            gentoo: "sys-auth/polkit"
+        }
+    },
+
+    Dependency {
+        executables: &["bwrap"],
+        required: false,
+        feature: "dependency-sandbox",
+        packages: Packages::same("bubblewrap", "sys-apps/bubblewrap")
+    },
+
+    Dependency {
+        executables: &["gamescope"],
+        required: false,
+        feature: "dependency-gamescope",
+        packages: Packages::same("gamescope", "gui-wm/gamescope")
+    },
+
+    Dependency {
+        executables: &["gamemoderun"],
+        required: false,
+        feature: "dependency-gamemode",
+        packages: Packages::same("gamemode", "games-util/gamemode")
+    },
+
+    Dependency {
+        executables: &["winetricks"],
+        required: false,
+        feature: "dependency-winetricks",
+        packages: Packages::same("winetricks", "app-emulation/winetricks")
+    },
+
+    Dependency {
+        executables: &["mangohud"],
+        required: false,
+        feature: "dependency-mangohud",
+        packages: Packages {
+            arch: "mangohud",
+            debian: "mangohud",
+            fedora: "mangohud",
+            opensuse: "mangohud",
+            void: "MangoHud",
+            nixos: "mangohud",
+            gentoo: "games-util/mangohud"
+        }
+    }
+];
+
+/// Get dependencies which are not installed in the system
+pub fn missing() -> Vec<&'static Dependency> {
+    DEPENDENCIES.iter()
+        .filter(|dependency| !dependency.is_available())
+        .collect()
+}
+
+/// Get command which installs given dependencies in the given distro
+pub fn install_command(distro: Distro, dependencies: &[&Dependency]) -> String {
+    let mut packages = dependencies.iter()
+        .map(|dependency| dependency.packages.get(distro))
+        .collect::<Vec<_>>();
+
+    packages.dedup();
+
+    distro.install_command(&packages)
+}
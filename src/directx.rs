@@ -0,0 +1,246 @@
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use anime_launcher_sdk::wincompatlib::prelude::*;
+
+use anime_launcher_sdk::components::wine::UnifiedWine;
+
+use anime_launcher_sdk::config::ConfigExt;
+use anime_launcher_sdk::wuwa::config::{Config, Schema};
+
+use crate::checksums::ComponentKind;
+use crate::settings::prelude::*;
+
+/// Environment variable which enables NVAPI support in DXVK
+const NVAPI_ENV: &str = "DXVK_ENABLE_NVAPI";
+
+/// VKD3D-Proton or DXVK-NVAPI version from the components index
+///
+/// Versions are stored in `<kind>/<group>.json` files like the DXVK ones
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Version {
+    pub name: String,
+    pub title: String,
+    pub uri: String,
+
+    #[serde(default)]
+    pub format: Option<String>,
+
+    #[serde(default = "default_recommended")]
+    pub recommended: bool
+}
+
+#[inline]
+fn default_recommended() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Group {
+    pub name: String,
+    pub title: String,
+    pub versions: Vec<Version>
+}
+
+#[derive(Deserialize)]
+struct GroupInfo {
+    name: String,
+    title: String
+}
+
+/// Get DLLs installed by the component and their archive folders
+/// for 64 and 32 bit system folders
+fn dlls(kind: ComponentKind) -> (&'static [&'static str], [(&'static str, &'static str); 2]) {
+    match kind {
+        ComponentKind::Nvapi => (&["nvapi64", "nvapi"], [("x64", "system32"), ("x32", "syswow64")]),
+
+        // Wine and DXVK are not installed from here
+        _ => (&["d3d12", "d3d12core"], [("x64", "system32"), ("x86", "syswow64")])
+    }
+}
+
+/// Get folder with downloaded builds of the given kind from the launcher settings
+pub fn builds_folder(kind: ComponentKind) -> PathBuf {
+    Settings::get()
+        .map(|settings| settings.directx.builds)
+        .unwrap_or_default()
+        .get(kind)
+        .to_path_buf()
+}
+
+/// Get groups of the given kind from the components index
+///
+/// Index without these components gives an empty list
+pub fn get_groups(components: impl AsRef<Path>, kind: ComponentKind) -> anyhow::Result<Vec<Group>> {
+    let components = components.as_ref();
+    let groups_path = components.join(format!("{}.json", kind.folder()));
+
+    if !groups_path.exists() {
+        return Ok(vec![]);
+    }
+
+    let groups: Vec<GroupInfo> = serde_json::from_slice(&std::fs::read(groups_path)?)?;
+
+    groups.into_iter()
+        .map(|group| {
+            let versions = std::fs::read(components.join(kind.folder()).join(format!("{}.json", group.name)))?;
+
+            Ok(Group {
+                versions: serde_json::from_slice(&versions)?,
+                name: group.name,
+                title: group.title
+            })
+        })
+        .collect()
+}
+
+/// Get downloaded versions of the given kind
+pub fn get_downloaded(components: impl AsRef<Path>, kind: ComponentKind) -> anyhow::Result<Vec<Version>> {
+    let builds = builds_folder(kind);
+
+    Ok(get_groups(components, kind)?
+        .into_iter()
+        .flat_map(|group| group.versions)
+        .filter(|version| builds.join(&version.name).exists())
+        .collect())
+}
+
+/// Get selected wine with the prefix applied, to install components to
+pub fn prefix_wine(config: &Schema) -> anyhow::Result<Wine> {
+    let wine = match crate::custom_wine::get_selected(config)? {
+        Some(wine) => match wine.to_wine(&config.components.path, Some(config.game.wine.builds.join(&wine.name))) {
+            UnifiedWine::Default(wine) => wine,
+            UnifiedWine::Proton(_) => anyhow::bail!("Components can't be installed with proton builds")
+        }
+
+        None => Wine::default()
+    };
+
+    Ok(wine.with_prefix(&config.game.wine.prefix))
+}
+
+fn set_overrides(wine: &Wine, config: &Schema, dlls: &[&str], native: bool) -> anyhow::Result<()> {
+    for dll in dlls {
+        let command = if native {
+            vec!["reg", "add", "HKCU\\Software\\Wine\\DllOverrides", "/v", *dll, "/d", "native", "/f"]
+        } else {
+            vec!["reg", "delete", "HKCU\\Software\\Wine\\DllOverrides", "/v", *dll, "/f"]
+        };
+
+        // Deleting not existing override fails, so the status is not checked
+        wine.run_args_with_env(command, config.game.wine.sync.get_env_vars())?.wait()?;
+    }
+
+    Ok(())
+}
+
+fn remove_files(prefix: &Path, kind: ComponentKind) -> anyhow::Result<()> {
+    let (dlls, folders) = dlls(kind);
+
+    for (_, system) in folders {
+        for dll in dlls {
+            let path = prefix.join("drive_c/windows").join(system).join(format!("{dll}.dll"));
+
+            if path.exists() {
+                std::fs::remove_file(path)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Copy the build's DLLs to the wine prefix and set their overrides
+///
+/// Unlike `install` it doesn't change the settings
+pub fn install_to_prefix(kind: ComponentKind, name: impl AsRef<str>, config: &Schema) -> anyhow::Result<()> {
+    let build = builds_folder(kind).join(name.as_ref());
+    let wine = prefix_wine(config)?;
+
+    let (dlls, folders) = dlls(kind);
+
+    for (arch, system) in folders {
+        let target = config.game.wine.prefix.join("drive_c/windows").join(system);
+
+        // 32 bit DLLs can be missing in some builds
+        if !build.join(arch).exists() {
+            continue;
+        }
+
+        for dll in dlls {
+            let source = build.join(arch).join(format!("{dll}.dll"));
+
+            if source.exists() {
+                std::fs::copy(source, target.join(format!("{dll}.dll")))?;
+            }
+        }
+    }
+
+    set_overrides(&wine, config, dlls, true)?;
+
+    wine.shutdown()?;
+
+    Ok(())
+}
+
+/// Remember the build as selected, or unselect it, without touching the wine prefix
+///
+/// VKD3D-Proton disables DX11 mode so the game runs with DX12,
+/// DXVK-NVAPI enables NVAPI support in DXVK
+pub fn set_selected(kind: ComponentKind, name: Option<String>, config: &mut Schema, settings: &mut Settings) {
+    match kind {
+        ComponentKind::Nvapi => {
+            if name.is_some() {
+                config.game.environment.insert(NVAPI_ENV.to_string(), String::from("1"));
+            } else {
+                config.game.environment.remove(NVAPI_ENV);
+            }
+
+            settings.directx.nvapi = name;
+        }
+
+        _ => {
+            config.game.enhancements.dx11 = name.is_none();
+
+            settings.directx.vkd3d = name;
+        }
+    }
+}
+
+/// Install downloaded build to the wine prefix and remember it as selected
+pub fn install(kind: ComponentKind, name: impl AsRef<str>) -> anyhow::Result<()> {
+    let name = name.as_ref();
+
+    let mut config = Config::get()?;
+
+    tracing::info!("Installing {} {name} to the wine prefix", kind.title());
+
+    install_to_prefix(kind, name, &config)?;
+
+    Settings::modify(|settings| set_selected(kind, Some(name.to_string()), &mut config, settings))?;
+
+    Config::update_raw(config)?;
+
+    Ok(())
+}
+
+/// Remove installed build from the wine prefix
+pub fn uninstall(kind: ComponentKind) -> anyhow::Result<()> {
+    let mut config = Config::get()?;
+
+    tracing::info!("Removing {} from the wine prefix", kind.title());
+
+    let wine = prefix_wine(&config)?;
+
+    set_overrides(&wine, &config, dlls(kind).0, false)?;
+    remove_files(&config.game.wine.prefix, kind)?;
+
+    wine.shutdown()?;
+
+    Settings::modify(|settings| set_selected(kind, None, &mut config, settings))?;
+
+    Config::update_raw(config)?;
+
+    Ok(())
+}
@@ -0,0 +1,216 @@
+use std::path::{Path, PathBuf};
+
+use anime_launcher_sdk::components::*;
+
+use anime_launcher_sdk::config::ConfigExt;
+use anime_launcher_sdk::wuwa::config::Config;
+
+use crate::checksums::ComponentKind;
+use crate::settings::prelude::*;
+
+/// Wine, DXVK, VKD3D-Proton or DXVK-NVAPI build from the builds folder
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DownloadedBuild {
+    pub kind: ComponentKind,
+    pub name: String,
+    pub path: PathBuf,
+
+    /// Size of the build's folder in bytes
+    pub size: u64,
+
+    pub selected: bool,
+
+    /// Build selected before the last components upgrade, kept for rollback
+    pub rollback: bool,
+
+    /// Custom builds are links to the user's folders and are never removed
+    pub custom: bool
+}
+
+/// Archive left in the temp folder by an interrupted download
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TempLeftover {
+    pub path: PathBuf,
+    pub size: u64
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DiskUsage {
+    pub builds: Vec<DownloadedBuild>,
+    pub leftovers: Vec<TempLeftover>
+}
+
+impl DiskUsage {
+    /// Size of the builds which can be removed
+    pub fn unused_size(&self) -> u64 {
+        self.builds.iter()
+            .filter(|build| build.is_unused())
+            .map(|build| build.size)
+            .sum()
+    }
+}
+
+impl DownloadedBuild {
+    #[inline]
+    pub fn is_unused(&self) -> bool {
+        !self.selected && !self.rollback && !self.custom
+    }
+}
+
+/// Get size of the file or folder, not following symlinks
+pub fn size(path: impl AsRef<Path>) -> u64 {
+    let path = path.as_ref();
+
+    let Ok(metadata) = path.symlink_metadata() else {
+        return 0;
+    };
+
+    if metadata.is_symlink() {
+        0
+    }
+
+    else if metadata.is_dir() {
+        path.read_dir()
+            .map(|entries| entries.flatten().map(|entry| size(entry.path())).sum())
+            .unwrap_or(0)
+    }
+
+    else {
+        metadata.len()
+    }
+}
+
+fn list_builds(kind: ComponentKind, folder: &Path, selected: Option<&str>, rollback: Option<&str>) -> Vec<DownloadedBuild> {
+    let Ok(entries) = folder.read_dir() else {
+        return vec![];
+    };
+
+    let mut builds = entries.flatten()
+        .filter(|entry| entry.path().is_dir())
+        .map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let custom = entry.file_type().map(|kind| kind.is_symlink()).unwrap_or(false);
+
+            DownloadedBuild {
+                kind,
+                selected: selected == Some(name.as_str()),
+                rollback: rollback == Some(name.as_str()),
+                size: if custom { 0 } else { size(entry.path()) },
+                path: entry.path(),
+                name,
+                custom
+            }
+        })
+        .collect::<Vec<_>>();
+
+    builds.sort_by(|a, b| a.name.cmp(&b.name));
+
+    builds
+}
+
+/// Get archive names which can be downloaded to the temp folder by the components installers
+fn archive_names(components: &Path) -> Vec<String> {
+    let wine = wine::get_groups(components)
+        .unwrap_or_default()
+        .into_iter()
+        .flat_map(|group| group.versions)
+        .map(|version| (version.name, version.uri, version.format));
+
+    let dxvk = dxvk::get_groups(components)
+        .unwrap_or_default()
+        .into_iter()
+        .flat_map(|group| group.versions)
+        .map(|version| (version.name, version.uri, version.format));
+
+    let directx = [ComponentKind::Vkd3d, ComponentKind::Nvapi]
+        .into_iter()
+        .flat_map(|kind| crate::directx::get_groups(components, kind).unwrap_or_default())
+        .flat_map(|group| group.versions)
+        .map(|version| (version.name, version.uri, version.format));
+
+    wine.chain(dxvk)
+        .chain(directx)
+        .flat_map(|(name, uri, format)| {
+            let mut names = vec![uri.rsplit('/').next().unwrap_or(&uri).to_string()];
+
+            if let Some(format) = format {
+                names.push(format!("{name}.{format}"));
+            }
+
+            names
+        })
+        .collect()
+}
+
+/// Get disk usage of the downloaded components builds, and of the temp leftovers
+pub fn get() -> anyhow::Result<DiskUsage> {
+    let config = Config::get()?;
+
+    let selected_dxvk = config.get_selected_dxvk()?.map(|dxvk| dxvk.name);
+
+    let settings = Settings::get()?;
+
+    let upgrade = &settings.components_upgrade;
+    let directx = &settings.directx;
+
+    let mut builds = list_builds(ComponentKind::Wine, &config.game.wine.builds, config.game.wine.selected.as_deref(), upgrade.previous_wine.as_deref());
+
+    builds.extend(list_builds(ComponentKind::Dxvk, &config.game.dxvk.builds, selected_dxvk.as_deref(), upgrade.previous_dxvk.as_deref()));
+
+    builds.extend(list_builds(ComponentKind::Vkd3d, &crate::directx::builds_folder(ComponentKind::Vkd3d), directx.vkd3d.as_deref(), None));
+    builds.extend(list_builds(ComponentKind::Nvapi, &crate::directx::builds_folder(ComponentKind::Nvapi), directx.nvapi.as_deref(), None));
+
+    let temp = config.launcher.temp.unwrap_or_else(std::env::temp_dir);
+
+    // Only files which match the components archives are listed
+    // because the temp folder can be shared with other programs
+    let leftovers = archive_names(&config.components.path)
+        .into_iter()
+        .map(|name| temp.join(name))
+        .filter(|path| path.is_file())
+        .map(|path| TempLeftover {
+            size: size(&path),
+            path
+        })
+        .collect();
+
+    Ok(DiskUsage {
+        builds,
+        leftovers
+    })
+}
+
+/// Remove the build's folder
+pub fn remove_build(build: &DownloadedBuild) -> anyhow::Result<()> {
+    if build.custom {
+        anyhow::bail!("Custom builds can't be removed here");
+    }
+
+    tracing::info!("Removing build: {:?}", build.path);
+
+    std::fs::remove_dir_all(&build.path)?;
+
+    Ok(())
+}
+
+/// Remove all the builds which are not selected or kept for rollback, returning freed space in bytes
+pub fn remove_unused() -> anyhow::Result<u64> {
+    let mut freed = 0;
+
+    for build in get()?.builds.into_iter().filter(DownloadedBuild::is_unused) {
+        remove_build(&build)?;
+
+        freed += build.size;
+    }
+
+    Ok(freed)
+}
+
+/// Remove the temp leftover
+pub fn remove_leftover(leftover: &TempLeftover) -> anyhow::Result<()> {
+    tracing::info!("Removing temp leftover: {:?}", leftover.path);
+
+    std::fs::remove_file(&leftover.path)?;
+
+    Ok(())
+}
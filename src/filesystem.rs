@@ -0,0 +1,188 @@
+use std::path::{Path, PathBuf};
+use std::ffi::OsString;
+use std::os::unix::ffi::OsStringExt;
+
+use anime_launcher_sdk::anime_game_core::installer::free_space;
+
+use crate::tr;
+
+/// Filesystems which don't support unix permissions and symlinks needed by wine
+pub const UNSUPPORTED_FILESYSTEMS: &[&str] = &["ntfs", "ntfs3", "fuseblk", "exfat", "vfat", "msdos"];
+
+/// Problem with the folder chosen for the launcher files
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathWarning {
+    NotWritable,
+
+    NotEnoughSpace {
+        required: u64,
+        available: u64
+    },
+
+    /// Filesystem which is known to break wine prefixes
+    Filesystem(String),
+
+    CaseInsensitive,
+
+    /// Folder is mounted with `noexec` option so files from it can't be run
+    NoExec
+}
+
+impl PathWarning {
+    /// Critical problems don't allow to use the folder at all
+    pub fn is_critical(&self) -> bool {
+        matches!(self, Self::NotWritable | Self::NotEnoughSpace { .. } | Self::NoExec)
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            Self::NotWritable => tr!("path-not-writable"),
+
+            Self::NotEnoughSpace { required, available } => tr!("path-not-enough-space", {
+                "required" = format!("{:.2}", *required as f64 / 1024.0 / 1024.0 / 1024.0),
+                "available" = format!("{:.2}", *available as f64 / 1024.0 / 1024.0 / 1024.0)
+            }),
+
+            Self::Filesystem(filesystem) => tr!("path-unsupported-filesystem", {
+                "filesystem" = filesystem.clone()
+            }),
+
+            Self::CaseInsensitive => tr!("path-case-insensitive"),
+            Self::NoExec => tr!("path-noexec")
+        }
+    }
+}
+
+/// Mount point from the `/proc/self/mountinfo` file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mount {
+    pub path: PathBuf,
+    pub filesystem: String,
+    pub options: Vec<String>
+}
+
+/// Get the mount point which contains the given path
+pub fn get_mount(path: impl AsRef<Path>) -> Option<Mount> {
+    let path = path.as_ref().canonicalize().ok()?;
+    let mountinfo = std::fs::read_to_string("/proc/self/mountinfo").ok()?;
+
+    let mut result: Option<Mount> = None;
+
+    // 36 35 98:0 /mnt1 /mnt2 rw,noatime master:1 - ext3 /dev/root rw,errors=continue
+    for line in mountinfo.lines() {
+        let Some((mount, filesystem)) = line.split_once(" - ") else {
+            continue;
+        };
+
+        let mount = mount.split(' ').collect::<Vec<_>>();
+
+        let (Some(mount_path), Some(options), Some(filesystem)) = (mount.get(4), mount.get(5), filesystem.split(' ').next()) else {
+            continue;
+        };
+
+        let mount_path = unescape(mount_path);
+
+        // Use the deepest mount point which contains the path
+        let deeper = result.as_ref()
+            .map(|result| result.path.as_os_str().len() <= mount_path.as_os_str().len())
+            .unwrap_or(true);
+
+        if path.starts_with(&mount_path) && deeper {
+            result = Some(Mount {
+                path: mount_path,
+                filesystem: filesystem.to_string(),
+                options: options.split(',').map(String::from).collect()
+            });
+        }
+    }
+
+    result
+}
+
+/// Decode octal escapes (e.g. `\040` for space) used in the `/proc/self/mountinfo`
+fn unescape(value: &str) -> PathBuf {
+    let bytes = value.as_bytes();
+
+    let mut result = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let code = bytes.get(i + 1..i + 4)
+            .filter(|_| bytes[i] == b'\\')
+            .and_then(|code| std::str::from_utf8(code).ok())
+            .and_then(|code| u8::from_str_radix(code, 8).ok());
+
+        match code {
+            Some(code) => {
+                result.push(code);
+
+                i += 4;
+            }
+
+            None => {
+                result.push(bytes[i]);
+
+                i += 1;
+            }
+        }
+    }
+
+    PathBuf::from(OsString::from_vec(result))
+}
+
+/// Check if the given path can be used to store launcher files
+///
+/// The path may not exist yet, then its closest existing parent is checked.
+/// `required` is the expected size of the folder content, and `executable`
+/// means that files from this folder will be executed (wine builds, game, etc.)
+pub fn check_path(path: impl AsRef<Path>, required: u64, executable: bool) -> Vec<PathWarning> {
+    let mut warnings = Vec::new();
+
+    let Some(existing) = path.as_ref().ancestors().find(|path| path.exists()) else {
+        return vec![PathWarning::NotWritable];
+    };
+
+    // Probe file is created to check both writability and case sensitivity
+    let probe = existing.join(format!(".Wavey-Launcher-Check-{}", std::process::id()));
+
+    if std::fs::write(&probe, "").is_err() {
+        warnings.push(PathWarning::NotWritable);
+    }
+
+    else {
+        let lowercase = existing.join(format!(".wavey-launcher-check-{}", std::process::id()));
+
+        if lowercase.exists() {
+            warnings.push(PathWarning::CaseInsensitive);
+        }
+
+        if let Err(err) = std::fs::remove_file(&probe) {
+            tracing::warn!("Failed to remove probe file {:?}: {err}", probe);
+        }
+    }
+
+    // Not empty folder most likely has already installed files
+    let is_empty = path.as_ref().read_dir()
+        .map(|mut entries| entries.next().is_none())
+        .unwrap_or(true);
+
+    if is_empty {
+        if let Some(available) = free_space::available(existing) {
+            if available < required {
+                warnings.push(PathWarning::NotEnoughSpace { required, available });
+            }
+        }
+    }
+
+    if let Some(mount) = get_mount(existing) {
+        if UNSUPPORTED_FILESYSTEMS.contains(&mount.filesystem.as_str()) {
+            warnings.push(PathWarning::Filesystem(mount.filesystem));
+        }
+
+        if executable && mount.options.iter().any(|option| option == "noexec") {
+            warnings.push(PathWarning::NoExec);
+        }
+    }
+
+    warnings
+}
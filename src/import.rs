@@ -0,0 +1,302 @@
+use std::path::{Path, PathBuf};
+
+use anime_launcher_sdk::anime_game_core::prelude::*;
+use anime_launcher_sdk::anime_game_core::wuwa::prelude::*;
+
+/// Steam application id of the game
+pub const STEAM_APP_ID: &str = "3513350";
+
+/// Launcher which has the game installed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ImportSource {
+    Lutris,
+    Heroic,
+    Bottles,
+    Steam,
+
+    /// Folder chosen by user
+    Folder
+}
+
+impl ImportSource {
+    pub fn title(&self) -> &'static str {
+        match self {
+            Self::Lutris  => "Lutris",
+            Self::Heroic  => "Heroic",
+            Self::Bottles => "Bottles",
+            Self::Steam   => "Steam",
+            Self::Folder  => "Folder"
+        }
+    }
+}
+
+/// Game installation found in another launcher
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FoundInstallation {
+    pub source: ImportSource,
+    pub game: PathBuf,
+    pub prefix: Option<PathBuf>
+}
+
+impl FoundInstallation {
+    /// Get installed game version. Fails if the folder doesn't contain the game
+    pub fn verify(&self, edition: GameEdition) -> anyhow::Result<Version> {
+        Game::new(&self.game, edition).get_version()
+    }
+}
+
+/// Check if the folder contains the game files
+pub fn is_game_folder(path: impl AsRef<Path>) -> bool {
+    let path = path.as_ref();
+
+    path.join("Wuthering Waves.exe").exists() ||
+    path.join("Client/Binaries/Win64/Client-Win64-Shipping.exe").exists()
+}
+
+/// Find the game folder inside of the given one, checking at most `depth` levels of subfolders
+pub fn find_game_folder(path: impl AsRef<Path>, depth: usize) -> Option<PathBuf> {
+    let path = path.as_ref();
+
+    if is_game_folder(path) {
+        return Some(path.to_path_buf());
+    }
+
+    if depth == 0 {
+        return None;
+    }
+
+    path.read_dir().ok()?
+        .flatten()
+        .filter(|entry| entry.file_type().map(|kind| kind.is_dir()).unwrap_or(false))
+        // Wine system folders can't contain the game and are pretty huge
+        .filter(|entry| !["windows", "users", "ProgramData"].contains(&entry.file_name().to_string_lossy().as_ref()))
+        .find_map(|entry| find_game_folder(entry.path(), depth - 1))
+}
+
+/// Get the wine prefix which contains the given path
+fn find_prefix(path: &Path) -> Option<PathBuf> {
+    path.ancestors()
+        .find(|path| path.join("drive_c").is_dir() && path.join("system.reg").exists())
+        .map(Path::to_path_buf)
+}
+
+fn home() -> Option<PathBuf> {
+    std::env::var_os("HOME").map(PathBuf::from)
+}
+
+fn lutris() -> Vec<FoundInstallation> {
+    let Some(home) = home() else {
+        return vec![];
+    };
+
+    let folders = [
+        home.join(".config/lutris/games"),
+        home.join(".local/share/lutris/games"),
+        home.join(".var/app/net.lutris.Lutris/data/lutris/games")
+    ];
+
+    let mut installations = Vec::new();
+
+    for folder in folders {
+        let Ok(entries) = folder.read_dir() else {
+            continue;
+        };
+
+        for entry in entries.flatten() {
+            let Ok(config) = std::fs::read_to_string(entry.path()) else {
+                continue;
+            };
+
+            let mut exe = None;
+            let mut prefix = None;
+
+            // Game configs are YAML files with `game.exe` and `game.prefix` fields
+            for line in config.lines() {
+                let line = line.trim();
+
+                if let Some(value) = line.strip_prefix("exe:") {
+                    exe = Some(PathBuf::from(value.trim().trim_matches(['\'', '"'])));
+                }
+
+                else if let Some(value) = line.strip_prefix("prefix:") {
+                    prefix = Some(PathBuf::from(value.trim().trim_matches(['\'', '"'])));
+                }
+            }
+
+            let game = exe.as_ref()
+                .and_then(|exe| exe.ancestors().skip(1).take(4).find(|path| is_game_folder(path)))
+                .map(Path::to_path_buf)
+                .or_else(|| prefix.as_ref().and_then(|prefix| find_game_folder(prefix.join("drive_c"), 4)));
+
+            if let Some(game) = game {
+                installations.push(FoundInstallation {
+                    source: ImportSource::Lutris,
+                    prefix: prefix.or_else(|| find_prefix(&game)),
+                    game
+                });
+            }
+        }
+    }
+
+    installations
+}
+
+fn heroic() -> Vec<FoundInstallation> {
+    let Some(home) = home() else {
+        return vec![];
+    };
+
+    let folders = [
+        home.join(".config/heroic"),
+        home.join(".var/app/com.heroicgameslauncher.hgl/config/heroic")
+    ];
+
+    let mut installations = Vec::new();
+
+    for folder in folders {
+        let Ok(installed) = std::fs::read(folder.join("legendaryConfig/legendary/installed.json")) else {
+            continue;
+        };
+
+        let Ok(serde_json::Value::Object(installed)) = serde_json::from_slice(&installed) else {
+            continue;
+        };
+
+        for (app_name, game) in installed {
+            let Some(game) = game.get("install_path").and_then(|path| path.as_str()) else {
+                continue;
+            };
+
+            let Some(game) = find_game_folder(game, 2) else {
+                continue;
+            };
+
+            // Per-game settings contain the wine prefix path
+            let prefix = std::fs::read(folder.join(format!("GamesConfig/{app_name}.json"))).ok()
+                .and_then(|config| serde_json::from_slice::<serde_json::Value>(&config).ok())
+                .and_then(|config| {
+                    config.get(&app_name)
+                        .and_then(|config| config.get("winePrefix"))
+                        .and_then(|prefix| prefix.as_str())
+                        .map(PathBuf::from)
+                });
+
+            installations.push(FoundInstallation {
+                source: ImportSource::Heroic,
+                game,
+                prefix
+            });
+        }
+    }
+
+    installations
+}
+
+fn bottles() -> Vec<FoundInstallation> {
+    let Some(home) = home() else {
+        return vec![];
+    };
+
+    let folders = [
+        home.join(".local/share/bottles/bottles"),
+        home.join(".var/app/com.usebottles.bottles/data/bottles/bottles")
+    ];
+
+    let mut installations = Vec::new();
+
+    for folder in folders {
+        let Ok(entries) = folder.read_dir() else {
+            continue;
+        };
+
+        // Every bottle is a wine prefix
+        for entry in entries.flatten() {
+            if let Some(game) = find_game_folder(entry.path().join("drive_c"), 4) {
+                installations.push(FoundInstallation {
+                    source: ImportSource::Bottles,
+                    game,
+                    prefix: Some(entry.path())
+                });
+            }
+        }
+    }
+
+    installations
+}
+
+fn steam() -> Vec<FoundInstallation> {
+    let Some(home) = home() else {
+        return vec![];
+    };
+
+    let roots = [
+        home.join(".local/share/Steam"),
+        home.join(".steam/steam"),
+        home.join(".var/app/com.valvesoftware.Steam/.local/share/Steam")
+    ];
+
+    let mut libraries = Vec::new();
+
+    for root in roots {
+        libraries.push(root.clone());
+
+        let Ok(folders) = std::fs::read_to_string(root.join("steamapps/libraryfolders.vdf")) else {
+            continue;
+        };
+
+        // "path"		"/mnt/games/SteamLibrary"
+        for line in folders.lines() {
+            let mut values = line.split('"')
+                .map(str::trim)
+                .filter(|value| !value.is_empty());
+
+            if let (Some("path"), Some(path)) = (values.next(), values.next()) {
+                libraries.push(PathBuf::from(path));
+            }
+        }
+    }
+
+    libraries.into_iter()
+        .filter_map(|library| {
+            let game = find_game_folder(library.join("steamapps/common/Wuthering Waves"), 2)?;
+            let prefix = library.join(format!("steamapps/compatdata/{STEAM_APP_ID}/pfx"));
+
+            Some(FoundInstallation {
+                source: ImportSource::Steam,
+                game,
+                prefix: prefix.exists().then_some(prefix)
+            })
+        })
+        .collect()
+}
+
+/// Find game installations made by other launchers
+pub fn detect() -> Vec<FoundInstallation> {
+    let mut installations = Vec::<FoundInstallation>::new();
+
+    let found = [lutris(), heroic(), bottles(), steam()];
+
+    for installation in found.into_iter().flatten() {
+        // Some launchers have several config locations pointing to the same folders
+        let duplicate = installations.iter().any(|found| {
+            found.game.canonicalize().ok() == installation.game.canonicalize().ok()
+        });
+
+        if !duplicate {
+            installations.push(installation);
+        }
+    }
+
+    installations
+}
+
+/// Use the folder chosen by user. The folder itself or one of its subfolders should contain the game
+pub fn from_folder(path: impl AsRef<Path>) -> Option<FoundInstallation> {
+    let game = find_game_folder(path, 3)?;
+
+    Some(FoundInstallation {
+        source: ImportSource::Folder,
+        prefix: find_prefix(&game),
+        game
+    })
+}
@@ -20,8 +20,25 @@ use tracing_subscriber::prelude::*;
 use tracing_subscriber::filter::*;
 
 pub mod move_files;
+pub mod filesystem;
+pub mod dependencies;
+pub mod setup;
+pub mod import;
+pub mod bundle;
+pub mod custom_wine;
+pub mod components_index;
+pub mod checksums;
+pub mod disk_usage;
+pub mod directx;
+pub mod snapshots;
+pub mod prefix_health;
+pub mod winetricks;
+pub mod wine_run;
+pub mod registry;
+pub mod upgrade;
 pub mod i18n;
 pub mod background;
+pub mod settings;
 pub mod ui;
 
 use ui::main::*;
@@ -60,9 +77,15 @@ lazy_static::lazy_static! {
     /// Path to `debug.log` file. Standard is `$HOME/.local/share/wavey-launcher/debug.log`
     pub static ref DEBUG_FILE: PathBuf = LAUNCHER_FOLDER.join("debug.log");
 
+    /// Path to `settings.json` file with launcher-specific settings. Standard is `$HOME/.local/share/wavey-launcher/settings.json`
+    pub static ref SETTINGS_FILE: PathBuf = LAUNCHER_FOLDER.join("settings.json");
+
     /// Path to `background` file. Standard is `$HOME/.local/share/wavey-launcher/background`
     pub static ref BACKGROUND_FILE: PathBuf = LAUNCHER_FOLDER.join("background");
 
+    /// Path to `background-video` file. Standard is `$HOME/.local/share/wavey-launcher/background-video`
+    pub static ref BACKGROUND_VIDEO_FILE: PathBuf = LAUNCHER_FOLDER.join("background-video");
+
     /// Path to the processed `background` file. Standard is `$HOME/.cache/anime-game-launcher/background`
     pub static ref PROCESSED_BACKGROUND_FILE: PathBuf = CACHE_FOLDER.join("background");
 
@@ -71,11 +94,19 @@ lazy_static::lazy_static! {
     /// Standard is `$HOME/.local/share/wavey-launcher/.keep-background`
     pub static ref KEEP_BACKGROUND_FILE: PathBuf = LAUNCHER_FOLDER.join(".keep-background");
 
+    /// Path to `.migration` file. Used to resume or revert interrupted installation migration
+    ///
+    /// Standard is `$HOME/.local/share/wavey-launcher/.migration`
+    pub static ref MIGRATION_JOURNAL_FILE: PathBuf = LAUNCHER_FOLDER.join(".migration");
+
     /// Path to `.first-run` file. Used to mark launcher that it should run FirstRun window
     ///
     /// Standard is `$HOME/.local/share/wavey-launcher/.first-run`
     pub static ref FIRST_RUN_FILE: PathBuf = LAUNCHER_FOLDER.join(".first-run");
 
+    /// Path to `snapshots` folder with wine prefix snapshots. Standard is `$HOME/.local/share/wavey-launcher/snapshots`
+    pub static ref SNAPSHOTS_FOLDER: PathBuf = LAUNCHER_FOLDER.join("snapshots");
+
     /// Global app's css
     static ref GLOBAL_CSS: String = format!("
             progressbar > text {{
@@ -164,17 +195,30 @@ fn main() -> anyhow::Result<()> {
     // Force disable verbose tracing output in stdout
     let mut no_verbose_tracing = false;
 
-    let args = std::env::args().collect::<Vec<_>>();
+    // Run first run setup from the seed file
+    let mut setup_seed = None;
+
+    let mut args = std::env::args();
     let mut gtk_args = Vec::new();
 
     // Parse arguments
-    for arg in args {
+    while let Some(arg) = args.next() {
         match arg.as_str() {
             "--debug"              => force_debug        = true,
             "--run-game"           => run_game           = true,
             "--just-run-game"      => just_run_game      = true,
             "--no-verbose-tracing" => no_verbose_tracing = true,
 
+            "--setup" => match args.next() {
+                Some(seed) => setup_seed = Some(PathBuf::from(seed)),
+
+                None => {
+                    eprintln!("--setup requires a seed file path");
+
+                    std::process::exit(1);
+                }
+            }
+
             _ => gtk_args.push(arg)
         }
     }
@@ -211,6 +255,28 @@ fn main() -> anyhow::Result<()> {
 
     tracing::info!("Starting application ({APP_VERSION})");
 
+    if let Err(err) = settings::Settings::migrate() {
+        tracing::error!("Failed to migrate launcher settings: {err}");
+    }
+
+    // Run unattended setup without creating any windows
+    if let Some(seed) = setup_seed {
+        // Set language for the path warnings
+        if let Ok(config) = Config::get() {
+            if let Ok(lang) = config.launcher.language.parse() {
+                let _ = i18n::set_lang(lang);
+            }
+        }
+
+        if let Err(err) = setup::run(seed) {
+            eprintln!("{err}");
+
+            std::process::exit(err.exit_code());
+        }
+
+        return Ok(());
+    }
+
     adw::init().expect("Libadwaita initialization failed");
 
     // Register and include resources
@@ -248,6 +314,9 @@ fn main() -> anyhow::Result<()> {
     // Run the app if everything's ready
     else {
         if run_game || just_run_game {
+            // Selected wine can be a custom build which SDK looks up in the components index
+            let _custom_wine = custom_wine::register();
+
             let state = LauncherState::get_from_config(|_| {})
                 .expect("Failed to get launcher state");
 
@@ -1,31 +1,380 @@
-use std::path::Path;
-use std::io::Result;
+use std::path::{Path, PathBuf};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{Result, Error, ErrorKind, Write, BufRead, BufReader};
+use std::os::unix::fs::MetadataExt;
+use std::sync::atomic::{AtomicBool, Ordering};
 
-/// Move files from one folder to another
-pub fn move_files(from: impl AsRef<Path>, to: impl AsRef<Path>) -> Result<()> {
-    for entry in from.as_ref().read_dir()?.flatten() {
-        let source = entry.path();
-        let target = to.as_ref().join(entry.file_name());
+use serde::{Serialize, Deserialize};
 
-        if std::fs::rename(&source, &target).is_err() {
-            if source.is_dir() {
-                std::fs::create_dir_all(&target)
-                    .and_then(|_| move_files(&source, &target))
-                    .and_then(|_| std::fs::remove_dir_all(&source))?;
+use anime_launcher_sdk::anime_game_core::installer::free_space;
+
+use crate::settings::prelude::*;
+
+/// Folder which should be migrated to another place
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MigrationFolder {
+    pub from: PathBuf,
+    pub to: PathBuf
+}
+
+/// Folders which failed to migrate, with their errors
+#[derive(Debug)]
+pub struct FoldersError(pub Vec<(MigrationFolder, Error)>);
+
+impl std::fmt::Display for FoldersError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, (folder, err)) in self.0.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+
+            write!(f, "{:?} -> {:?}: {err}", folder.from, folder.to)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for FoldersError {}
+
+#[inline]
+fn default_journal_file() -> PathBuf {
+    crate::MIGRATION_JOURNAL_FILE.to_path_buf()
+}
+
+/// Journal of the installation migration
+///
+/// Stored in the `MIGRATION_JOURNAL_FILE` while migration is in progress,
+/// so interrupted migration can be resumed or reverted on the next launcher start.
+/// Moved entries are appended to the `.log` file next to it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Journal {
+    pub folders: Vec<MigrationFolder>,
+
+    /// Launcher config before migration, restored when migration is reverted
+    pub config: serde_json::Value,
+
+    /// DirectX components builds folders before migration, restored when migration is reverted
+    #[serde(default)]
+    pub directx_builds: Option<DirectXBuilds>,
+
+    /// Path to the journal file
+    #[serde(skip, default = "default_journal_file")]
+    pub file: PathBuf
+}
+
+impl Journal {
+    /// Prepare migration of the given folders, skipping not existing and unchanged ones
+    pub fn new(folders: impl IntoIterator<Item = (PathBuf, PathBuf)>, config: serde_json::Value) -> Self {
+        Self {
+            folders: folders.into_iter()
+                .filter(|(from, to)| from != to && from.exists())
+                .map(|(from, to)| MigrationFolder { from, to })
+                .collect(),
+
+            config,
+            directx_builds: None,
+            file: default_journal_file()
+        }
+    }
+
+    /// Restore DirectX components builds folders from the launcher settings
+    /// when migration is reverted
+    pub fn with_directx_builds(self, builds: DirectXBuilds) -> Self {
+        Self {
+            directx_builds: Some(builds),
+            ..self
+        }
+    }
+
+    /// Store the journal in another file, so the migration
+    /// is not resumed by the launcher on the next start
+    pub fn with_file(self, file: impl Into<PathBuf>) -> Self {
+        Self {
+            file: file.into(),
+            ..self
+        }
+    }
+
+    /// Read journal of the interrupted migration, if there's one
+    pub fn get() -> anyhow::Result<Option<Self>> {
+        if !crate::MIGRATION_JOURNAL_FILE.exists() {
+            return Ok(None);
+        }
+
+        let journal = std::fs::read(crate::MIGRATION_JOURNAL_FILE.as_path())?;
+
+        Ok(Some(serde_json::from_slice(&journal)?))
+    }
+
+    #[inline]
+    fn log_file(&self) -> PathBuf {
+        self.file.with_extension("log")
+    }
+
+    /// Remove journal files when migration is finished or reverted
+    fn remove(&self) -> Result<()> {
+        if self.log_file().exists() {
+            std::fs::remove_file(self.log_file())?;
+        }
+
+        std::fs::remove_file(&self.file)
+    }
+
+    /// Size of all the files which should be migrated, in bytes
+    pub fn total_size(&self) -> u64 {
+        self.folders.iter()
+            .map(|folder| crate::disk_usage::size(&folder.from))
+            .sum()
+    }
+
+    /// Check that target disks have enough free space for the folders
+    /// which can't be just renamed because they're on other disks
+    pub fn check_free_space(&self) -> anyhow::Result<()> {
+        // Target device => (target path, required space)
+        let mut required = HashMap::<u64, (PathBuf, u64)>::new();
+
+        for folder in &self.folders {
+            let Some(target) = existing_ancestor(&folder.to) else {
+                continue;
+            };
+
+            let source_device = folder.from.metadata()?.dev();
+            let target_device = target.metadata()?.dev();
+
+            if source_device != target_device {
+                required.entry(target_device)
+                    .or_insert_with(|| (target.to_path_buf(), 0))
+                    .1 += crate::disk_usage::size(&folder.from);
+            }
+        }
+
+        for (target, required) in required.into_values() {
+            if let Some(available) = free_space::available(&target) {
+                if available < required {
+                    anyhow::bail!(
+                        "Not enough free space in {:?}: required {:.2} GB, available {:.2} GB",
+                        target,
+                        required as f64 / 1024.0 / 1024.0 / 1024.0,
+                        available as f64 / 1024.0 / 1024.0 / 1024.0
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Move folders to their new places
+    ///
+    /// Can be called for the interrupted migration to resume it.
+    /// `updater` is called with the amount of moved and total bytes.
+    /// Migration stops when `cancel` is set, and the journal is kept so it can be reverted.
+    /// If some folders fail to migrate, the rest are still moved and `FoldersError` is returned
+    pub fn migrate(&self, cancel: &AtomicBool, updater: impl Fn(u64, u64)) -> anyhow::Result<()> {
+        std::fs::write(&self.file, serde_json::to_string_pretty(self)?)?;
+
+        let mut mover = Mover {
+            log: Some(File::options().create(true).append(true).open(self.log_file())?),
+            cancel: Some(cancel),
+            folder: 0,
+            moved: 0,
+            total: self.total_size(),
+            updater: &updater
+        };
+
+        let mut errors = Vec::new();
+
+        for (i, folder) in self.folders.iter().enumerate() {
+            tracing::info!("Migrating folder: {:?} -> {:?}", folder.from, folder.to);
+
+            // Folder could be already moved by the interrupted migration
+            if !folder.from.exists() {
+                continue;
+            }
+
+            mover.folder = i;
+
+            let result = std::fs::create_dir_all(&folder.to)
+                .and_then(|_| folder.from.read_dir())
+                .and_then(|entries| {
+                    for entry in entries {
+                        let name = entry?.file_name();
+
+                        mover.move_entry(&folder.from.join(&name), &folder.to.join(&name), Path::new(&name))?;
+                    }
+
+                    Ok(())
+                });
+
+            match result {
+                Ok(_) => (),
+
+                Err(err) if err.kind() == ErrorKind::Interrupted && cancel.load(Ordering::Relaxed) => {
+                    tracing::warn!("Installation migration was cancelled");
+
+                    anyhow::bail!("Installation migration was cancelled");
+                }
+
+                Err(err) => {
+                    tracing::error!("Failed to migrate folder {:?} -> {:?}: {err}", folder.from, folder.to);
+
+                    errors.push((folder.clone(), err));
+                }
             }
+        }
 
-            else if source.is_symlink() {
-                std::fs::read_link(&source)
-                    .and_then(|link_target| std::os::unix::fs::symlink(link_target, &target))
-                    .and_then(|_| std::fs::remove_file(&source))?;
+        if !errors.is_empty() {
+            return Err(FoldersError(errors).into());
+        }
+
+        self.remove()?;
+
+        Ok(())
+    }
+
+    /// Move already migrated entries back to their original places and restore launcher config
+    ///
+    /// `updater` is called with the amount of reverted and total entries
+    pub fn revert(&self, updater: impl Fn(u64, u64)) -> anyhow::Result<()> {
+        let mut entries = Vec::new();
+
+        if self.log_file().exists() {
+            for line in BufReader::new(File::open(self.log_file())?).lines() {
+                entries.push(serde_json::from_str::<(usize, PathBuf)>(&line?)?);
             }
+        }
+
+        let total = entries.len() as u64;
 
-            else {
-                std::fs::copy(&source, &target)
-                    .and_then(|_| std::fs::remove_file(&source))?;
-            } 
+        let mut mover = Mover {
+            // Reverted entries don't need to be logged
+            log: None,
+            cancel: None,
+            folder: 0,
+            moved: 0,
+            total: 0,
+            updater: &|_, _| {}
+        };
+
+        for (i, (folder, entry)) in entries.into_iter().rev().enumerate() {
+            if let Some(folder) = self.folders.get(folder) {
+                let source = folder.to.join(&entry);
+                let target = folder.from.join(&entry);
+
+                let source_meta = source.symlink_metadata();
+
+                // Entry is logged before moving, so it could be not moved at all
+                if let Ok(source_meta) = source_meta {
+                    // Copying was interrupted before the original entry was removed,
+                    // so the original is kept and its partial copy is deleted
+                    if !source_meta.is_dir() && target.symlink_metadata().is_ok() {
+                        std::fs::remove_file(&source)?;
+                    }
+
+                    else {
+                        if let Some(parent) = target.parent() {
+                            std::fs::create_dir_all(parent)?;
+                        }
+
+                        mover.move_entry(&source, &target, &entry)?;
+                    }
+                }
+            }
+
+            updater(i as u64 + 1, total);
         }
+
+        anime_launcher_sdk::wuwa::config::Config::update_raw((&self.config).into())?;
+
+        if let Some(builds) = &self.directx_builds {
+            Settings::modify(|settings| settings.directx.builds = builds.clone())?;
+        }
+
+        self.remove()?;
+
+        Ok(())
     }
+}
+
+struct Mover<'a> {
+    log: Option<File>,
+    cancel: Option<&'a AtomicBool>,
+    folder: usize,
+    moved: u64,
+    total: u64,
+    updater: &'a dyn Fn(u64, u64)
+}
+
+impl Mover<'_> {
+    /// Move file, symlink or folder, falling back to copying when it's placed on another disk
+    ///
+    /// Entry is logged before moving, so it can be reverted if the launcher
+    /// is closed in the middle of moving
+    fn move_entry(&mut self, source: &Path, target: &Path, relative: &Path) -> Result<()> {
+        if self.cancel.is_some_and(|cancel| cancel.load(Ordering::Relaxed)) {
+            return Err(Error::new(ErrorKind::Interrupted, "Migration was cancelled"));
+        }
+
+        if let Some(log) = &mut self.log {
+            writeln!(log, "{}", serde_json::to_string(&(self.folder, relative))?)?;
+
+            log.flush()?;
+        }
+
+        if std::fs::rename(source, target).is_ok() {
+            self.finish_entry(crate::disk_usage::size(target));
+
+            return Ok(());
+        }
+
+        let metadata = source.symlink_metadata()?;
+
+        if metadata.is_dir() {
+            std::fs::create_dir_all(target)?;
+
+            for entry in source.read_dir()? {
+                let name = entry?.file_name();
+
+                self.move_entry(&source.join(&name), &target.join(&name), &relative.join(&name))?;
+            }
+
+            std::fs::remove_dir(source)
+        }
+
+        else if metadata.is_symlink() {
+            if target.symlink_metadata().is_ok() {
+                std::fs::remove_file(target)?;
+            }
+
+            std::fs::read_link(source)
+                .and_then(|link_target| std::os::unix::fs::symlink(link_target, target))
+                .and_then(|_| std::fs::remove_file(source))?;
+
+            self.finish_entry(0);
+
+            Ok(())
+        }
+
+        else {
+            std::fs::copy(source, target)
+                .and_then(|_| std::fs::remove_file(source))?;
+
+            self.finish_entry(metadata.len());
+
+            Ok(())
+        }
+    }
+
+    fn finish_entry(&mut self, size: u64) {
+        self.moved += size;
+
+        (self.updater)(self.moved, self.total);
+    }
+}
 
-    Ok(())
+/// Get the closest existing parent of the path, or the path itself
+fn existing_ancestor(path: &Path) -> Option<&Path> {
+    path.ancestors().find(|path| path.exists())
 }
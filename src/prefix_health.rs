@@ -0,0 +1,394 @@
+use std::path::{Path, PathBuf};
+
+use anime_launcher_sdk::wincompatlib::prelude::*;
+
+use anime_launcher_sdk::components::*;
+
+use anime_launcher_sdk::config::ConfigExt;
+use anime_launcher_sdk::wuwa::config::Config;
+
+use crate::checksums::ComponentKind;
+use crate::settings::prelude::*;
+
+/// Registry hives wine keeps in the prefix root
+const REGISTRY_HIVES: &[&str] = &["system.reg", "user.reg", "userdef.reg"];
+
+/// DLLs installed by DXVK which the game needs
+const DXVK_DLLS: &[&str] = &["d3d11", "dxgi"];
+
+/// Folders of the wine prefix users which are moved to the rebuilt prefix.
+/// Games keep their settings there
+const PRESERVED_USER_FOLDERS: &[&str] = &["AppData", "Documents"];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HealthIssue {
+    PrefixNotExists,
+
+    /// Prefix was created or updated by another wine build
+    WineMismatch {
+        selected: String
+    },
+
+    /// DXVK DLLs are missing in the prefix while the selected wine needs it
+    DxvkMissing(Vec<String>),
+
+    /// Registry hive can't be parsed. Line number starts from 1
+    RegistryBroken {
+        hive: String,
+        line: usize
+    },
+
+    /// Drive link doesn't point to the expected folder
+    DriveMapping {
+        drive: String,
+        expected: PathBuf
+    }
+}
+
+impl std::fmt::Display for HealthIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::PrefixNotExists => write!(f, "Wine prefix doesn't exist"),
+            Self::WineMismatch { selected } => write!(f, "Wine prefix was not updated with the selected wine build {selected}"),
+            Self::DxvkMissing(dlls) => write!(f, "DXVK files are missing: {}", dlls.join(", ")),
+            Self::RegistryBroken { hive, line } => write!(f, "Registry file {hive} is broken at line {line}"),
+            Self::DriveMapping { drive, expected } => write!(f, "Drive {drive} is not mapped to {expected:?}")
+        }
+    }
+}
+
+/// Check that the registry hive has wine's header and valid lines
+///
+/// Returns number of the first broken line
+fn check_hive(path: &Path) -> anyhow::Result<Option<usize>> {
+    let hive = String::from_utf8_lossy(&std::fs::read(path)?).to_string();
+
+    let mut lines = hive.lines().enumerate();
+
+    match lines.next() {
+        Some((_, header)) if header.starts_with("WINE REGISTRY Version") => (),
+        _ => return Ok(Some(1))
+    }
+
+    let mut continued = false;
+
+    for (i, line) in lines {
+        let valid = continued
+            || line.is_empty()
+            || (line.starts_with('[') && line.contains(']'))
+            || (line.starts_with('"') && line.contains('='))
+            || line.starts_with("@=")
+            || line.starts_with('#')
+            || line.starts_with(';');
+
+        if !valid {
+            return Ok(Some(i + 1));
+        }
+
+        continued = line.ends_with('\\');
+    }
+
+    Ok(None)
+}
+
+/// Check that the prefix was last updated by the given wine build
+///
+/// Wine stores modification time of its `wine.inf` file in the prefix's `.update-timestamp`
+fn check_wine(prefix: &Path, build: &Path) -> bool {
+    let Ok(timestamp) = std::fs::read_to_string(prefix.join(".update-timestamp")) else {
+        return false;
+    };
+
+    let timestamp = timestamp.split_whitespace().next().unwrap_or_default();
+
+    // Prefix updates are disabled by the user
+    if timestamp == "disable" {
+        return true;
+    }
+
+    let inf_time = ["share/wine/wine.inf", "files/share/wine/wine.inf"]
+        .iter()
+        .map(|path| build.join(path))
+        .find(|path| path.exists())
+        .and_then(|path| path.metadata().ok())
+        .and_then(|metadata| metadata.modified().ok())
+        .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|time| time.as_secs().to_string());
+
+    match inf_time {
+        Some(inf_time) => inf_time == timestamp,
+
+        // Can't be checked for builds with different layout
+        None => true
+    }
+}
+
+fn check_drive(prefix: &Path, drive: &str, expected: impl AsRef<Path>) -> Option<HealthIssue> {
+    let expected = expected.as_ref();
+    let link = prefix.join("dosdevices").join(drive);
+
+    let mapped = link.canonicalize().ok();
+    let expected_path = expected.canonicalize().ok();
+
+    if mapped.is_none() || mapped != expected_path {
+        Some(HealthIssue::DriveMapping {
+            drive: drive.to_string(),
+            expected: expected.to_path_buf()
+        })
+    } else {
+        None
+    }
+}
+
+/// Check the wine prefix
+///
+/// Empty list means the prefix is healthy
+pub fn check() -> anyhow::Result<Vec<HealthIssue>> {
+    let config = Config::get()?;
+    let prefix = &config.game.wine.prefix;
+
+    if !prefix.join("drive_c").exists() {
+        return Ok(vec![HealthIssue::PrefixNotExists]);
+    }
+
+    let mut issues = Vec::new();
+
+    if let Some(wine) = crate::custom_wine::get_selected(&config)? {
+        if !check_wine(prefix, &config.game.wine.builds.join(&wine.name)) {
+            issues.push(HealthIssue::WineMismatch {
+                selected: wine.title.clone()
+            });
+        }
+
+        let need_dxvk = wine::Group::find_in(&config.components.path, &wine.name)
+            .ok()
+            .flatten()
+            .map(|group| group.features.unwrap_or_default().need_dxvk)
+            .unwrap_or(true);
+
+        if need_dxvk {
+            let missing = DXVK_DLLS.iter()
+                .map(|dll| format!("{dll}.dll"))
+                .filter(|dll| !prefix.join("drive_c/windows/system32").join(dll).exists())
+                .collect::<Vec<_>>();
+
+            if !missing.is_empty() {
+                issues.push(HealthIssue::DxvkMissing(missing));
+            }
+        }
+    }
+
+    for hive in REGISTRY_HIVES {
+        let path = prefix.join(hive);
+
+        if !path.exists() {
+            continue;
+        }
+
+        if let Some(line) = check_hive(&path)? {
+            issues.push(HealthIssue::RegistryBroken {
+                hive: hive.to_string(),
+                line
+            });
+        }
+    }
+
+    if config.game.wine.drives.drive_c {
+        issues.extend(check_drive(prefix, "c:", &prefix.join("drive_c")));
+    }
+
+    if let Some(drive) = config.game.wine.drives.game_folder {
+        let game = config.game.path.for_edition(config.launcher.edition);
+
+        issues.extend(check_drive(prefix, &drive.to_drive().to_lowercase(), game));
+    }
+
+    Ok(issues)
+}
+
+/// Copy users' folders with the games settings from one prefix to another
+fn copy_user_folders(from: &Path, to: &Path) -> anyhow::Result<()> {
+    let Ok(users) = from.join("drive_c/users").read_dir() else {
+        return Ok(());
+    };
+
+    for user in users.flatten() {
+        // Links to the host user's folders
+        if user.file_type()?.is_symlink() {
+            continue;
+        }
+
+        for folder in PRESERVED_USER_FOLDERS {
+            let source = user.path().join(folder);
+
+            if !source.is_dir() || source.symlink_metadata()?.file_type().is_symlink() {
+                continue;
+            }
+
+            let target = to.join("drive_c/users").join(user.file_name());
+
+            std::fs::create_dir_all(&target)?;
+
+            let output = std::process::Command::new("cp")
+                .arg("-a")
+                .arg("--remove-destination")
+                .arg(&source)
+                .arg(&target)
+                .output()?;
+
+            if !output.status.success() {
+                anyhow::bail!("Failed to copy {source:?}: {}", String::from_utf8_lossy(&output.stderr).trim());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Create new wine prefix with the selected components
+///
+/// Users' `AppData` and `Documents` folders are moved to the new prefix,
+/// installed winetricks verbs are reapplied. Old prefix is put back if rebuilding failed
+pub fn rebuild() -> anyhow::Result<()> {
+    let config = Config::get()?;
+    let prefix = config.game.wine.prefix.clone();
+
+    let Some(wine) = crate::custom_wine::get_selected(&config)? else {
+        anyhow::bail!("Wine is not selected");
+    };
+
+    tracing::info!("Rebuilding wine prefix {prefix:?}");
+
+    // DXVK version is read from the prefix so it should be remembered before rebuilding
+    let dxvk = config.get_selected_dxvk().ok().flatten();
+
+    let old_prefix = prefix.with_file_name(format!(".{}.rebuilding", prefix.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default()));
+
+    if old_prefix.exists() {
+        std::fs::remove_dir_all(&old_prefix)?;
+    }
+
+    if prefix.exists() {
+        std::fs::rename(&prefix, &old_prefix)?;
+    }
+
+    let result = (|| -> anyhow::Result<()> {
+        let wine_folder = config.game.wine.builds.join(&wine.name);
+
+        let wine = wine
+            .to_wine(&config.components.path, Some(&wine_folder))
+            .with_prefix(&prefix)
+            .with_loader(WineLoader::Current)
+            .with_arch(WineArch::Win64);
+
+        wine.init_prefix(None::<&str>)?;
+
+        if let Some(dxvk) = dxvk {
+            let wine = crate::directx::prefix_wine(&config)?;
+
+            Dxvk::install(&wine, config.game.dxvk.builds.join(&dxvk.name), InstallParams::default())?;
+        }
+
+        let directx = Settings::get()?.directx;
+
+        for (kind, name) in [(ComponentKind::Vkd3d, directx.vkd3d), (ComponentKind::Nvapi, directx.nvapi)] {
+            if let Some(name) = name {
+                crate::directx::install_to_prefix(kind, &name, &config)?;
+            }
+        }
+
+        copy_user_folders(&old_prefix, &prefix)?;
+
+        Ok(())
+    })();
+
+    match result {
+        Ok(_) => {
+            if old_prefix.exists() {
+                std::fs::remove_dir_all(&old_prefix)?;
+            }
+
+            // Prefix is already usable, so failed verbs can be installed later from the preferences
+            if let Err(err) = crate::winetricks::reapply(|line| tracing::debug!("[winetricks] {line}")) {
+                tracing::error!("Failed to reapply winetricks verbs: {err}");
+            }
+
+            Ok(())
+        }
+
+        Err(err) => {
+            if prefix.exists() {
+                std::fs::remove_dir_all(&prefix)?;
+            }
+
+            if old_prefix.exists() {
+                std::fs::rename(&old_prefix, &prefix)?;
+            }
+
+            Err(err)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const USER_REG: &str = r#"WINE REGISTRY Version 2
+;; All keys relative to \\User\\S-1-5-21-0-0-0-1000
+
+#arch=win64
+
+[Control Panel\\Desktop] 1718035522
+#time=1dabb2ea2ecb3a4
+"ActiveWndTrkTimeout"=dword:00000000
+"DragFullWindows"="0"
+"UserPreferencesMask"=hex:9e,1e,07,80,12,00,00,00
+
+[Software\\Microsoft\\Windows\\CurrentVersion\\Explorer\\Shell Folders] 1718035522
+#time=1dabb2ea2ed0cb2
+"AppData"="C:\\users\\steamuser\\AppData\\Roaming"
+@="default"
+
+[Software\\Wine\\Fonts] 1718035523
+#time=1dabb2ea3b8e0ee
+"Cache"=hex:04,00,00,00,14,00,00,00,01,00,00,00,64,00,00,00,00,00,00,00,00,00,\
+  00,00,00,00,00,00,00,00,00,00,00,00,00,00,00,00,00,00,00,00,00,00,00,00,00,\
+  00,00,00,00,00,00,00,00
+"#;
+
+    fn check(name: &str, hive: &str) -> anyhow::Result<Option<usize>> {
+        let path = std::env::temp_dir().join(format!("prefix-health-test-{}-{name}.reg", std::process::id()));
+
+        std::fs::write(&path, hive)?;
+
+        let result = check_hive(&path);
+
+        std::fs::remove_file(&path)?;
+
+        result
+    }
+
+    #[test]
+    fn check_valid_hive() -> anyhow::Result<()> {
+        assert_eq!(check("valid", USER_REG)?, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_broken_hive() -> anyhow::Result<()> {
+        // Truncated value data continued on the next line without a backslash
+        let broken = USER_REG.replace("00,00,00,00,\\\n  00,00,00,00,00,00,00,00\n", "00,00,00,00,\n  00,00,00,00,00,00,00,00\n");
+
+        assert_eq!(check("broken", &broken)?, Some(21));
+
+        assert_eq!(check("garbage", &format!("{USER_REG}\0\0\0\0"))?, Some(22));
+        assert_eq!(check("no-header", &USER_REG[USER_REG.find('\n').unwrap() + 1..])?, Some(1));
+        assert_eq!(check("empty", "")?, Some(1));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,244 @@
+use std::path::{Path, PathBuf};
+
+use anime_launcher_sdk::wincompatlib::prelude::*;
+
+use anime_launcher_sdk::config::ConfigExt;
+use anime_launcher_sdk::wuwa::config::Schema;
+
+use crate::settings::prelude::*;
+
+/// Escape string the way wine writes it to the registry hives
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Split key path to the prefix's hive file and the key path inside of it
+fn hive(prefix: &Path, key: &str) -> Option<(PathBuf, String)> {
+    let (root, path) = key.split_once('\\').unwrap_or((key, ""));
+
+    let hive = match root.to_uppercase().as_str() {
+        "HKCU" | "HKEY_CURRENT_USER"  => "user.reg",
+        "HKLM" | "HKEY_LOCAL_MACHINE" => "system.reg",
+
+        _ => return None
+    };
+
+    Some((prefix.join(hive), path.to_string()))
+}
+
+/// Read raw value's data from the hive file, e.g. `"win7"` or `dword:00000001`
+fn read_value(hive: &Path, key: &str, name: &str) -> anyhow::Result<Option<String>> {
+    let hive = String::from_utf8_lossy(&std::fs::read(hive)?).to_string();
+
+    let section = escape(key);
+
+    let value = if name.is_empty() {
+        String::from("@=")
+    } else {
+        format!("\"{}\"=", escape(name))
+    };
+
+    let mut in_section = false;
+
+    for line in hive.lines() {
+        if let Some(header) = line.strip_prefix('[') {
+            in_section = header.rfind(']')
+                .map(|end| header[..end].eq_ignore_ascii_case(&section))
+                .unwrap_or(false);
+        }
+
+        // Compared without lowercasing the line so the data is sliced at the same byte position
+        else if in_section && line.get(..value.len()).map(|prefix| prefix.eq_ignore_ascii_case(&value)).unwrap_or(false) {
+            return Ok(Some(line[value.len()..].to_string()));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Parse dword written as decimal or `0x` prefixed hex number
+pub fn parse_dword(data: &str) -> Option<u32> {
+    let data = data.trim();
+
+    match data.strip_prefix("0x").or_else(|| data.strip_prefix("0X")) {
+        Some(hex) => u32::from_str_radix(hex, 16).ok(),
+        None => data.parse().ok()
+    }
+}
+
+/// Raw data of the tweak's value as wine writes it to the hive
+fn expected_data(tweak: &RegistryTweak) -> Option<String> {
+    match tweak.kind {
+        RegistryValueKind::String => Some(format!("\"{}\"", escape(&tweak.data))),
+        RegistryValueKind::ExpandString => Some(format!("str(2):\"{}\"", escape(&tweak.data))),
+        RegistryValueKind::Dword => parse_dword(&tweak.data).map(|dword| format!("dword:{dword:08x}"))
+    }
+}
+
+/// Check that the prefix already has the tweak's state
+///
+/// Tweaks for keys outside of `HKCU` and `HKLM` can't be checked, so they're always considered not applied
+pub fn is_applied(prefix: &Path, tweak: &RegistryTweak) -> bool {
+    let Some((hive, key)) = hive(prefix, &tweak.key) else {
+        return false;
+    };
+
+    let Ok(current) = read_value(&hive, &key, &tweak.name) else {
+        return false;
+    };
+
+    if tweak.enabled {
+        current.is_some() && current == expected_data(tweak)
+    } else {
+        current.is_none()
+    }
+}
+
+/// Apply registry tweaks from the settings to the wine prefix
+///
+/// Wine is started only if the prefix state differs from the tweaks
+pub fn apply(config: &Schema) -> anyhow::Result<()> {
+    let prefix = &config.game.wine.prefix;
+
+    let tweaks = Settings::get()?.registry.tweaks;
+
+    let pending = tweaks.iter()
+        .filter(|tweak| !is_applied(prefix, tweak))
+        .collect::<Vec<_>>();
+
+    if pending.is_empty() {
+        tracing::debug!("Registry tweaks are already applied");
+
+        return Ok(());
+    }
+
+    let Some(wine) = crate::custom_wine::get_selected(config)? else {
+        anyhow::bail!("Wine is not selected");
+    };
+
+    let wine = wine
+        .to_wine(&config.components.path, Some(config.game.wine.builds.join(&wine.name)))
+        .with_loader(WineLoader::Current)
+        .with_arch(WineArch::Win64)
+        .with_prefix(prefix);
+
+    for tweak in pending {
+        tracing::info!("Applying registry tweak: {tweak:?}");
+
+        let mut command = vec![
+            String::from("reg"),
+            String::from(if tweak.enabled { "add" } else { "delete" }),
+            tweak.key.clone()
+        ];
+
+        if tweak.name.is_empty() {
+            command.push(String::from("/ve"));
+        } else {
+            command.extend([String::from("/v"), tweak.name.clone()]);
+        }
+
+        if tweak.enabled {
+            let data = match tweak.kind {
+                RegistryValueKind::Dword => parse_dword(&tweak.data)
+                    .ok_or_else(|| anyhow::anyhow!("Invalid dword value: {}", tweak.data))?
+                    .to_string(),
+
+                _ => tweak.data.clone()
+            };
+
+            command.extend([
+                String::from("/t"), tweak.kind.reg_type().to_string(),
+                String::from("/d"), data
+            ]);
+        }
+
+        command.push(String::from("/f"));
+
+        let status = wine.run_args_with_env(&command, config.game.wine.sync.get_env_vars())?.wait()?;
+
+        if !status.success() {
+            tracing::warn!("Registry command {command:?} exited with {status}");
+        }
+    }
+
+    wine.shutdown()?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const USER_REG: &str = r#"WINE REGISTRY Version 2
+;; All keys relative to \\User\\S-1-5-21-0-0-0-1000
+
+#arch=win64
+
+[Control Panel\\Desktop] 1718035522
+#time=1dabb2ea2ecb3a4
+"LogPixels"=dword:00000060
+"UserPreferencesMask"=hex:9e,1e,07,80,12,00,00,00
+
+[Software\\Wine] 1718035600
+#time=1dabb2ecd6a5c2e
+"Version"="win7"
+
+[Software\\Wine\\DllOverrides] 1718035600
+#time=1dabb2ecd6a5c2e
+"d3d11"="native"
+"krsdkexternal.exe"=""
+"#;
+
+    fn tweak(key: &str, name: &str, kind: RegistryValueKind, data: &str, enabled: bool) -> RegistryTweak {
+        RegistryTweak {
+            key: key.to_string(),
+            name: name.to_string(),
+            kind,
+            data: data.to_string(),
+            enabled
+        }
+    }
+
+    #[test]
+    fn parse_dword_values() {
+        assert_eq!(parse_dword("42"), Some(42));
+        assert_eq!(parse_dword(" 7 "), Some(7));
+        assert_eq!(parse_dword("0x60"), Some(96));
+        assert_eq!(parse_dword("0XfF"), Some(255));
+        assert_eq!(parse_dword("0xffffffff"), Some(u32::MAX));
+
+        assert_eq!(parse_dword(""), None);
+        assert_eq!(parse_dword("0x"), None);
+        assert_eq!(parse_dword("-1"), None);
+        assert_eq!(parse_dword("0x100000000"), None);
+        assert_eq!(parse_dword("ten"), None);
+    }
+
+    #[test]
+    fn check_applied_tweaks() -> anyhow::Result<()> {
+        let prefix = std::env::temp_dir().join(format!("registry-tweaks-test-{}", std::process::id()));
+
+        std::fs::create_dir_all(&prefix)?;
+        std::fs::write(prefix.join("user.reg"), USER_REG)?;
+
+        let string = RegistryValueKind::String;
+        let dword = RegistryValueKind::Dword;
+
+        assert!(is_applied(&prefix, &tweak("HKCU\\Software\\Wine", "Version", string, "win7", true)));
+        assert!(is_applied(&prefix, &tweak("hkey_current_user\\software\\wine", "version", string, "win7", true)));
+        assert!(is_applied(&prefix, &tweak("HKCU\\Software\\Wine\\DllOverrides", "krsdkexternal.exe", string, "", true)));
+        assert!(is_applied(&prefix, &tweak("HKCU\\Control Panel\\Desktop", "LogPixels", dword, "0x60", true)));
+        assert!(is_applied(&prefix, &tweak("HKCU\\Control Panel\\Desktop", "LogPixels", dword, "96", true)));
+        assert!(is_applied(&prefix, &tweak("HKCU\\Software\\Wine", "Missing", string, "", false)));
+
+        assert!(!is_applied(&prefix, &tweak("HKCU\\Software\\Wine", "Version", string, "win10", true)));
+        assert!(!is_applied(&prefix, &tweak("HKCU\\Software\\Wine", "Version", string, "win7", false)));
+        assert!(!is_applied(&prefix, &tweak("HKCU\\Software\\Wine\\DllOverrides", "Version", string, "win7", true)));
+        assert!(!is_applied(&prefix, &tweak("HKCR\\Software\\Wine", "Version", string, "win7", true)));
+
+        std::fs::remove_dir_all(&prefix)?;
+
+        Ok(())
+    }
+}
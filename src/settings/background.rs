@@ -0,0 +1,135 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use serde::{Serialize, Deserialize};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "type", content = "path")]
+pub enum BackgroundSource {
+    /// Official background picture downloaded from the game's website
+    #[default]
+    Official,
+
+    /// Local image file
+    File(PathBuf),
+
+    /// Local folder with images which will be rotated on each launcher start
+    Folder(PathBuf)
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BackgroundScaling {
+    /// Scale the image to cover the whole window, cutting its sides
+    #[default]
+    Cover,
+
+    /// Scale the image to fit the window, adding black bars
+    Contain,
+
+    /// Stretch the image to the window size
+    Stretch
+}
+
+impl BackgroundScaling {
+    #[inline]
+    pub fn list() -> &'static [Self] {
+        &[Self::Cover, Self::Contain, Self::Stretch]
+    }
+}
+
+/// Area of the source image which should be used as background, in the image's pixels
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct BackgroundCrop {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32
+}
+
+/// Processing parameters of a single local image
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BackgroundImage {
+    pub crop: Option<BackgroundCrop>,
+    pub scaling: BackgroundScaling
+}
+
+/// Effects applied to any background picture to make launcher controls more legible
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BackgroundEffects {
+    /// Blur radius, `0` to disable
+    pub blur: f32,
+
+    /// Dimming strength from `0` (disabled) to `1` (black picture)
+    pub dim: f32
+}
+
+/// Looping muted video or animated image played behind the classic style controls
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BackgroundVideo {
+    pub enabled: bool,
+
+    /// Local video or animated image file, or `None` to use the official video
+    pub path: Option<PathBuf>,
+
+    /// Show the static background picture instead when running on battery
+    pub disable_on_battery: bool,
+
+    /// Cached state of the official video
+    pub official: OfficialBackground
+}
+
+impl Default for BackgroundVideo {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            path: None,
+            disable_on_battery: true,
+            official: OfficialBackground::default()
+        }
+    }
+}
+
+/// Cached state of the official background picture
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OfficialBackground {
+    /// `ETag` header of the downloaded picture
+    pub etag: Option<String>,
+
+    /// `Last-Modified` header of the downloaded picture
+    pub last_modified: Option<String>,
+
+    /// MD5 hash of the downloaded picture
+    pub hash: Option<String>,
+
+    /// UNIX timestamp of the last successful check for the picture changes
+    pub checked_at: u64
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Background {
+    pub source: BackgroundSource,
+    pub effects: BackgroundEffects,
+    pub official: OfficialBackground,
+    pub video: BackgroundVideo,
+
+    /// Processing parameters of local images
+    pub images: HashMap<PathBuf, BackgroundImage>,
+
+    /// Last image used from the `BackgroundSource::Folder` source
+    pub current: Option<PathBuf>
+}
+
+impl Background {
+    /// Get processing parameters of the given image, or default ones
+    #[inline]
+    pub fn image(&self, path: &Path) -> BackgroundImage {
+        self.images.get(path).copied().unwrap_or_default()
+    }
+}
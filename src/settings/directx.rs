@@ -0,0 +1,48 @@
+use std::path::{Path, PathBuf};
+
+use serde::{Serialize, Deserialize};
+
+use crate::checksums::ComponentKind;
+
+/// DirectX translation layers installed to the wine prefix in addition to DXVK
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DirectX {
+    /// Name of the installed VKD3D-Proton build
+    pub vkd3d: Option<String>,
+
+    /// Name of the installed DXVK-NVAPI build
+    pub nvapi: Option<String>,
+
+    pub builds: DirectXBuilds
+}
+
+/// Folders with downloaded builds, like the wine and DXVK ones in the config
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DirectXBuilds {
+    pub vkd3d: PathBuf,
+    pub nvapi: PathBuf
+}
+
+impl Default for DirectXBuilds {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            vkd3d: crate::LAUNCHER_FOLDER.join(ComponentKind::Vkd3d.folder()),
+            nvapi: crate::LAUNCHER_FOLDER.join(ComponentKind::Nvapi.folder())
+        }
+    }
+}
+
+impl DirectXBuilds {
+    /// Get folder with downloaded builds of the given kind
+    pub fn get(&self, kind: ComponentKind) -> &Path {
+        match kind {
+            ComponentKind::Nvapi => &self.nvapi,
+
+            // Wine and DXVK folders are stored in the config
+            _ => &self.vkd3d
+        }
+    }
+}
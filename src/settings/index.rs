@@ -0,0 +1,12 @@
+use serde::{Serialize, Deserialize};
+
+/// Last successful components index sync from a server
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct IndexSync {
+    /// UNIX timestamp of the sync
+    pub synced_at: u64,
+
+    /// Changes list reported by the sync
+    pub changes: Vec<String>
+}
@@ -0,0 +1,139 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use serde::{Serialize, Deserialize};
+
+use anime_launcher_sdk::config::ConfigExt;
+use anime_launcher_sdk::wuwa::config::Config;
+
+pub mod background;
+pub mod wine;
+pub mod index;
+pub mod upgrade;
+pub mod directx;
+pub mod snapshots;
+pub mod registry;
+
+pub mod prelude {
+    pub use super::Settings;
+
+    pub use super::background::*;
+    pub use super::wine::*;
+    pub use super::index::*;
+    pub use super::upgrade::*;
+    pub use super::directx::*;
+    pub use super::snapshots::*;
+    pub use super::registry::*;
+}
+
+/// Launcher-specific settings which are not covered by the SDK's config schema
+///
+/// Stored in `settings.json` next to the main config file
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub background: background::Background,
+
+    /// Wine builds added from local folders
+    pub custom_wine: Vec<wine::CustomWine>,
+
+    /// Components index server -> its last successful sync
+    pub index_syncs: HashMap<String, index::IndexSync>,
+
+    pub components_upgrade: upgrade::ComponentsUpgrade,
+
+    pub directx: directx::DirectX,
+
+    pub prefix_snapshots: snapshots::PrefixSnapshots,
+
+    /// Winetricks verbs installed to the wine prefix, reapplied after its rebuild
+    pub winetricks: Vec<String>,
+
+    pub registry: registry::RegistryTweaks
+}
+
+/// Settings are changed from the UI and background threads,
+/// so all the writes are serialized to not lose each other's changes
+static SETTINGS_LOCK: Mutex<()> = Mutex::new(());
+
+impl Settings {
+    /// Read settings from the `settings.json` file, or get default ones if it doesn't exist
+    pub fn get() -> anyhow::Result<Self> {
+        if !crate::SETTINGS_FILE.exists() {
+            return Ok(Self::default());
+        }
+
+        let settings = std::fs::read(crate::SETTINGS_FILE.as_path())?;
+
+        Ok(serde_json::from_slice(&settings)?)
+    }
+
+    /// Write settings to the temp file and move it to `settings.json`,
+    /// so the file is never left half-written
+    fn write(settings: &Self) -> anyhow::Result<()> {
+        let temp = crate::SETTINGS_FILE.with_extension("json.tmp");
+
+        std::fs::write(&temp, serde_json::to_string_pretty(settings)?)?;
+        std::fs::rename(&temp, crate::SETTINGS_FILE.as_path())?;
+
+        Ok(())
+    }
+
+    /// Replace the `settings.json` file with the given settings
+    ///
+    /// Prefer `modify` because changes made by other threads
+    /// after these settings were read are overwritten
+    pub fn update(settings: Self) -> anyhow::Result<()> {
+        let _lock = SETTINGS_LOCK.lock().unwrap_or_else(|err| err.into_inner());
+
+        Self::write(&settings)
+    }
+
+    /// Move launcher options from the config to the settings
+    /// when `settings.json` doesn't have them yet
+    ///
+    /// Called once on the launcher start
+    pub fn migrate() -> anyhow::Result<()> {
+        let _lock = SETTINGS_LOCK.lock().unwrap_or_else(|err| err.into_inner());
+
+        let stored = if crate::SETTINGS_FILE.exists() {
+            serde_json::from_slice::<serde_json::Value>(&std::fs::read(crate::SETTINGS_FILE.as_path())?)?
+        } else {
+            serde_json::Value::Null
+        };
+
+        // Launch dialog fix was the `fix_launch_dialog` config option before registry tweaks
+        if stored.get("registry").is_none() {
+            let fix_launch_dialog = Config::get()?.game.enhancements.fix_launch_dialog;
+
+            tracing::info!("Migrating launch dialog fix to the registry tweaks: {fix_launch_dialog}");
+
+            let mut settings = Self::get()?;
+
+            for tweak in &mut settings.registry.tweaks {
+                if tweak.name == registry::LAUNCH_DIALOG_FIX {
+                    tweak.enabled = fix_launch_dialog;
+                }
+            }
+
+            Self::write(&settings)?;
+        }
+
+        Ok(())
+    }
+
+    /// Read, change and write settings while no other thread can change them
+    ///
+    /// `modify` must not be called from the callback
+    pub fn modify<T>(callback: impl FnOnce(&mut Self) -> T) -> anyhow::Result<T> {
+        let _lock = SETTINGS_LOCK.lock().unwrap_or_else(|err| err.into_inner());
+
+        let mut settings = Self::get()?;
+
+        let result = callback(&mut settings);
+
+        Self::write(&settings)?;
+
+        Ok(result)
+    }
+}
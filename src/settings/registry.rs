@@ -0,0 +1,79 @@
+use serde::{Serialize, Deserialize};
+
+/// Value name of the launch dialog fix, which replaced the `fix_launch_dialog` config option
+pub const LAUNCH_DIALOG_FIX: &str = "krsdkexternal.exe";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RegistryValueKind {
+    String,
+    ExpandString,
+    Dword
+}
+
+impl RegistryValueKind {
+    #[inline]
+    pub fn list() -> &'static [Self] {
+        &[Self::String, Self::ExpandString, Self::Dword]
+    }
+
+    /// Type name used by the `reg` command
+    #[inline]
+    pub fn reg_type(&self) -> &'static str {
+        match self {
+            Self::String       => "REG_SZ",
+            Self::ExpandString => "REG_EXPAND_SZ",
+            Self::Dword        => "REG_DWORD"
+        }
+    }
+}
+
+/// Registry value set in the wine prefix before launching the game
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct RegistryTweak {
+    /// Full key path, e.g. `HKCU\Software\Wine`
+    pub key: String,
+
+    /// Value name. Empty string means the key's default value
+    pub name: String,
+
+    pub kind: RegistryValueKind,
+
+    /// Value data. Dwords can be written as decimal or `0x` prefixed hex numbers
+    pub data: String,
+
+    /// Disabled tweaks are removed from the prefix
+    pub enabled: bool
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RegistryTweaks {
+    pub tweaks: Vec<RegistryTweak>
+}
+
+impl Default for RegistryTweaks {
+    fn default() -> Self {
+        Self {
+            tweaks: vec![
+                // Fix for the blank launch dialog
+                RegistryTweak {
+                    key: String::from("HKCU\\Software\\Wine\\DllOverrides"),
+                    name: String::from(LAUNCH_DIALOG_FIX),
+                    kind: RegistryValueKind::String,
+                    data: String::new(),
+                    enabled: true
+                },
+
+                // Fix for the in-game browser being a black window
+                RegistryTweak {
+                    key: String::from("HKCU\\Software\\Wine"),
+                    name: String::from("Version"),
+                    kind: RegistryValueKind::String,
+                    data: String::from("win7"),
+                    enabled: true
+                }
+            ]
+        }
+    }
+}
@@ -0,0 +1,22 @@
+use serde::{Serialize, Deserialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PrefixSnapshots {
+    /// Make snapshot of the wine prefix before switching wine, DXVK or other components.
+    /// Disabled by default because prefixes are archived on filesystems without reflink support
+    pub automatic: bool,
+
+    /// Amount of automatic snapshots to keep, older ones are removed
+    pub keep_automatic: usize
+}
+
+impl Default for PrefixSnapshots {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            automatic: false,
+            keep_automatic: 3
+        }
+    }
+}
@@ -0,0 +1,35 @@
+use serde::{Serialize, Deserialize};
+
+/// What to do when the components index publishes new recommended wine or DXVK builds
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UpgradePolicy {
+    /// Don't check for new builds
+    #[default]
+    Disabled,
+
+    /// Show a notification on the launcher start
+    Notify,
+
+    /// Download and select new builds on the launcher start
+    Install
+}
+
+impl UpgradePolicy {
+    #[inline]
+    pub fn list() -> &'static [Self] {
+        &[Self::Disabled, Self::Notify, Self::Install]
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ComponentsUpgrade {
+    pub policy: UpgradePolicy,
+
+    /// Name of the wine build selected before the last upgrade
+    pub previous_wine: Option<String>,
+
+    /// Name of the DXVK build selected before the last upgrade
+    pub previous_dxvk: Option<String>
+}
@@ -0,0 +1,16 @@
+use std::path::PathBuf;
+
+use serde::{Serialize, Deserialize};
+
+/// Wine build from a local folder which is not listed in the components index
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct CustomWine {
+    /// Unique name used as the build's folder name and in the config
+    pub name: String,
+
+    /// Folder with the `bin/wine` binary
+    pub path: PathBuf,
+
+    /// Output of the `wine --version` command
+    pub version: String
+}
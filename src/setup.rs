@@ -0,0 +1,395 @@
+use std::path::{Path, PathBuf};
+use std::io::Write;
+
+use serde::Deserialize;
+
+use anime_launcher_sdk::anime_game_core::prelude::*;
+use anime_launcher_sdk::wincompatlib::prelude::*;
+
+use anime_launcher_sdk::components::*;
+use anime_launcher_sdk::components::loader::ComponentsLoader;
+use anime_launcher_sdk::components::wine::UnifiedWine;
+
+use anime_launcher_sdk::config::ConfigExt;
+use anime_launcher_sdk::wuwa::config::{Config, Schema};
+
+use crate::*;
+use crate::checksums::{self, ComponentKind};
+use crate::settings::prelude::*;
+use crate::ui::first_run::default_paths::Folders;
+
+/// Seed file used to run the first run setup without user interaction
+///
+/// Can be written in TOML or JSON format. Paths which are not set
+/// keep their default values, and the first listed wine and DXVK
+/// versions are used if they're not specified
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct Seed {
+    pub accept_tos: bool,
+
+    /// `global` or `china`
+    pub edition: Option<String>,
+
+    pub voices: Vec<String>,
+
+    pub paths: SeedPaths,
+    pub components: SeedComponents
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct SeedPaths {
+    pub runners: Option<PathBuf>,
+    pub dxvks: Option<PathBuf>,
+    pub vkd3d: Option<PathBuf>,
+    pub nvapi: Option<PathBuf>,
+    pub prefix: Option<PathBuf>,
+    pub game_global: Option<PathBuf>,
+    pub game_china: Option<PathBuf>,
+    pub components: Option<PathBuf>,
+    pub patch: Option<PathBuf>,
+    pub temp: Option<PathBuf>
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct SeedComponents {
+    /// Wine version name, e.g. `lutris-GE-Proton8-26-x86_64`
+    pub wine: Option<String>,
+
+    /// DXVK version name, e.g. `dxvk-2.3`
+    pub dxvk: Option<String>
+}
+
+impl Seed {
+    pub fn from_file(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let seed = std::fs::read_to_string(path)?;
+
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => Ok(serde_json::from_str(&seed)?),
+            _ => Ok(toml::from_str(&seed)?)
+        }
+    }
+
+    pub fn edition(&self) -> anyhow::Result<Option<GameEdition>> {
+        match self.edition.as_deref().map(str::to_ascii_lowercase).as_deref() {
+            None => Ok(None),
+
+            Some("global") => Ok(Some(GameEdition::Global)),
+            Some("china")  => Ok(Some(GameEdition::China)),
+
+            Some(edition) => anyhow::bail!("Unknown game edition: {edition}")
+        }
+    }
+}
+
+/// Unattended setup error. Each variant has its own process exit code
+#[derive(Debug)]
+pub enum SetupError {
+    Seed(anyhow::Error),
+    TosNotAccepted,
+    MissingDependencies(Vec<&'static str>),
+    Paths(String),
+    Config(anyhow::Error),
+    ComponentsIndex(anyhow::Error),
+    Wine(anyhow::Error),
+    Prefix(anyhow::Error),
+    Dxvk(anyhow::Error)
+}
+
+impl SetupError {
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Self::Seed(_)                => 2,
+            Self::TosNotAccepted         => 3,
+            Self::MissingDependencies(_) => 4,
+            Self::Paths(_)               => 5,
+            Self::Config(_)              => 6,
+            Self::ComponentsIndex(_)     => 7,
+            Self::Wine(_)                => 8,
+            Self::Prefix(_)              => 9,
+            Self::Dxvk(_)                => 10
+        }
+    }
+}
+
+impl std::fmt::Display for SetupError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Seed(err)      => write!(f, "Failed to read seed file: {err}"),
+            Self::TosNotAccepted => write!(f, "Terms of service are not accepted, set `accept_tos = true` in the seed file"),
+
+            Self::MissingDependencies(dependencies) => write!(f, "Missing required dependencies: {}", dependencies.join(", ")),
+
+            Self::Paths(err)           => write!(f, "Chosen folders can't be used:\n{err}"),
+            Self::Config(err)          => write!(f, "Failed to update config: {err}"),
+            Self::ComponentsIndex(err) => write!(f, "Failed to sync components index: {err}"),
+            Self::Wine(err)            => write!(f, "Failed to install wine: {err}"),
+            Self::Prefix(err)          => write!(f, "Failed to create wine prefix: {err}"),
+            Self::Dxvk(err)            => write!(f, "Failed to install DXVK: {err}")
+        }
+    }
+}
+
+impl std::error::Error for SetupError {}
+
+const STEPS: usize = 7;
+
+fn step(number: usize, title: &str) {
+    println!("[{number}/{STEPS}] {title}");
+}
+
+/// Run the first run steps using the given seed file
+pub fn run(seed: impl AsRef<Path>) -> Result<(), SetupError> {
+    step(1, "Reading seed file");
+
+    let seed = Seed::from_file(seed).map_err(SetupError::Seed)?;
+    let edition = seed.edition().map_err(SetupError::Seed)?;
+
+    if !seed.accept_tos {
+        return Err(SetupError::TosNotAccepted);
+    }
+
+    if !seed.voices.is_empty() {
+        println!("Voice packages are not supported yet and will be ignored: {}", seed.voices.join(", "));
+    }
+
+    step(2, "Checking dependencies");
+
+    let missing = crate::dependencies::missing();
+
+    for dependency in missing.iter().filter(|dependency| !dependency.required) {
+        println!("Optional dependency is missing: {} ({})", dependency.name(), tr!(dependency.feature));
+    }
+
+    let required = missing.iter()
+        .filter(|dependency| dependency.required)
+        .map(|dependency| dependency.name())
+        .collect::<Vec<_>>();
+
+    if !required.is_empty() {
+        return Err(SetupError::MissingDependencies(required));
+    }
+
+    step(3, "Updating default paths");
+
+    let mut config = Config::get().map_err(SetupError::Config)?;
+
+    let paths = &seed.paths;
+
+    if let Some(edition) = edition {
+        config.launcher.edition = edition;
+    }
+
+    if let Some(path) = &paths.runners     { config.game.wine.builds.clone_from(path); }
+    if let Some(path) = &paths.dxvks       { config.game.dxvk.builds.clone_from(path); }
+    if let Some(path) = &paths.prefix      { config.game.wine.prefix.clone_from(path); }
+    if let Some(path) = &paths.game_global { config.game.path.global.clone_from(path); }
+    if let Some(path) = &paths.game_china  { config.game.path.china.clone_from(path); }
+    if let Some(path) = &paths.components  { config.components.path.clone_from(path); }
+    if let Some(path) = &paths.patch       { config.patch.path.clone_from(path); }
+
+    if let Some(path) = &paths.temp {
+        config.launcher.temp = Some(path.clone());
+    }
+
+    let temp = config.launcher.temp.clone().unwrap_or_else(std::env::temp_dir);
+
+    let mut directx_builds = Settings::get().map_err(SetupError::Config)?.directx.builds;
+
+    if let Some(path) = &paths.vkd3d { directx_builds.vkd3d.clone_from(path); }
+    if let Some(path) = &paths.nvapi { directx_builds.nvapi.clone_from(path); }
+
+    let mut problems = Vec::new();
+
+    for folder in Folders::list() {
+        let path = match folder {
+            Folders::Launcher   => LAUNCHER_FOLDER.as_path(),
+            Folders::Runners    => config.game.wine.builds.as_path(),
+            Folders::DXVK       => config.game.dxvk.builds.as_path(),
+            Folders::Vkd3d      => directx_builds.vkd3d.as_path(),
+            Folders::Nvapi      => directx_builds.nvapi.as_path(),
+            Folders::Prefix     => config.game.wine.prefix.as_path(),
+            Folders::GameGlobal => config.game.path.global.as_path(),
+            Folders::GameChina  => config.game.path.china.as_path(),
+            Folders::Components => config.components.path.as_path(),
+            Folders::Patch      => config.patch.path.as_path(),
+            Folders::Temp       => temp.as_path()
+        };
+
+        // Only the selected game edition will be installed
+        let required = match folder {
+            Folders::GameGlobal if config.launcher.edition != GameEdition::Global => 0,
+            Folders::GameChina  if config.launcher.edition != GameEdition::China  => 0,
+
+            _ => folder.expected_size()
+        };
+
+        for warning in crate::filesystem::check_path(path, required, folder.is_executable()) {
+            if warning.is_critical() {
+                problems.push(format!("{path:?}: {}", warning.describe()));
+            } else {
+                println!("Warning: {path:?}: {}", warning.describe());
+            }
+        }
+    }
+
+    if !problems.is_empty() {
+        return Err(SetupError::Paths(problems.join("\n")));
+    }
+
+    Settings::modify(|settings| settings.directx.builds = directx_builds).map_err(SetupError::Config)?;
+
+    Config::update_raw(config.clone()).map_err(SetupError::Config)?;
+
+    step(4, "Updating components index");
+
+    let components = ComponentsLoader::new(&config.components.path);
+
+    match components.is_sync(&config.components.servers) {
+        Ok(Some(_)) => (),
+
+        Ok(None) => {
+            let mut result = Err(anyhow::anyhow!("No components index servers set"));
+
+            for host in &config.components.servers {
+                result = crate::components_index::sync(&components, host).map(|_| ());
+
+                if result.is_ok() {
+                    break;
+                }
+            }
+
+            result.map_err(SetupError::ComponentsIndex)?;
+        }
+
+        Err(err) => return Err(SetupError::ComponentsIndex(err))
+    }
+
+    let wine = find_version(
+        wine::get_groups(&config.components.path).map_err(SetupError::Wine)?
+            .into_iter()
+            .flat_map(|group| group.versions),
+        seed.components.wine.as_deref(),
+        |version: &wine::Version| version.name.as_str()
+    ).map_err(SetupError::Wine)?;
+
+    let dxvk = find_version(
+        dxvk::get_groups(&config.components.path).map_err(SetupError::Dxvk)?
+            .into_iter()
+            .flat_map(|group| group.versions),
+        seed.components.dxvk.as_deref(),
+        |version: &dxvk::Version| version.name.as_str()
+    ).map_err(SetupError::Dxvk)?;
+
+    step(5, &format!("Installing wine: {}", wine.title));
+
+    if wine.is_downloaded_in(&config.game.wine.builds) {
+        println!("Wine is already installed");
+    } else {
+        install(ComponentKind::Wine, &wine.name, &wine.uri, &config.game.wine.builds, &config)
+            .map_err(SetupError::Wine)?;
+    }
+
+    config.game.wine.selected = Some(wine.name.clone());
+
+    Config::update_raw(config.clone()).map_err(SetupError::Config)?;
+
+    step(6, &format!("Creating wine prefix: {:?}", config.game.wine.prefix));
+
+    let unified_wine = wine
+        .to_wine(config.components.path.clone(), Some(config.game.wine.builds.join(&wine.name)))
+        .with_prefix(&config.game.wine.prefix)
+        .with_loader(WineLoader::Current)
+        .with_arch(WineArch::Win64);
+
+    unified_wine.init_prefix(None::<&str>)
+        .map_err(|err| SetupError::Prefix(err.into()))?;
+
+    step(7, &format!("Installing DXVK: {}", dxvk.name));
+
+    let group = wine.find_group(&config.components.path)
+        .map_err(SetupError::Dxvk)?;
+
+    let need_dxvk = match group {
+        Some(group) => wine.features_in(&group).unwrap_or_default().need_dxvk,
+        None => true
+    };
+
+    if !need_dxvk {
+        println!("Selected wine version doesn't need DXVK");
+    }
+
+    else if let UnifiedWine::Default(unified_wine) = unified_wine {
+        if dxvk.is_downloaded_in(&config.game.dxvk.builds) {
+            println!("DXVK is already installed");
+        } else {
+            install(ComponentKind::Dxvk, &dxvk.name, &dxvk.uri, &config.game.dxvk.builds, &config)
+                .map_err(SetupError::Dxvk)?;
+        }
+
+        let params = InstallParams {
+            // We just created prefix so don't need to repair it
+            repair_dlls: false,
+
+            ..InstallParams::default()
+        };
+
+        unified_wine.install_dxvk(config.game.dxvk.builds.join(&dxvk.name), params)
+            .map_err(|err| SetupError::Dxvk(err.into()))?;
+    }
+
+    if FIRST_RUN_FILE.exists() {
+        std::fs::remove_file(FIRST_RUN_FILE.as_path())
+            .map_err(|err| SetupError::Config(err.into()))?;
+    }
+
+    println!("Setup finished");
+
+    Ok(())
+}
+
+/// Find version with the given name, or the first one if name is not specified
+fn find_version<T>(versions: impl IntoIterator<Item = T>, name: Option<&str>, get_name: impl Fn(&T) -> &str) -> anyhow::Result<T> {
+    let mut versions = versions.into_iter();
+
+    let version = match name {
+        Some(name) => versions.find(|version| get_name(version) == name),
+        None => versions.next()
+    };
+
+    match (version, name) {
+        (Some(version), _) => Ok(version),
+        (None, Some(name)) => anyhow::bail!("Version is not available: {name}"),
+        (None, None) => anyhow::bail!("No versions available")
+    }
+}
+
+fn print_progress(current: u64, total: u64) {
+    print!("\r{:.2}%", current as f64 / total.max(1) as f64 * 100.0);
+
+    let _ = std::io::stdout().flush();
+}
+
+/// Download, verify and unpack component archive, printing progress to stdout
+fn install(kind: ComponentKind, name: &str, uri: &str, folder: &Path, config: &Schema) -> anyhow::Result<()> {
+    if checksums::get(&config.components.path, kind, name).is_none() {
+        println!("Checksum unavailable, {name} archive won't be verified");
+    }
+
+    checksums::install(kind, name, uri, folder, config, |update| {
+        match update {
+            InstallerUpdate::DownloadingStarted(_) => println!("Downloading..."),
+            InstallerUpdate::UnpackingStarted(_) => println!("\nUnpacking..."),
+
+            InstallerUpdate::DownloadingProgress(current, total) |
+            InstallerUpdate::UnpackingProgress(current, total) => print_progress(current, total),
+
+            InstallerUpdate::UnpackingFinished => println!(),
+
+            _ => ()
+        }
+    })
+}
@@ -0,0 +1,332 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Serialize, Deserialize};
+
+use anime_launcher_sdk::config::ConfigExt;
+use anime_launcher_sdk::wuwa::config::Config;
+
+use crate::checksums::ComponentKind;
+use crate::settings::prelude::*;
+use crate::SNAPSHOTS_FOLDER;
+
+/// Prefixes larger than this size are not archived by automatic snapshots,
+/// reflink copies are still made as they don't take time and space
+pub const AUTOMATIC_ARCHIVE_SIZE_LIMIT: u64 = 4 * 1024 * 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SnapshotFormat {
+    /// Reflink copy of the prefix folder, only available on CoW filesystems like btrfs or xfs
+    Copy,
+
+    /// `tar.gz` archive of the prefix folder
+    Archive
+}
+
+/// Components selected when the snapshot was made
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotComponents {
+    pub wine: Option<String>,
+
+    /// DXVK is installed to the prefix files, so it's restored with them.
+    /// The name is kept to show what the snapshot contains
+    pub dxvk: Option<String>,
+
+    pub directx: DirectX
+}
+
+/// Wine prefix snapshot
+///
+/// Described by the `<id>.json` file in the snapshots folder,
+/// stored next to it as `<id>` folder or `<id>.tar.gz` archive
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub id: String,
+
+    /// Snapshot name given by the user. For automatic snapshots
+    /// it's the name of the component which was being selected
+    pub name: String,
+
+    /// Unix timestamp in seconds
+    pub created_at: u64,
+
+    pub automatic: bool,
+    pub format: SnapshotFormat,
+
+    /// Not available in snapshots made by older launcher versions
+    #[serde(default)]
+    pub components: Option<SnapshotComponents>
+}
+
+impl Snapshot {
+    #[inline]
+    pub fn path(&self) -> PathBuf {
+        match self.format {
+            SnapshotFormat::Copy => SNAPSHOTS_FOLDER.join(&self.id),
+            SnapshotFormat::Archive => SNAPSHOTS_FOLDER.join(format!("{}.tar.gz", self.id))
+        }
+    }
+
+    /// Size of the snapshot in bytes
+    ///
+    /// Reflink copies share data with the prefix, so the real used space can be smaller
+    #[inline]
+    pub fn size(&self) -> u64 {
+        crate::disk_usage::size(self.path())
+    }
+}
+
+fn run(command: &mut Command) -> anyhow::Result<()> {
+    let output = command.output()?;
+
+    if !output.status.success() {
+        anyhow::bail!("{}", String::from_utf8_lossy(&output.stderr).trim());
+    }
+
+    Ok(())
+}
+
+/// Copy or unpack the snapshot data to the given folder
+fn unpack(format: SnapshotFormat, from: &Path, to: &Path) -> anyhow::Result<()> {
+    match format {
+        SnapshotFormat::Copy => run(Command::new("cp").arg("-a").arg("--reflink=auto").arg(from).arg(to)),
+
+        SnapshotFormat::Archive => {
+            std::fs::create_dir_all(to)?;
+
+            run(Command::new("tar").arg("-xzf").arg(from).arg("-C").arg(to))
+        }
+    }
+}
+
+/// Get list of the snapshots, newest first
+pub fn list() -> anyhow::Result<Vec<Snapshot>> {
+    if !SNAPSHOTS_FOLDER.exists() {
+        return Ok(vec![]);
+    }
+
+    let mut snapshots = SNAPSHOTS_FOLDER.read_dir()?
+        .flatten()
+        .filter(|entry| entry.path().extension().map(|ext| ext == "json").unwrap_or(false))
+        .filter_map(|entry| std::fs::read(entry.path()).ok())
+        .filter_map(|snapshot| serde_json::from_slice::<Snapshot>(&snapshot).ok())
+        .filter(|snapshot| snapshot.path().exists())
+        .collect::<Vec<_>>();
+
+    snapshots.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+
+    Ok(snapshots)
+}
+
+/// Make snapshot of the wine prefix
+///
+/// Reflink copy is tried first as it's instant and doesn't take additional space,
+/// otherwise the prefix is packed to a `tar.gz` archive. Automatic snapshots
+/// of the prefixes larger than `AUTOMATIC_ARCHIVE_SIZE_LIMIT` are not archived
+pub fn create(name: impl ToString, automatic: bool) -> anyhow::Result<Snapshot> {
+    let config = Config::get()?;
+    let prefix = config.game.wine.prefix.clone();
+
+    if !prefix.exists() {
+        anyhow::bail!("Wine prefix doesn't exist: {prefix:?}");
+    }
+
+    let components = SnapshotComponents {
+        wine: config.game.wine.selected.clone(),
+        dxvk: config.get_selected_dxvk().ok().flatten().map(|dxvk| dxvk.name),
+        directx: Settings::get()?.directx
+    };
+
+    std::fs::create_dir_all(SNAPSHOTS_FOLDER.as_path())?;
+
+    let created_at = SystemTime::now().duration_since(UNIX_EPOCH)?;
+
+    let mut snapshot = Snapshot {
+        id: created_at.as_millis().to_string(),
+        name: name.to_string(),
+        created_at: created_at.as_secs(),
+        automatic,
+        format: SnapshotFormat::Copy,
+        components: Some(components)
+    };
+
+    tracing::info!("Creating wine prefix snapshot: {snapshot:?}");
+
+    let copied = run(Command::new("cp")
+        .arg("-a")
+        .arg("--reflink=always")
+        .arg(&prefix)
+        .arg(snapshot.path()));
+
+    if let Err(err) = copied {
+        tracing::debug!("Failed to make reflink copy of the wine prefix, packing it instead: {err}");
+
+        // Partial copy can be left by the failed cp
+        if snapshot.path().exists() {
+            std::fs::remove_dir_all(snapshot.path())?;
+        }
+
+        if automatic {
+            let size = crate::disk_usage::size(&prefix);
+
+            if size > AUTOMATIC_ARCHIVE_SIZE_LIMIT {
+                anyhow::bail!("Wine prefix is too large to be archived automatically: {} GB", size / 1024 / 1024 / 1024);
+            }
+        }
+
+        snapshot.format = SnapshotFormat::Archive;
+
+        let packed = run(Command::new("tar")
+            .arg("-czf")
+            .arg(snapshot.path())
+            .arg("-C")
+            .arg(&prefix)
+            .arg("."));
+
+        if let Err(err) = packed {
+            if snapshot.path().exists() {
+                std::fs::remove_file(snapshot.path())?;
+            }
+
+            return Err(err);
+        }
+    }
+
+    std::fs::write(SNAPSHOTS_FOLDER.join(format!("{}.json", snapshot.id)), serde_json::to_string_pretty(&snapshot)?)?;
+
+    Ok(snapshot)
+}
+
+/// Make automatic snapshot before selecting the given component, if enabled in the settings
+///
+/// Archiving the prefix can take minutes, so it must be called from a worker thread.
+/// Failed snapshot shouldn't stop the component's selection, the error is meant to be shown to the user
+pub fn create_automatic(component: impl AsRef<str>) -> anyhow::Result<()> {
+    let settings = Settings::get()?.prefix_snapshots;
+
+    if !settings.automatic || !Config::get()?.game.wine.prefix.exists() {
+        return Ok(());
+    }
+
+    if let Err(err) = create(component.as_ref(), true) {
+        tracing::error!("Failed to make automatic wine prefix snapshot: {err}");
+
+        return Err(err);
+    }
+
+    // Remove the oldest automatic snapshots
+    let outdated = list()
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|snapshot| snapshot.automatic)
+        .skip(settings.keep_automatic);
+
+    for snapshot in outdated {
+        if let Err(err) = remove(&snapshot) {
+            tracing::error!("Failed to remove outdated wine prefix snapshot: {err}");
+        }
+    }
+
+    Ok(())
+}
+
+/// Select the components which were selected when the snapshot was made
+fn restore_components(components: &SnapshotComponents) -> anyhow::Result<()> {
+    let mut config = Config::get()?;
+
+    match &components.wine {
+        Some(wine) if !config.game.wine.builds.join(wine).exists() => {
+            tracing::warn!("Wine build {wine} from the snapshot is not downloaded anymore, keeping the selected one");
+        }
+
+        _ => config.game.wine.selected = components.wine.clone()
+    }
+
+    Settings::modify(|settings| {
+        let selections = [
+            (ComponentKind::Vkd3d, settings.directx.vkd3d.clone(), components.directx.vkd3d.clone()),
+            (ComponentKind::Nvapi, settings.directx.nvapi.clone(), components.directx.nvapi.clone())
+        ];
+
+        // Related game options are changed only for the changed components
+        // so the user's own DirectX version choice is kept
+        for (kind, current, restored) in selections {
+            if current != restored {
+                crate::directx::set_selected(kind, restored, &mut config, settings);
+            }
+        }
+    })?;
+
+    Config::update_raw(config)?;
+
+    Ok(())
+}
+
+/// Replace the wine prefix with the snapshot and select the components it was made with
+///
+/// Current prefix is kept until the snapshot is restored, and put back on failure
+pub fn restore(snapshot: &Snapshot) -> anyhow::Result<()> {
+    let config = Config::get()?;
+    let prefix = config.game.wine.prefix;
+
+    tracing::info!("Restoring wine prefix snapshot: {snapshot:?}");
+
+    let backup = prefix.with_file_name(format!(".{}.restoring", prefix.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default()));
+
+    if backup.exists() {
+        std::fs::remove_dir_all(&backup)?;
+    }
+
+    if prefix.exists() {
+        std::fs::rename(&prefix, &backup)?;
+    }
+
+    match unpack(snapshot.format, &snapshot.path(), &prefix) {
+        Ok(_) => {
+            if backup.exists() {
+                std::fs::remove_dir_all(&backup)?;
+            }
+
+            if let Some(components) = &snapshot.components {
+                restore_components(components)?;
+            }
+
+            Ok(())
+        }
+
+        Err(err) => {
+            if prefix.exists() {
+                std::fs::remove_dir_all(&prefix)?;
+            }
+
+            if backup.exists() {
+                std::fs::rename(&backup, &prefix)?;
+            }
+
+            Err(err)
+        }
+    }
+}
+
+/// Remove the snapshot's data and description
+pub fn remove(snapshot: &Snapshot) -> anyhow::Result<()> {
+    tracing::info!("Removing wine prefix snapshot: {snapshot:?}");
+
+    let path = snapshot.path();
+
+    if path.is_dir() {
+        std::fs::remove_dir_all(path)?;
+    }
+
+    else if path.exists() {
+        std::fs::remove_file(path)?;
+    }
+
+    std::fs::remove_file(SNAPSHOTS_FOLDER.join(format!("{}.json", snapshot.id)))?;
+
+    Ok(())
+}
@@ -17,7 +17,12 @@ pub struct ComponentGroup {
 pub enum ComponentGroupMsg {
     ShowRecommendedOnly(bool),
     CallOnDownloaded,
-    CallOnDeleted
+    CallOnDeleted,
+
+    Toast {
+        title: String,
+        description: Option<String>
+    }
 }
 
 #[relm4::component(async, pub)]
@@ -80,6 +85,11 @@ impl SimpleAsyncComponent for ComponentGroup {
             ComponentGroupMsg::CallOnDeleted => {
                 sender.output(ComponentsListMsg::CallOnDeleted);
             }
+
+            #[allow(unused_must_use)]
+            ComponentGroupMsg::Toast { title, description } => {
+                sender.output(ComponentsListMsg::Toast { title, description });
+            }
         }
     }
 }
@@ -7,7 +7,10 @@ use super::ComponentGroupMsg;
 pub struct ComponentsListInit<T> {
     pub pattern: super::ComponentsListPattern,
     pub on_downloaded: Option<T>,
-    pub on_deleted: Option<T>
+    pub on_deleted: Option<T>,
+
+    /// Build message from the toast title and description
+    pub on_toast: Option<fn(String, Option<String>) -> T>
 }
 
 pub struct ComponentsList<T> {
@@ -21,7 +24,12 @@ pub struct ComponentsList<T> {
 pub enum ComponentsListMsg {
     ShowRecommendedOnly(bool),
     CallOnDownloaded,
-    CallOnDeleted
+    CallOnDeleted,
+
+    Toast {
+        title: String,
+        description: Option<String>
+    }
 }
 
 #[relm4::component(async, pub)]
@@ -84,6 +92,11 @@ impl<T: std::fmt::Debug + Clone + 'static> SimpleAsyncComponent for ComponentsLi
             ComponentsListMsg::CallOnDeleted => if let Some(on_deleted) = &self.init.on_deleted {
                 sender.output(on_deleted.to_owned());
             }
+
+            #[allow(unused_must_use)]
+            ComponentsListMsg::Toast { title, description } => if let Some(on_toast) = &self.init.on_toast {
+                sender.output(on_toast(title, description));
+            }
         }
     }
 }
@@ -44,13 +44,26 @@ impl From<dxvk::Group> for ComponentsListGroup {
     }
 }
 
+impl From<crate::directx::Group> for ComponentsListGroup {
+    #[inline]
+    fn from(group: crate::directx::Group) -> Self {
+        Self {
+            title: group.title,
+            versions: group.versions.into_iter().map(|version| version.into()).collect()
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ComponentsListVersion {
     pub name: String,
     pub title: String,
     pub uri: String,
     pub format: Option<String>,
-    pub recommended: bool
+    pub recommended: bool,
+
+    /// SHA-256 checksum of the archive from the components index
+    pub checksum: Option<String>
 }
 
 impl From<wine::Version> for ComponentsListVersion {
@@ -65,7 +78,8 @@ impl From<wine::Version> for ComponentsListVersion {
             name: version.name,
             title: version.title,
             uri: version.uri,
-            format: version.format
+            format: version.format,
+            checksum: None
         }
     }
 }
@@ -82,7 +96,22 @@ impl From<dxvk::Version> for ComponentsListVersion {
             name: version.name,
             title: version.title,
             uri: version.uri,
-            format: version.format
+            format: version.format,
+            checksum: None
+        }
+    }
+}
+
+impl From<crate::directx::Version> for ComponentsListVersion {
+    #[inline]
+    fn from(version: crate::directx::Version) -> Self {
+        Self {
+            recommended: version.recommended,
+            name: version.name,
+            title: version.title,
+            uri: version.uri,
+            format: version.format,
+            checksum: None
         }
     }
 }
@@ -15,6 +15,9 @@ use anime_launcher_sdk::wuwa::config::Config;
 use super::ComponentGroupMsg;
 use super::progress_bar::ProgressBarMsg;
 
+use crate::checksums::{self, VerifyError};
+use crate::*;
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum VersionState {
     Downloaded,
@@ -31,8 +34,12 @@ pub struct ComponentVersion {
     pub download_folder: PathBuf,
     pub download_filename: Option<String>,
 
+    /// SHA-256 checksum of the archive
+    pub checksum: Option<String>,
+
     pub show_recommended_only: bool,
     pub state: VersionState,
+    pub verifying: bool,
 
     pub progress_bar: AsyncController<super::ProgressBar>
 }
@@ -41,7 +48,11 @@ pub struct ComponentVersion {
 pub enum ComponentVersionMsg {
     ShowRecommendedOnly(bool),
     PerformAction,
-    SetState(VersionState)
+    SetState(VersionState),
+
+    /// Check unpacked component's files against its manifest
+    Verify,
+    SetVerifying(bool)
 }
 
 #[relm4::component(async, pub)]
@@ -57,6 +68,26 @@ impl SimpleAsyncComponent for ComponentVersion {
             #[watch]
             set_visible: !model.show_recommended_only || model.recommended || model.state != VersionState::NotDownloaded,
 
+            add_suffix = &gtk::Spinner {
+                set_spinning: true,
+
+                #[watch]
+                set_visible: model.verifying
+            },
+
+            add_suffix = &gtk::Button {
+                set_icon_name: "security-high-symbolic",
+                set_tooltip_text: Some(&tr!("verify-component")),
+
+                add_css_class: "flat",
+                set_valign: gtk::Align::Center,
+
+                #[watch]
+                set_visible: model.state == VersionState::Downloaded && !model.verifying,
+
+                connect_clicked => ComponentVersionMsg::Verify
+            },
+
             add_suffix = &gtk::Button {
                 #[watch]
                 set_icon_name: match model.state {
@@ -91,8 +122,11 @@ impl SimpleAsyncComponent for ComponentVersion {
             download_folder: init.1,
             download_filename: init.0.format.map(|format| format!("{}.{format}", init.0.name)),
 
+            checksum: init.0.checksum,
+
             show_recommended_only: true,
             state: VersionState::NotDownloaded,
+            verifying: false,
 
             progress_bar: super::ProgressBar::builder()
                 .launch(super::ProgressBarInit {
@@ -147,10 +181,12 @@ impl SimpleAsyncComponent for ComponentVersion {
 
                     VersionState::NotDownloaded => {
                         if let Ok(config) = Config::get() {
+                            let temp_folder = config.launcher.temp.unwrap_or_else(std::env::temp_dir);
+
                             // todo
                             let mut installer = Installer::new(&self.download_uri)
                                 .expect("Failed to create installer instance for this version")
-                                .with_temp_folder(config.launcher.temp.unwrap_or_else(std::env::temp_dir));
+                                .with_temp_folder(temp_folder.clone());
 
                             if let Some(filename) = &self.download_filename {
                                 installer = installer.with_filename(filename.to_owned());
@@ -160,11 +196,56 @@ impl SimpleAsyncComponent for ComponentVersion {
 
                             let progress_bar_sender = self.progress_bar.sender().clone();
 
+                            let uri = self.download_uri.clone();
+                            let filename = self.download_filename.clone();
+                            let checksum = self.checksum.clone();
+                            let component_folder = self.download_folder.join(&self.name);
+
                             #[allow(unused_must_use)]
                             std::thread::spawn(clone!(@strong self.download_folder as download_folder => move || {
                                 progress_bar_sender.send(ProgressBarMsg::Reset);
                                 progress_bar_sender.send(ProgressBarMsg::SetVisible(true));
 
+                                // Verify the archive before unpacking it
+                                if let Some(checksum) = &checksum {
+                                    let progress_sender = progress_bar_sender.clone();
+
+                                    let result = checksums::download(&uri, filename, &temp_folder, checksum, move |curr, total| {
+                                        progress_sender.send(ProgressBarMsg::UpdateProgress(curr, total));
+                                    });
+
+                                    match result {
+                                        Ok(filename) => installer = installer.with_filename(filename),
+
+                                        Err(err) => {
+                                            tracing::error!("Failed to verify component archive: {err}");
+
+                                            progress_bar_sender.send(ProgressBarMsg::SetVisible(false));
+
+                                            sender.input(ComponentVersionMsg::SetState(VersionState::NotDownloaded));
+
+                                            sender.output(ComponentGroupMsg::Toast {
+                                                title: match err {
+                                                    VerifyError::Mismatch { .. } => tr!("component-checksum-mismatch"),
+                                                    VerifyError::Download(_) => tr!("downloading-failed")
+                                                },
+                                                description: Some(err.to_string())
+                                            });
+
+                                            return;
+                                        }
+                                    }
+                                }
+
+                                else {
+                                    tracing::warn!("Components index has no checksum for {uri}, archive won't be verified");
+
+                                    sender.output(ComponentGroupMsg::Toast {
+                                        title: tr!("component-checksum-unavailable"),
+                                        description: None
+                                    });
+                                }
+
                                 installer.install(download_folder, move |state| {
                                     match &state {
                                         InstallerUpdate::UnpackingFinished |
@@ -173,6 +254,10 @@ impl SimpleAsyncComponent for ComponentVersion {
                                             progress_bar_sender.send(ProgressBarMsg::SetVisible(false));
 
                                             if let InstallerUpdate::UnpackingFinished = &state {
+                                                if let Err(err) = checksums::write_manifest(&component_folder, checksum.clone()) {
+                                                    tracing::warn!("Failed to write component files manifest: {err}");
+                                                }
+
                                                 sender.input(ComponentVersionMsg::SetState(VersionState::Downloaded));
                                                 sender.output(ComponentGroupMsg::CallOnDownloaded);
                                             }
@@ -195,7 +280,50 @@ impl SimpleAsyncComponent for ComponentVersion {
                 }
             }
 
-            ComponentVersionMsg::SetState(state) => self.state = state
+            ComponentVersionMsg::SetState(state) => self.state = state,
+
+            ComponentVersionMsg::Verify => {
+                self.verifying = true;
+
+                let folder = self.download_folder.join(&self.name);
+
+                #[allow(unused_must_use)]
+                std::thread::spawn(move || {
+                    let toast = match checksums::verify_installed(folder) {
+                        Ok(verification) if verification.broken.is_empty() => ComponentGroupMsg::Toast {
+                            title: if verification.verified {
+                                tr!("component-verified")
+                            } else {
+                                tr!("component-files-unverified")
+                            },
+
+                            description: None
+                        },
+
+                        Ok(verification) => ComponentGroupMsg::Toast {
+                            title: tr!("component-files-broken"),
+                            description: Some(verification.broken.iter()
+                                .map(|path| format!("- {}", path.to_string_lossy()))
+                                .collect::<Vec<_>>()
+                                .join("\n"))
+                        },
+
+                        Err(err) => {
+                            tracing::error!("Failed to verify component: {err}");
+
+                            ComponentGroupMsg::Toast {
+                                title: tr!("component-verification-failed"),
+                                description: Some(err.to_string())
+                            }
+                        }
+                    };
+
+                    sender.output(toast);
+                    sender.input(ComponentVersionMsg::SetVerifying(false));
+                });
+            }
+
+            ComponentVersionMsg::SetVerifying(state) => self.verifying = state
         }
     }
 }
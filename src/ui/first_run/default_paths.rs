@@ -1,47 +1,132 @@
 use std::path::PathBuf;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use relm4::prelude::*;
 use adw::prelude::*;
 
 use crate::*;
+use crate::move_files::Journal;
+use crate::checksums::ComponentKind;
+use crate::settings::prelude::*;
+use crate::filesystem::PathWarning;
 use crate::ui::components::progress_bar::*;
+use crate::ui::import_installation::ImportInstallationApp;
 
 use super::main::*;
 
 pub struct DefaultPathsApp {
     progress_bar: AsyncController<ProgressBar>,
+    import_installation: AsyncController<ImportInstallationApp>,
 
     show_additional: bool,
     migrate_installation: bool,
     show_progress: bool,
 
+    /// Set to cancel running migration
+    cancel: Arc<AtomicBool>,
+
     launcher: PathBuf,
     runners: PathBuf,
     dxvks: PathBuf,
+    vkd3d: PathBuf,
+    nvapi: PathBuf,
     prefix: PathBuf,
     game_global: PathBuf,
     game_china: PathBuf,
     components: PathBuf,
-    temp: PathBuf
+    patch: PathBuf,
+    temp: PathBuf,
+
+    warnings: HashMap<Folders, Vec<PathWarning>>
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Folders {
     Launcher,
     Runners,
     DXVK,
+    Vkd3d,
+    Nvapi,
     Prefix,
     GameGlobal,
     GameChina,
     Components,
+    Patch,
     Temp
 }
 
+impl Folders {
+    #[inline]
+    pub fn list() -> &'static [Self] {
+        &[
+            Self::Launcher,
+            Self::Runners,
+            Self::DXVK,
+            Self::Vkd3d,
+            Self::Nvapi,
+            Self::Prefix,
+            Self::GameGlobal,
+            Self::GameChina,
+            Self::Components,
+            Self::Patch,
+            Self::Temp
+        ]
+    }
+
+    /// Approximate size of the folder content, in bytes
+    pub fn expected_size(&self) -> u64 {
+        const GB: u64 = 1024 * 1024 * 1024;
+
+        match self {
+            Self::Launcher   => 0,
+            Self::Runners    => GB,
+            Self::DXVK       => GB / 4,
+            Self::Vkd3d      => GB / 8,
+            Self::Nvapi      => GB / 64,
+            Self::Prefix     => GB,
+            Self::Components => GB / 64,
+            Self::Patch      => GB / 64,
+            Self::Temp       => 40 * GB,
+
+            // Only the selected game edition will be installed
+            Self::GameGlobal if CONFIG.launcher.edition == GameEdition::Global => 140 * GB,
+            Self::GameChina  if CONFIG.launcher.edition == GameEdition::China  => 140 * GB,
+
+            Self::GameGlobal | Self::GameChina => 0
+        }
+    }
+
+    /// Files from this folder will be executed
+    #[inline]
+    pub fn is_executable(&self) -> bool {
+        matches!(self, Self::Runners | Self::DXVK | Self::Vkd3d | Self::Nvapi | Self::Prefix | Self::GameGlobal | Self::GameChina | Self::Patch)
+    }
+}
+
+/// Result of the installation migration
+#[derive(Debug, Clone)]
+pub enum MigrationStatus {
+    Migrated,
+
+    /// Migration window was closed, or migration was cancelled
+    /// and moved files were returned back
+    Cancelled,
+
+    /// Migration failed with the given reason
+    Failed(String)
+}
+
 #[derive(Debug, Clone)]
 pub enum DefaultPathsAppMsg {
     ToggleShowAdditional,
     ChoosePath(Folders),
     Continue,
+    CancelMigration,
+    MigrationFinished(MigrationStatus),
+    OpenImportInstallation,
+    InstallationImported,
     Exit
 }
 
@@ -78,10 +163,20 @@ impl SimpleAsyncComponent for DefaultPathsApp {
                     set_activatable: true,
 
                     #[watch]
-                    set_subtitle: model.launcher.to_str().unwrap(),
+                    set_subtitle: &model.subtitle(Folders::Launcher),
 
                     connect_activated => DefaultPathsAppMsg::ChoosePath(Folders::Launcher),
 
+                    add_suffix = &gtk::Image {
+                        set_icon_name: Some("dialog-warning-symbolic"),
+
+                        #[watch]
+                        set_visible: !model.warnings(Folders::Launcher).is_empty(),
+
+                        #[watch]
+                        set_css_classes: if model.is_critical(Folders::Launcher) { &["error"] } else { &["warning"] }
+                    },
+
                     add_prefix = &gtk::Image {
                         set_icon_name: Some("folder-symbolic")
                     }
@@ -119,10 +214,20 @@ impl SimpleAsyncComponent for DefaultPathsApp {
                     set_activatable: true,
 
                     #[watch]
-                    set_subtitle: model.runners.to_str().unwrap(),
+                    set_subtitle: &model.subtitle(Folders::Runners),
 
                     connect_activated => DefaultPathsAppMsg::ChoosePath(Folders::Runners),
 
+                    add_suffix = &gtk::Image {
+                        set_icon_name: Some("dialog-warning-symbolic"),
+
+                        #[watch]
+                        set_visible: !model.warnings(Folders::Runners).is_empty(),
+
+                        #[watch]
+                        set_css_classes: if model.is_critical(Folders::Runners) { &["error"] } else { &["warning"] }
+                    },
+
                     add_prefix = &gtk::Image {
                         set_icon_name: Some("folder-symbolic")
                     }
@@ -133,10 +238,68 @@ impl SimpleAsyncComponent for DefaultPathsApp {
                     set_activatable: true,
 
                     #[watch]
-                    set_subtitle: model.dxvks.to_str().unwrap(),
+                    set_subtitle: &model.subtitle(Folders::DXVK),
 
                     connect_activated => DefaultPathsAppMsg::ChoosePath(Folders::DXVK),
 
+                    add_suffix = &gtk::Image {
+                        set_icon_name: Some("dialog-warning-symbolic"),
+
+                        #[watch]
+                        set_visible: !model.warnings(Folders::DXVK).is_empty(),
+
+                        #[watch]
+                        set_css_classes: if model.is_critical(Folders::DXVK) { &["error"] } else { &["warning"] }
+                    },
+
+                    add_prefix = &gtk::Image {
+                        set_icon_name: Some("folder-symbolic")
+                    }
+                },
+
+                adw::ActionRow {
+                    set_title: &tr!("vkd3d-folder"),
+                    set_activatable: true,
+
+                    #[watch]
+                    set_subtitle: &model.subtitle(Folders::Vkd3d),
+
+                    connect_activated => DefaultPathsAppMsg::ChoosePath(Folders::Vkd3d),
+
+                    add_suffix = &gtk::Image {
+                        set_icon_name: Some("dialog-warning-symbolic"),
+
+                        #[watch]
+                        set_visible: !model.warnings(Folders::Vkd3d).is_empty(),
+
+                        #[watch]
+                        set_css_classes: if model.is_critical(Folders::Vkd3d) { &["error"] } else { &["warning"] }
+                    },
+
+                    add_prefix = &gtk::Image {
+                        set_icon_name: Some("folder-symbolic")
+                    }
+                },
+
+                adw::ActionRow {
+                    set_title: &tr!("nvapi-folder"),
+                    set_activatable: true,
+
+                    #[watch]
+                    set_subtitle: &model.subtitle(Folders::Nvapi),
+
+                    connect_activated => DefaultPathsAppMsg::ChoosePath(Folders::Nvapi),
+
+                    add_suffix = &gtk::Image {
+                        set_icon_name: Some("dialog-warning-symbolic"),
+
+                        #[watch]
+                        set_visible: !model.warnings(Folders::Nvapi).is_empty(),
+
+                        #[watch]
+                        set_css_classes: if model.is_critical(Folders::Nvapi) { &["error"] } else { &["warning"] }
+                    },
+
                     add_prefix = &gtk::Image {
                         set_icon_name: Some("folder-symbolic")
                     }
@@ -147,10 +310,20 @@ impl SimpleAsyncComponent for DefaultPathsApp {
                     set_activatable: true,
 
                     #[watch]
-                    set_subtitle: model.prefix.to_str().unwrap(),
+                    set_subtitle: &model.subtitle(Folders::Prefix),
 
                     connect_activated => DefaultPathsAppMsg::ChoosePath(Folders::Prefix),
 
+                    add_suffix = &gtk::Image {
+                        set_icon_name: Some("dialog-warning-symbolic"),
+
+                        #[watch]
+                        set_visible: !model.warnings(Folders::Prefix).is_empty(),
+
+                        #[watch]
+                        set_css_classes: if model.is_critical(Folders::Prefix) { &["error"] } else { &["warning"] }
+                    },
+
                     add_prefix = &gtk::Image {
                         set_icon_name: Some("folder-symbolic")
                     }
@@ -161,10 +334,20 @@ impl SimpleAsyncComponent for DefaultPathsApp {
                     set_activatable: true,
 
                     #[watch]
-                    set_subtitle: model.game_global.to_str().unwrap(),
+                    set_subtitle: &model.subtitle(Folders::GameGlobal),
 
                     connect_activated => DefaultPathsAppMsg::ChoosePath(Folders::GameGlobal),
 
+                    add_suffix = &gtk::Image {
+                        set_icon_name: Some("dialog-warning-symbolic"),
+
+                        #[watch]
+                        set_visible: !model.warnings(Folders::GameGlobal).is_empty(),
+
+                        #[watch]
+                        set_css_classes: if model.is_critical(Folders::GameGlobal) { &["error"] } else { &["warning"] }
+                    },
+
                     add_prefix = &gtk::Image {
                         set_icon_name: Some("folder-symbolic")
                     }
@@ -175,10 +358,20 @@ impl SimpleAsyncComponent for DefaultPathsApp {
                     set_activatable: true,
 
                     #[watch]
-                    set_subtitle: model.game_china.to_str().unwrap(),
+                    set_subtitle: &model.subtitle(Folders::GameChina),
 
                     connect_activated => DefaultPathsAppMsg::ChoosePath(Folders::GameChina),
 
+                    add_suffix = &gtk::Image {
+                        set_icon_name: Some("dialog-warning-symbolic"),
+
+                        #[watch]
+                        set_visible: !model.warnings(Folders::GameChina).is_empty(),
+
+                        #[watch]
+                        set_css_classes: if model.is_critical(Folders::GameChina) { &["error"] } else { &["warning"] }
+                    },
+
                     add_prefix = &gtk::Image {
                         set_icon_name: Some("folder-symbolic")
                     }
@@ -189,10 +382,44 @@ impl SimpleAsyncComponent for DefaultPathsApp {
                     set_activatable: true,
 
                     #[watch]
-                    set_subtitle: model.components.to_str().unwrap(),
+                    set_subtitle: &model.subtitle(Folders::Components),
 
                     connect_activated => DefaultPathsAppMsg::ChoosePath(Folders::Components),
 
+                    add_suffix = &gtk::Image {
+                        set_icon_name: Some("dialog-warning-symbolic"),
+
+                        #[watch]
+                        set_visible: !model.warnings(Folders::Components).is_empty(),
+
+                        #[watch]
+                        set_css_classes: if model.is_critical(Folders::Components) { &["error"] } else { &["warning"] }
+                    },
+
+                    add_prefix = &gtk::Image {
+                        set_icon_name: Some("folder-symbolic")
+                    }
+                },
+
+                adw::ActionRow {
+                    set_title: &tr!("patch-folder"),
+                    set_activatable: true,
+
+                    #[watch]
+                    set_subtitle: &model.subtitle(Folders::Patch),
+
+                    connect_activated => DefaultPathsAppMsg::ChoosePath(Folders::Patch),
+
+                    add_suffix = &gtk::Image {
+                        set_icon_name: Some("dialog-warning-symbolic"),
+
+                        #[watch]
+                        set_visible: !model.warnings(Folders::Patch).is_empty(),
+
+                        #[watch]
+                        set_css_classes: if model.is_critical(Folders::Patch) { &["error"] } else { &["warning"] }
+                    },
+
                     add_prefix = &gtk::Image {
                         set_icon_name: Some("folder-symbolic")
                     }
@@ -203,10 +430,20 @@ impl SimpleAsyncComponent for DefaultPathsApp {
                     set_activatable: true,
 
                     #[watch]
-                    set_subtitle: model.temp.to_str().unwrap(),
+                    set_subtitle: &model.subtitle(Folders::Temp),
 
                     connect_activated => DefaultPathsAppMsg::ChoosePath(Folders::Temp),
 
+                    add_suffix = &gtk::Image {
+                        set_icon_name: Some("dialog-warning-symbolic"),
+
+                        #[watch]
+                        set_visible: !model.warnings(Folders::Temp).is_empty(),
+
+                        #[watch]
+                        set_css_classes: if model.is_critical(Folders::Temp) { &["error"] } else { &["warning"] }
+                    },
+
                     add_prefix = &gtk::Image {
                         set_icon_name: Some("folder-symbolic")
                     }
@@ -234,9 +471,22 @@ impl SimpleAsyncComponent for DefaultPathsApp {
 
                         set_css_classes: &["suggested-action", "pill"],
 
+                        #[watch]
+                        set_sensitive: !model.has_critical_warnings(),
+
                         connect_clicked => DefaultPathsAppMsg::Continue
                     },
 
+                    gtk::Button {
+                        set_label: &tr!("import-installation"),
+                        set_tooltip_text: Some(&tr!("import-installation-description")),
+                        add_css_class: "pill",
+
+                        set_visible: !model.migrate_installation,
+
+                        connect_clicked => DefaultPathsAppMsg::OpenImportInstallation
+                    },
+
                     gtk::Button {
                         set_label: &if model.migrate_installation {
                             tr!("close", { "form" = "noun" })
@@ -246,9 +496,6 @@ impl SimpleAsyncComponent for DefaultPathsApp {
 
                         add_css_class: "pill",
 
-                        #[watch]
-                        set_visible: !model.migrate_installation,
-
                         connect_clicked => DefaultPathsAppMsg::Exit
                     }
                 }
@@ -262,45 +509,74 @@ impl SimpleAsyncComponent for DefaultPathsApp {
                 set_visible: model.show_progress,
 
                 gtk::Box {
-                    set_orientation: gtk::Orientation::Horizontal,
+                    set_orientation: gtk::Orientation::Vertical,
                     set_halign: gtk::Align::Center,
+                    set_spacing: 16,
 
                     append = model.progress_bar.widget(),
+
+                    gtk::Button {
+                        set_label: &tr!("cancel"),
+                        set_halign: gtk::Align::Center,
+                        add_css_class: "pill",
+
+                        #[watch]
+                        set_visible: model.migrate_installation,
+
+                        connect_clicked => DefaultPathsAppMsg::CancelMigration
+                    }
                 }
             }
         }
     }
 
-    async fn init(init: Self::Init, root: Self::Root, _sender: AsyncComponentSender<Self>) -> AsyncComponentParts<Self> {
-        let model = Self {
+    async fn init(init: Self::Init, root: Self::Root, sender: AsyncComponentSender<Self>) -> AsyncComponentParts<Self> {
+        let directx_builds = Settings::get()
+            .map(|settings| settings.directx.builds)
+            .unwrap_or_default();
+
+        let mut model = Self {
             progress_bar: ProgressBar::builder()
                 .launch(ProgressBarInit {
                     caption: None,
                     display_progress: true,
-                    display_fraction: false,
+                    display_fraction: true,
                     visible: false
                 })
                 .detach(),
 
+            import_installation: ImportInstallationApp::builder()
+                .launch(())
+                .forward(sender.input_sender(), |_| DefaultPathsAppMsg::InstallationImported),
+
             show_additional: false,
             migrate_installation: init,
             show_progress: false,
 
+            cancel: Arc::new(AtomicBool::new(false)),
+
             launcher: LAUNCHER_FOLDER.to_path_buf(),
             runners: CONFIG.game.wine.builds.clone(),
             dxvks: CONFIG.game.dxvk.builds.clone(),
+            vkd3d: directx_builds.vkd3d,
+            nvapi: directx_builds.nvapi,
             prefix: CONFIG.game.wine.prefix.clone(),
             game_global: CONFIG.game.path.global.clone(),
             game_china: CONFIG.game.path.china.clone(),
             components: CONFIG.components.path.clone(),
+            patch: CONFIG.patch.path.clone(),
 
             #[allow(clippy::or_fun_call)]
-            temp: CONFIG.launcher.temp.clone().unwrap_or(std::env::temp_dir())
+            temp: CONFIG.launcher.temp.clone().unwrap_or(std::env::temp_dir()),
+
+            warnings: HashMap::new()
         };
 
         // Set progress bar width
         model.progress_bar.widget().set_width_request(400);
 
+        model.check_folders();
+
         let widgets = view_output!();
 
         AsyncComponentParts { model, widgets }
@@ -322,10 +598,13 @@ impl SimpleAsyncComponent for DefaultPathsApp {
                         Folders::Launcher => {
                             self.runners     = result.join("runners");
                             self.dxvks       = result.join("dxvks");
+                            self.vkd3d       = result.join(ComponentKind::Vkd3d.folder());
+                            self.nvapi       = result.join(ComponentKind::Nvapi.folder());
                             self.prefix      = result.join("prefix");
                             self.game_global = result.join("Wuthering Waves");
                             self.game_china  = result.join("Wuthering Waves China");
                             self.components  = result.join("components");
+                            self.patch       = result.join("patch");
 
                             self.temp.clone_from(&result);
 
@@ -334,74 +613,104 @@ impl SimpleAsyncComponent for DefaultPathsApp {
 
                         Folders::Runners    => self.runners     = result,
                         Folders::DXVK       => self.dxvks       = result,
+                        Folders::Vkd3d      => self.vkd3d       = result,
+                        Folders::Nvapi      => self.nvapi       = result,
                         Folders::Prefix     => self.prefix      = result,
                         Folders::GameGlobal => self.game_global = result,
                         Folders::GameChina  => self.game_china  = result,
                         Folders::Components => self.components  = result,
+                        Folders::Patch      => self.patch       = result,
                         Folders::Temp       => self.temp        = result
                     }
+
+                    self.check_folders();
                 }
             }
 
             #[allow(unused_must_use)]
             DefaultPathsAppMsg::Continue => {
-                let old_config = Config::get().unwrap_or_else(|_| CONFIG.clone());
-
-                match self.update_config() {
-                    Ok(_) => {
-                        if self.migrate_installation {
-                            self.progress_bar.sender().send(ProgressBarMsg::SetVisible(true));
-
-                            self.show_progress = true;
-
-                            let folders = [
-                                (old_config.game.wine.builds, &self.runners),
-                                (old_config.game.dxvk.builds, &self.dxvks),
-                                (old_config.game.wine.prefix, &self.prefix),
-                                (old_config.game.path.global, &self.game_global),
-                                (old_config.game.path.china,  &self.game_china),
-                                (old_config.components.path,  &self.components),
-                                // (old_config.patch.path,       &self.patch)
-                            ];
+                // Folders could've been changed since they were chosen
+                self.check_folders();
 
-                            #[allow(clippy::expect_fun_call)]
-                            for (i, (from, to)) in folders.iter().enumerate() {
-                                self.progress_bar.sender().send(ProgressBarMsg::UpdateCaption(Some(
-                                    from.to_str().map(|str| str.to_string()).unwrap_or_else(|| format!("{:?}", from))
-                                )));
+                if self.has_critical_warnings() {
+                    sender.output(Self::Output::Toast {
+                        title: tr!("path-check-failed"),
+                        description: None
+                    });
 
-                                if &from != to && from.exists() {
-                                    move_files::move_files(from, to).expect(&format!("Failed to move folder: {:?} -> {:?}", from, to));
-                                }
-
-                                self.progress_bar.sender().send(ProgressBarMsg::UpdateProgress(i as u64 + 1, folders.len() as u64));
-                            }
+                    return;
+                }
 
-                            // Restart the app
+                if self.migrate_installation {
+                    match self.prepare_migration() {
+                        Ok(journal) => self.migrate(journal, sender),
 
-                            std::process::Command::new(std::env::current_exe().unwrap()).spawn().unwrap();
+                        Err(err) => {
+                            tracing::error!("Failed to prepare installation migration: {err}");
 
-                            relm4::main_application().quit();
+                            sender.output(Self::Output::Toast {
+                                title: tr!("migration-failed"),
+                                description: Some(err.to_string())
+                            });
                         }
+                    }
+                }
 
-                        else {
+                else {
+                    match self.update_config() {
+                        Ok(_) => {
                             sender.output(Self::Output::ScrollToDownloadComponents);
                         }
-                    }
 
-                    Err(err) => {
-                        sender.output(Self::Output::Toast {
-                            title: tr!("config-update-error"),
-                            description: Some(err.to_string())
-                        });
+                        Err(err) => {
+                            sender.output(Self::Output::Toast {
+                                title: tr!("config-update-error"),
+                                description: Some(err.to_string())
+                            });
+                        }
                     }
                 }
             }
 
+            #[allow(unused_must_use)]
+            DefaultPathsAppMsg::CancelMigration => {
+                self.cancel.store(true, Ordering::Relaxed);
+
+                self.progress_bar.sender().send(ProgressBarMsg::UpdateCaption(Some(tr!("cancelling"))));
+            }
+
+            #[allow(unused_must_use)]
+            DefaultPathsAppMsg::MigrationFinished(status) => {
+                self.show_progress = false;
+
+                self.progress_bar.sender().send(ProgressBarMsg::SetVisible(false));
+
+                sender.output(Self::Output::MigrationFinished(status));
+            }
+
+            DefaultPathsAppMsg::OpenImportInstallation => unsafe {
+                if let Some(window) = MAIN_WINDOW.as_ref() {
+                    self.import_installation.widget().set_transient_for(Some(window));
+                }
+
+                self.import_installation.widget().present();
+            }
+
+            // Imported game and prefix folders are written to the config
+            DefaultPathsAppMsg::InstallationImported => {
+                if let Ok(config) = Config::get() {
+                    self.game_global.clone_from(&config.game.path.global);
+                    self.game_china.clone_from(&config.game.path.china);
+                    self.prefix.clone_from(&config.game.wine.prefix);
+                }
+
+                self.check_folders();
+            }
+
+            #[allow(unused_must_use)]
             DefaultPathsAppMsg::Exit => {
                 if self.migrate_installation {
-                    // TODO: this shit should return message to general preferences component somehow to close MigrateInstallation window
-                    todo!();
+                    sender.output(Self::Output::MigrationFinished(MigrationStatus::Cancelled));
                 }
 
                 else {
@@ -413,6 +722,151 @@ impl SimpleAsyncComponent for DefaultPathsApp {
 }
 
 impl DefaultPathsApp {
+    pub fn path(&self, folder: Folders) -> &PathBuf {
+        match folder {
+            Folders::Launcher   => &self.launcher,
+            Folders::Runners    => &self.runners,
+            Folders::DXVK       => &self.dxvks,
+            Folders::Vkd3d      => &self.vkd3d,
+            Folders::Nvapi      => &self.nvapi,
+            Folders::Prefix     => &self.prefix,
+            Folders::GameGlobal => &self.game_global,
+            Folders::GameChina  => &self.game_china,
+            Folders::Components => &self.components,
+            Folders::Patch      => &self.patch,
+            Folders::Temp       => &self.temp
+        }
+    }
+
+    /// Check chosen folders for filesystem problems
+    pub fn check_folders(&mut self) {
+        self.warnings = Folders::list().iter()
+            .map(|folder| {
+                // Free space for migration is checked by the migration journal
+                let required = if self.migrate_installation {
+                    0
+                } else {
+                    folder.expected_size()
+                };
+
+                let warnings = crate::filesystem::check_path(self.path(*folder), required, folder.is_executable());
+
+                (*folder, warnings)
+            })
+            .collect();
+    }
+
+    #[inline]
+    pub fn warnings(&self, folder: Folders) -> &[PathWarning] {
+        self.warnings.get(&folder).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    #[inline]
+    pub fn is_critical(&self, folder: Folders) -> bool {
+        self.warnings(folder).iter().any(PathWarning::is_critical)
+    }
+
+    pub fn has_critical_warnings(&self) -> bool {
+        Folders::list().iter().any(|folder| self.is_critical(*folder))
+    }
+
+    pub fn describe_warnings(&self, folder: Folders) -> String {
+        self.warnings(folder).iter()
+            .map(PathWarning::describe)
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Folder's path with its warnings listed below it, as the row's subtitle markup
+    pub fn subtitle(&self, folder: Folders) -> String {
+        let path = gtk::glib::markup_escape_text(&self.path(folder).to_string_lossy());
+
+        if self.warnings(folder).is_empty() {
+            return path.to_string();
+        }
+
+        format!("{path}\n{}", gtk::glib::markup_escape_text(&self.describe_warnings(folder)))
+    }
+
+    /// Get journal of the failed migration to resume it,
+    /// or prepare new migration and update config with new paths
+    fn prepare_migration(&self) -> anyhow::Result<Journal> {
+        if let Some(journal) = Journal::get()? {
+            journal.check_free_space()?;
+
+            return Ok(journal);
+        }
+
+        let old_config = Config::get()?;
+        let old_builds = Settings::get()?.directx.builds;
+
+        let folders = [
+            (old_config.game.wine.builds.clone(), self.runners.clone()),
+            (old_config.game.dxvk.builds.clone(), self.dxvks.clone()),
+            (old_builds.vkd3d.clone(),            self.vkd3d.clone()),
+            (old_builds.nvapi.clone(),            self.nvapi.clone()),
+            (old_config.game.wine.prefix.clone(), self.prefix.clone()),
+            (old_config.game.path.global.clone(), self.game_global.clone()),
+            (old_config.game.path.china.clone(),  self.game_china.clone()),
+            (old_config.components.path.clone(),  self.components.clone()),
+            (old_config.patch.path.clone(),       self.patch.clone())
+        ];
+
+        let journal = Journal::new(folders, serde_json::to_value(&old_config)?)
+            .with_directx_builds(old_builds);
+
+        journal.check_free_space()?;
+
+        self.update_config()?;
+
+        Ok(journal)
+    }
+
+    /// Run migration in background and send `MigrationFinished` when it's done
+    #[allow(unused_must_use)]
+    fn migrate(&mut self, journal: Journal, sender: AsyncComponentSender<Self>) {
+        self.show_progress = true;
+
+        self.cancel.store(false, Ordering::Relaxed);
+
+        let progress_bar = self.progress_bar.sender().to_owned();
+
+        progress_bar.send(ProgressBarMsg::Reset);
+        progress_bar.send(ProgressBarMsg::UpdateCaption(Some(tr!("migrating-folders"))));
+        progress_bar.send(ProgressBarMsg::SetVisible(true));
+
+        let cancel = self.cancel.clone();
+
+        std::thread::spawn(move || {
+            let result = journal.migrate(&cancel, move |moved, total| {
+                progress_bar.send(ProgressBarMsg::UpdateProgress(moved, total));
+            });
+
+            let status = match result {
+                Ok(_) => MigrationStatus::Migrated,
+
+                // Return already moved files back
+                Err(_) if cancel.load(Ordering::Relaxed) => match journal.revert(|_, _| {}) {
+                    Ok(_) => MigrationStatus::Cancelled,
+
+                    Err(err) => {
+                        tracing::error!("Failed to revert cancelled installation migration: {err}");
+
+                        MigrationStatus::Failed(err.to_string())
+                    }
+                }
+
+                Err(err) => {
+                    tracing::error!("Failed to migrate installation: {err}");
+
+                    MigrationStatus::Failed(err.to_string())
+                }
+            };
+
+            sender.input(DefaultPathsAppMsg::MigrationFinished(status));
+        });
+    }
+
     pub fn update_config(&self) -> anyhow::Result<()> {
         let mut config = Config::get()?;
 
@@ -422,9 +876,17 @@ impl DefaultPathsApp {
         config.game.path.global.clone_from(&self.game_global);
         config.game.path.china.clone_from(&self.game_china);
         config.components.path.clone_from(&self.components);
+        config.patch.path.clone_from(&self.patch);
 
         config.launcher.temp = Some(self.temp.clone());
 
+        Settings::modify(|settings| {
+            settings.directx.builds = DirectXBuilds {
+                vkd3d: self.vkd3d.clone(),
+                nvapi: self.nvapi.clone()
+            };
+        })?;
+
         Config::update_raw(config)
     }
 }
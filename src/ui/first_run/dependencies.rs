@@ -1,25 +1,82 @@
 use relm4::prelude::*;
 use adw::prelude::*;
 
-use anime_launcher_sdk::is_available;
-
 use crate::*;
+use crate::dependencies::*;
 
 use super::main::FirstRunAppMsg;
 
 pub struct DependenciesApp {
-    show_arch: bool,
-    show_debian: bool,
-    show_fedora: bool,
-    show_list: bool
+    distro: Distro,
+    missing: Vec<&'static Dependency>,
+
+    missing_group: adw::PreferencesGroup,
+    missing_rows: Vec<adw::ActionRow>
 }
 
 #[derive(Debug, Clone)]
 pub enum DependenciesAppMsg {
+    SetDistro(usize),
+    CopyCommand { required: bool },
     Continue,
     Exit
 }
 
+impl DependenciesApp {
+    fn missing_required(&self) -> Vec<&'static Dependency> {
+        self.missing.iter()
+            .copied()
+            .filter(|dependency| dependency.required)
+            .collect()
+    }
+
+    fn missing_optional(&self) -> Vec<&'static Dependency> {
+        self.missing.iter()
+            .copied()
+            .filter(|dependency| !dependency.required)
+            .collect()
+    }
+
+    fn command(&self, required: bool) -> String {
+        let dependencies = if required {
+            self.missing_required()
+        } else {
+            self.missing_optional()
+        };
+
+        install_command(self.distro, &dependencies)
+    }
+
+    /// Check dependencies again and rebuild the list of missing ones
+    fn update_missing(&mut self) {
+        self.missing = missing();
+
+        for row in self.missing_rows.drain(..) {
+            self.missing_group.remove(&row);
+        }
+
+        for dependency in &self.missing {
+            let row = adw::ActionRow::new();
+
+            row.set_title(dependency.name());
+            row.set_subtitle(&tr!(dependency.feature));
+
+            let label = gtk::Label::new(Some(&if dependency.required {
+                tr!("dependency-required")
+            } else {
+                tr!("dependency-optional")
+            }));
+
+            label.add_css_class(if dependency.required { "error" } else { "dim-label" });
+
+            row.add_suffix(&label);
+
+            self.missing_group.add(&row);
+            self.missing_rows.push(row);
+        }
+    }
+}
+
 #[relm4::component(async, pub)]
 impl SimpleAsyncComponent for DependenciesApp {
     type Input = DependenciesAppMsg;
@@ -41,84 +98,70 @@ impl SimpleAsyncComponent for DependenciesApp {
 
                 gtk::Label {
                     set_label: &tr!("missing-dependencies-message"),
-    
+
                     set_justify: gtk::Justification::Center,
                     set_wrap: true,
                     set_margin_top: 32
                 }
             },
 
-            add = &adw::PreferencesGroup {
-                set_valign: gtk::Align::Center,
-                set_vexpand: true,
-
-                gtk::Box {
-                    set_orientation: gtk::Orientation::Vertical,
-                    set_spacing: 16,
-
-                    gtk::Box {
-                        set_orientation: gtk::Orientation::Vertical,
-                        set_spacing: 16,
+            #[local_ref]
+            add = missing_group -> adw::PreferencesGroup {
+                set_title: &tr!("missing-dependencies")
+            },
 
-                        #[watch]
-                        set_visible: model.show_arch,
+            add = &adw::PreferencesGroup {
+                adw::ComboRow {
+                    set_title: &tr!("distribution"),
 
-                        gtk::Label {
-                            set_label: "Arch (pacman)"
-                        },
+                    set_model: Some(&gtk::StringList::new(&Distro::list().iter()
+                        .map(|distro| distro.title())
+                        .collect::<Vec<_>>())),
 
-                        gtk::Entry {
-                            set_text: "sudo pacman -Syu git",
-                            set_editable: false
-                        }
-                    },
+                    set_selected: Distro::list().iter()
+                        .position(|distro| distro == &model.distro)
+                        .unwrap_or(0) as u32,
 
-                    gtk::Box {
-                        set_orientation: gtk::Orientation::Vertical,
-                        set_spacing: 16,
+                    connect_selected_notify[sender] => move |row| {
+                        sender.input(DependenciesAppMsg::SetDistro(row.selected() as usize));
+                    }
+                },
 
-                        #[watch]
-                        set_visible: model.show_debian,
+                adw::EntryRow {
+                    set_title: &tr!("install-required-dependencies"),
+                    set_editable: false,
 
-                        gtk::Label {
-                            set_label: "Debian / Ubuntu (apt)"
-                        },
+                    #[watch]
+                    set_visible: !model.missing_required().is_empty(),
 
-                        gtk::Entry {
-                            set_text: "sudo apt install git",
-                            set_editable: false
-                        }
-                    },
+                    #[watch]
+                    set_text: &model.command(true),
 
-                    gtk::Box {
-                        set_orientation: gtk::Orientation::Vertical,
-                        set_spacing: 16,
+                    add_suffix = &gtk::Button {
+                        set_icon_name: "edit-copy-symbolic",
+                        add_css_class: "flat",
+                        set_valign: gtk::Align::Center,
 
-                        #[watch]
-                        set_visible: model.show_fedora,
+                        connect_clicked => DependenciesAppMsg::CopyCommand { required: true }
+                    }
+                },
 
-                        gtk::Label {
-                            set_label: "Fedora (dnf)"
-                        },
+                adw::EntryRow {
+                    set_title: &tr!("install-optional-dependencies"),
+                    set_editable: false,
 
-                        gtk::Entry {
-                            set_text: "sudo dnf install git",
-                            set_editable: false
-                        }
-                    },
+                    #[watch]
+                    set_visible: !model.missing_optional().is_empty(),
 
-                    gtk::Box {
-                        set_orientation: gtk::Orientation::Vertical,
-                        set_spacing: 16,
+                    #[watch]
+                    set_text: &model.command(false),
 
-                        #[watch]
-                        set_visible: model.show_list,
+                    add_suffix = &gtk::Button {
+                        set_icon_name: "edit-copy-symbolic",
+                        add_css_class: "flat",
+                        set_valign: gtk::Align::Center,
 
-                        adw::PreferencesGroup {
-                            adw::ActionRow {
-                                set_title: "git"
-                            }
-                        }
+                        connect_clicked => DependenciesAppMsg::CopyCommand { required: false }
                     }
                 }
             },
@@ -126,14 +169,20 @@ impl SimpleAsyncComponent for DependenciesApp {
             add = &adw::PreferencesGroup {
                 set_valign: gtk::Align::Center,
                 set_vexpand: true,
-    
+
                 gtk::Box {
                     set_orientation: gtk::Orientation::Horizontal,
                     set_halign: gtk::Align::Center,
                     set_spacing: 8,
 
                     gtk::Button {
-                        set_label: &tr!("check"),
+                        #[watch]
+                        set_label: &if model.missing_required().is_empty() {
+                            tr!("continue")
+                        } else {
+                            tr!("check")
+                        },
+
                         set_css_classes: &["suggested-action", "pill"],
 
                         connect_clicked => DependenciesAppMsg::Continue
@@ -150,29 +199,18 @@ impl SimpleAsyncComponent for DependenciesApp {
         }
     }
 
-    async fn init(_init: Self::Init, root: Self::Root, _sender: AsyncComponentSender<Self>) -> AsyncComponentParts<Self> {
-        let distro = whatadistro::identify();
-
+    async fn init(_init: Self::Init, root: Self::Root, sender: AsyncComponentSender<Self>) -> AsyncComponentParts<Self> {
         let mut model = Self {
-            show_arch: match &distro {
-                Some(distro) => distro.is_similar("arch"),
-                None => false
-            },
-
-            show_debian: match &distro {
-                Some(distro) => distro.is_similar("debian"),
-                None => false
-            },
-
-            show_fedora: match &distro {
-                Some(distro) => distro.is_similar("fedora"),
-                None => false
-            },
+            distro: Distro::detect().unwrap_or(Distro::Arch),
+            missing: Vec::new(),
 
-            show_list: false
+            missing_group: adw::PreferencesGroup::new(),
+            missing_rows: Vec::new()
         };
 
-        model.show_list = !model.show_arch && !model.show_debian && !model.show_fedora;
+        model.update_missing();
+
+        let missing_group = &model.missing_group;
 
         let widgets = view_output!();
 
@@ -181,12 +219,32 @@ impl SimpleAsyncComponent for DependenciesApp {
 
     async fn update(&mut self, msg: Self::Input, sender: AsyncComponentSender<Self>) {
         match msg {
+            DependenciesAppMsg::SetDistro(index) => {
+                if let Some(distro) = Distro::list().get(index) {
+                    self.distro = *distro;
+                }
+            }
+
+            DependenciesAppMsg::CopyCommand { required } => {
+                match gtk::gdk::Display::default() {
+                    Some(display) => display.clipboard().set_text(&self.command(required)),
+                    None => tracing::error!("Failed to get default display to copy install command")
+                }
+            }
+
             #[allow(unused_must_use)]
             DependenciesAppMsg::Continue => {
-                if !is_available("git") {
+                self.update_missing();
+
+                let required = self.missing_required();
+
+                if !required.is_empty() {
                     sender.output(Self::Output::Toast {
                         title: tr!("package-not-available", {
-                            "package" = "git"
+                            "package" = required.iter()
+                                .map(|dependency| dependency.name())
+                                .collect::<Vec<_>>()
+                                .join(", ")
                         }),
                         description: None
                     });
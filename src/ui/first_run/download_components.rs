@@ -13,14 +13,62 @@ use anime_launcher_sdk::config::ConfigExt;
 use anime_launcher_sdk::wuwa::config::Config;
 
 use super::main::FirstRunAppMsg;
+use super::progress::FirstRunProgress;
 
 use crate::ui::components::*;
+use crate::checksums::{self, ComponentKind, VerifyError};
 use crate::*;
 
 fn get_installer(uri: &str, temp: Option<PathBuf>) -> anyhow::Result<Installer> {
     Ok(Installer::new(uri)?.with_temp_folder(temp.unwrap_or_else(std::env::temp_dir)))
 }
 
+/// Verify the component's archive before unpacking it, showing a toast if it's corrupted
+/// or the components index has no checksum to verify it
+#[allow(unused_must_use)]
+fn verify_archive(
+    installer: Installer,
+    kind: ComponentKind,
+    name: &str,
+    uri: &str,
+    progress_bar_input: relm4::Sender<ProgressBarMsg>,
+    sender: &AsyncComponentSender<DownloadComponentsApp>
+) -> Option<(Installer, Option<String>)> {
+    let config = Config::get().unwrap_or_else(|_| CONFIG.clone());
+    let temp = config.launcher.temp.unwrap_or_else(std::env::temp_dir);
+
+    let result = checksums::verify_archive(installer, &config.components.path, kind, name, uri, &temp, move |curr, total| {
+        progress_bar_input.send(ProgressBarMsg::UpdateProgress(curr, total));
+    });
+
+    match result {
+        Ok(result) => {
+            if result.1.is_none() {
+                sender.output(FirstRunAppMsg::Toast {
+                    title: tr!("component-checksum-unavailable"),
+                    description: None
+                });
+            }
+
+            Some(result)
+        }
+
+        Err(err) => {
+            tracing::error!("Failed to verify {name} archive: {err}");
+
+            sender.output(FirstRunAppMsg::Toast {
+                title: match err {
+                    VerifyError::Mismatch { .. } => tr!("component-checksum-mismatch"),
+                    VerifyError::Download(_) => tr!("downloading-failed")
+                },
+                description: Some(err.to_string())
+            });
+
+            None
+        }
+    }
+}
+
 pub struct DownloadComponentsApp {
     progress_bar: AsyncController<ProgressBar>,
 
@@ -33,6 +81,11 @@ pub struct DownloadComponentsApp {
     selected_wine: Option<wine::Version>,
     selected_dxvk: Option<dxvk::Version>,
 
+    /// Index of the version selected by default,
+    /// e.g. the one downloaded before the first run was interrupted
+    default_wine: u32,
+    default_dxvk: u32,
+
     /// `None` - default,
     /// `Some(false)` - processing,
     /// `Some(true)` - done
@@ -62,6 +115,7 @@ pub struct DownloadComponentsApp {
 #[derive(Debug, Clone)]
 pub enum DownloadComponentsAppMsg {
     UpdateVersionsLists,
+    ImportBundle,
     DownloadWine,
     CreatePrefix,
     DownloadDXVK,
@@ -105,7 +159,10 @@ impl SimpleAsyncComponent for DownloadComponentsApp {
                     set_model: Some(&gtk::StringList::new(model.wine_versions.iter()
                         .map(|version| version.title.as_ref())
                         .collect::<Vec<&str>>()
-                        .as_slice()))
+                        .as_slice())),
+
+                    #[watch]
+                    set_selected: model.default_wine
                 },
 
                 #[local_ref]
@@ -116,7 +173,10 @@ impl SimpleAsyncComponent for DownloadComponentsApp {
                     set_model: Some(&gtk::StringList::new(model.dxvk_versions.iter()
                         .map(|version| version.name.as_ref())
                         .collect::<Vec<&str>>()
-                        .as_slice()))
+                        .as_slice())),
+
+                    #[watch]
+                    set_selected: model.default_dxvk
                 }
             },
 
@@ -136,9 +196,20 @@ impl SimpleAsyncComponent for DownloadComponentsApp {
                         set_label: &tr!("download"),
                         set_css_classes: &["suggested-action", "pill"],
 
+                        #[watch]
+                        set_sensitive: !model.wine_versions.is_empty() && !model.dxvk_versions.is_empty(),
+
                         connect_clicked => DownloadComponentsAppMsg::DownloadWine
                     },
 
+                    gtk::Button {
+                        set_label: &tr!("import-components-bundle"),
+                        set_tooltip_text: Some(&tr!("import-components-bundle-description")),
+                        add_css_class: "pill",
+
+                        connect_clicked => DownloadComponentsAppMsg::ImportBundle
+                    },
+
                     gtk::Button {
                         set_label: &tr!("exit"),
                         add_css_class: "pill",
@@ -284,6 +355,9 @@ impl SimpleAsyncComponent for DownloadComponentsApp {
             selected_wine: None,
             selected_dxvk: None,
 
+            default_wine: 0,
+            default_dxvk: 0,
+
             downloading_wine: None,
             downloading_wine_version: String::new(),
 
@@ -313,19 +387,92 @@ impl SimpleAsyncComponent for DownloadComponentsApp {
             DownloadComponentsAppMsg::UpdateVersionsLists => {
                 let config = Config::get().unwrap_or_else(|_| CONFIG.clone());
 
+                // Components index can be missing on computers without internet connection
+                let wine_groups = wine::get_groups(&config.components.path).unwrap_or_default();
+                let dxvk_groups = dxvk::get_groups(&config.components.path).unwrap_or_default();
+
                 // 4 latest versions of 4 first available wine group
-                self.wine_versions = wine::get_groups(&config.components.path).unwrap()
-                    .into_iter()
+                self.wine_versions = wine_groups.iter()
                     .take(4)
-                    .flat_map(|group| group.versions.into_iter().take(4))
+                    .flat_map(|group| group.versions.iter().take(4).cloned())
                     .collect();
 
                 // 4 latest versions of 4 first available dxvk group
-                self.dxvk_versions = dxvk::get_groups(&config.components.path).unwrap()
-                    .into_iter()
+                self.dxvk_versions = dxvk_groups.iter()
                     .take(4)
-                    .flat_map(|group| group.versions.into_iter().take(4))
+                    .flat_map(|group| group.versions.iter().take(4).cloned())
                     .collect();
+
+                // Select versions downloaded before the first run was interrupted
+                let progress = FirstRunProgress::get();
+
+                if let Some(name) = &progress.wine {
+                    if !self.wine_versions.iter().any(|version| &version.name == name) {
+                        let version = wine_groups.into_iter()
+                            .flat_map(|group| group.versions)
+                            .find(|version| &version.name == name);
+
+                        if let Some(version) = version {
+                            self.wine_versions.push(version);
+                        }
+                    }
+
+                    self.default_wine = self.wine_versions.iter()
+                        .position(|version| &version.name == name)
+                        .unwrap_or(0) as u32;
+                }
+
+                if let Some(name) = &progress.dxvk {
+                    if !self.dxvk_versions.iter().any(|version| &version.name == name) {
+                        let version = dxvk_groups.into_iter()
+                            .flat_map(|group| group.versions)
+                            .find(|version| &version.name == name);
+
+                        if let Some(version) = version {
+                            self.dxvk_versions.push(version);
+                        }
+                    }
+
+                    self.default_dxvk = self.dxvk_versions.iter()
+                        .position(|version| &version.name == name)
+                        .unwrap_or(0) as u32;
+                }
+            }
+
+            #[allow(unused_must_use)]
+            DownloadComponentsAppMsg::ImportBundle => {
+                let Some(file) = rfd::AsyncFileDialog::new().add_filter("tar.gz", &["gz", "tgz"]).pick_file().await else {
+                    return;
+                };
+
+                let path = file.path().to_path_buf();
+
+                sender.output(Self::Output::SetLoadingStatus(Some(Some(tr!("importing-components-bundle")))));
+
+                std::thread::spawn(move || {
+                    match crate::bundle::import(path) {
+                        Ok(manifest) => {
+                            // Bundled builds are already downloaded so they're selected by default
+                            FirstRunProgress::update(|progress| {
+                                progress.wine = manifest.wine.first().cloned();
+                                progress.dxvk = manifest.dxvk.first().cloned();
+                            });
+
+                            sender.input(DownloadComponentsAppMsg::UpdateVersionsLists);
+                        }
+
+                        Err(err) => {
+                            tracing::error!("Failed to import components bundle: {err}");
+
+                            sender.output(Self::Output::Toast {
+                                title: tr!("components-bundle-failed"),
+                                description: Some(err.to_string())
+                            });
+                        }
+                    }
+
+                    sender.output(Self::Output::SetLoadingStatus(None));
+                });
             }
 
             #[allow(unused_must_use)]
@@ -349,6 +496,8 @@ impl SimpleAsyncComponent for DownloadComponentsApp {
                 if wine.is_downloaded_in(&config.game.wine.builds) {
                     tracing::info!("Wine already installed: {}", wine.name);
 
+                    FirstRunProgress::update(|progress| progress.wine = Some(wine.name.clone()));
+
                     let mut config = Config::get().unwrap_or_else(|_| CONFIG.clone());
 
                     config.game.wine.selected = Some(wine.name);
@@ -372,13 +521,19 @@ impl SimpleAsyncComponent for DownloadComponentsApp {
 
                         // Install wine
                         match get_installer(&wine.uri, config.launcher.temp.clone()) {
-                            Ok(mut installer) => {
+                            Ok(installer) => {
                                 // Create wine builds folder
                                 if config.game.wine.builds.exists() {
                                     std::fs::create_dir_all(&config.game.wine.builds)
                                         .expect("Failed to create wine builds directory");
                                 }
 
+                                let Some((mut installer, checksum)) = verify_archive(installer, ComponentKind::Wine, &wine.name, &wine.uri, progress_bar_input.clone(), &sender) else {
+                                    return;
+                                };
+
+                                let wine_folder = config.game.wine.builds.join(&wine.name);
+
                                 installer.install(&config.game.wine.builds, move |update| {
                                     match &update {
                                         InstallerUpdate::DownloadingError(err) => {
@@ -401,6 +556,12 @@ impl SimpleAsyncComponent for DownloadComponentsApp {
 
                                         // Create prefix
                                         InstallerUpdate::UnpackingFinished => {
+                                            if let Err(err) = checksums::write_manifest(&wine_folder, checksum.clone()) {
+                                                tracing::warn!("Failed to write wine files manifest: {err}");
+                                            }
+
+                                            FirstRunProgress::update(|progress| progress.wine = Some(wine.name.clone()));
+
                                             let mut config = Config::get().unwrap_or_else(|_| CONFIG.clone());
 
                                             config.game.wine.selected = Some(wine.name.clone());
@@ -446,6 +607,15 @@ impl SimpleAsyncComponent for DownloadComponentsApp {
 
                 let config = Config::get().unwrap_or_else(|_| CONFIG.clone());
 
+                // Skip prefix creation if it was done before the first run was interrupted
+                if FirstRunProgress::get().prefix_created && config.game.wine.prefix.exists() {
+                    tracing::info!("Wine prefix is already created");
+
+                    sender.input(DownloadComponentsAppMsg::DownloadDXVK);
+
+                    return;
+                }
+
                 tracing::info!("Creating wine prefix");
 
                 let wine = self.selected_wine.as_ref().unwrap();
@@ -459,7 +629,11 @@ impl SimpleAsyncComponent for DownloadComponentsApp {
                 std::thread::spawn(move || {
                     match wine.init_prefix(None::<&str>) {
                         // Download DXVK
-                        Ok(_) => sender.input(DownloadComponentsAppMsg::DownloadDXVK),
+                        Ok(_) => {
+                            FirstRunProgress::update(|progress| progress.prefix_created = true);
+
+                            sender.input(DownloadComponentsAppMsg::DownloadDXVK);
+                        }
 
                         Err(err) => {
                             tracing::error!("Failed to create prefix: {err}");
@@ -486,6 +660,8 @@ impl SimpleAsyncComponent for DownloadComponentsApp {
                 if dxvk.is_downloaded_in(&config.game.dxvk.builds) {
                     tracing::info!("DXVK is already downloaded: {}", dxvk.name);
 
+                    FirstRunProgress::update(|progress| progress.dxvk = Some(dxvk.name.clone()));
+
                     sender.input(DownloadComponentsAppMsg::ApplyDXVK);
                 }
 
@@ -495,7 +671,7 @@ impl SimpleAsyncComponent for DownloadComponentsApp {
                         tracing::info!("Installing DXVK: {}", dxvk.name);
 
                         match get_installer(&dxvk.uri, config.launcher.temp.clone()) {
-                            Ok(mut installer) => {
+                            Ok(installer) => {
                                 let progress_bar_input = progress_bar_input.clone();
                                 let sender = sender.clone();
 
@@ -505,6 +681,12 @@ impl SimpleAsyncComponent for DownloadComponentsApp {
                                         .expect("Failed to create DXVK builds directory");
                                 }
 
+                                let Some((mut installer, checksum)) = verify_archive(installer, ComponentKind::Dxvk, &dxvk.name, &dxvk.uri, progress_bar_input.clone(), &sender) else {
+                                    return;
+                                };
+
+                                let dxvk_folder = config.game.dxvk.builds.join(&dxvk.name);
+
                                 installer.install(&config.game.dxvk.builds, move |update| {
                                     match &update {
                                         InstallerUpdate::DownloadingError(err) => {
@@ -527,6 +709,12 @@ impl SimpleAsyncComponent for DownloadComponentsApp {
 
                                         // Apply DXVK
                                         InstallerUpdate::UnpackingFinished => {
+                                            if let Err(err) = checksums::write_manifest(&dxvk_folder, checksum.clone()) {
+                                                tracing::warn!("Failed to write DXVK files manifest: {err}");
+                                            }
+
+                                            FirstRunProgress::update(|progress| progress.dxvk = Some(dxvk.name.clone()));
+
                                             sender.input(DownloadComponentsAppMsg::ApplyDXVK);
                                         }
 
@@ -562,6 +750,17 @@ impl SimpleAsyncComponent for DownloadComponentsApp {
                 let wine = self.selected_wine.clone().unwrap();
                 let dxvk = self.selected_dxvk.clone().unwrap();
 
+                // Skip DXVK applying if it was done before the first run was interrupted
+                let progress = FirstRunProgress::get();
+
+                if progress.dxvk_applied && progress.dxvk.as_ref() == Some(&dxvk.name) {
+                    tracing::info!("DXVK is already applied: {}", dxvk.name);
+
+                    sender.input(DownloadComponentsAppMsg::Continue);
+
+                    return;
+                }
+
                 let group = wine.find_group(&config.components.path).unwrap().unwrap();
 
                 // Apply DXVK if we need it
@@ -588,7 +787,11 @@ impl SimpleAsyncComponent for DownloadComponentsApp {
 
                         match wine.install_dxvk(config.game.dxvk.builds.join(&dxvk.name), params) {
                             // Go to next page
-                            Ok(_) => sender.input(DownloadComponentsAppMsg::Continue),
+                            Ok(_) => {
+                                FirstRunProgress::update(|progress| progress.dxvk_applied = true);
+
+                                sender.input(DownloadComponentsAppMsg::Continue);
+                            }
 
                             Err(err) => {
                                 tracing::error!("Failed to apply DXVK: {err}");
@@ -13,6 +13,7 @@ use super::dependencies::*;
 use super::default_paths::*;
 use super::download_components::*;
 use super::finish::*;
+use super::progress::*;
 
 pub static mut MAIN_WINDOW: Option<adw::ApplicationWindow> = None;
 
@@ -43,6 +44,9 @@ pub enum FirstRunAppMsg {
     ScrollToDownloadComponents,
     ScrollToFinish,
 
+    /// Sent by the default paths page in the migrate installation mode
+    MigrationFinished(MigrationStatus),
+
     Toast {
         title: String,
         description: Option<String>
@@ -164,6 +168,21 @@ impl SimpleComponent for FirstRunApp {
 
         crate::READY.store(true, Ordering::Relaxed);
 
+        // Resume interrupted wizard from the first unfinished step
+        let step = FirstRunProgress::get().next_step();
+
+        tracing::info!("Resuming first run from {step:?} step");
+
+        match step {
+            FirstRunStep::Welcome => (),
+
+            FirstRunStep::TosWarning   => sender.input(FirstRunAppMsg::ScrollToTosWarning),
+            FirstRunStep::Dependencies => sender.input(FirstRunAppMsg::ScrollToDependencies),
+            FirstRunStep::DefaultPaths => sender.input(FirstRunAppMsg::ScrollToDefaultPaths),
+
+            FirstRunStep::DownloadComponents => sender.input(FirstRunAppMsg::ScrollToDownloadComponents)
+        }
+
         tracing::info!("First run window initialized. App is ready");
 
         ComponentParts { model, widgets } // will return soon
@@ -178,24 +197,32 @@ impl SimpleComponent for FirstRunApp {
             }
 
             FirstRunAppMsg::ScrollToTosWarning => {
+                FirstRunProgress::reach(FirstRunStep::TosWarning);
+
                 self.title = tr!("tos-warning");
 
                 self.carousel.scroll_to(self.tos_warning.widget(), true);
             }
 
             FirstRunAppMsg::ScrollToDependencies => {
+                FirstRunProgress::reach(FirstRunStep::Dependencies);
+
                 self.title = tr!("dependencies");
 
                 self.carousel.scroll_to(self.dependencies.widget(), true);
             }
 
             FirstRunAppMsg::ScrollToDefaultPaths => {
+                FirstRunProgress::reach(FirstRunStep::DefaultPaths);
+
                 self.title = tr!("default-paths");
 
                 self.carousel.scroll_to(self.default_paths.widget(), true);
             }
 
             FirstRunAppMsg::ScrollToDownloadComponents => {
+                FirstRunProgress::reach(FirstRunStep::DownloadComponents);
+
                 // Update components index
                 sender.input(FirstRunAppMsg::SetLoadingStatus(Some(Some(tr!("updating-components-index")))));
 
@@ -211,7 +238,7 @@ impl SimpleComponent for FirstRunApp {
 
                         Ok(None) => {
                             for host in &CONFIG.components.servers {
-                                match components.sync(host) {
+                                match crate::components_index::sync(&components, host) {
                                     Ok(_) => break,
 
                                     Err(err) => {
@@ -256,6 +283,9 @@ impl SimpleComponent for FirstRunApp {
                 self.carousel.scroll_to(self.finish.widget(), true);
             }
 
+            // Installation can't be migrated from the first run window
+            FirstRunAppMsg::MigrationFinished(_) => (),
+
             FirstRunAppMsg::Toast { title, description } => unsafe {
                 let toast = adw::Toast::new(&title);
 
@@ -1,4 +1,5 @@
 pub mod main;
+pub mod progress;
 pub mod welcome;
 pub mod tos_warning;
 pub mod dependencies;
@@ -0,0 +1,98 @@
+use serde::{Serialize, Deserialize};
+
+use crate::FIRST_RUN_FILE;
+
+/// Steps of the first run wizard which can be resumed
+///
+/// Finish page is not listed because `.first-run` file is removed
+/// once the components are downloaded
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum FirstRunStep {
+    Welcome,
+    TosWarning,
+    Dependencies,
+    DefaultPaths,
+    DownloadComponents
+}
+
+impl FirstRunStep {
+    #[inline]
+    pub fn list() -> &'static [Self] {
+        &[
+            Self::Welcome,
+            Self::TosWarning,
+            Self::Dependencies,
+            Self::DefaultPaths,
+            Self::DownloadComponents
+        ]
+    }
+}
+
+/// Progress of the first run wizard
+///
+/// Stored in the `.first-run` file so the wizard can be resumed
+/// from the first unfinished step after the launcher restart
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FirstRunProgress {
+    pub completed: Vec<FirstRunStep>,
+
+    /// Name of the downloaded wine version
+    pub wine: Option<String>,
+
+    /// Name of the downloaded DXVK version
+    pub dxvk: Option<String>,
+
+    pub prefix_created: bool,
+    pub dxvk_applied: bool
+}
+
+impl FirstRunProgress {
+    /// Read wizard progress. Empty `.first-run` file means that nothing was done yet
+    pub fn get() -> Self {
+        std::fs::read(FIRST_RUN_FILE.as_path()).ok()
+            .filter(|progress| !progress.is_empty())
+            .and_then(|progress| serde_json::from_slice(&progress).ok())
+            .unwrap_or_default()
+    }
+
+    /// Update wizard progress
+    ///
+    /// Does nothing if the first run was already finished and `.first-run` file removed
+    pub fn update(updater: impl FnOnce(&mut Self)) {
+        if !FIRST_RUN_FILE.exists() {
+            return;
+        }
+
+        let mut progress = Self::get();
+
+        updater(&mut progress);
+
+        let result = serde_json::to_string_pretty(&progress)
+            .map_err(anyhow::Error::from)
+            .and_then(|progress| Ok(std::fs::write(FIRST_RUN_FILE.as_path(), progress)?));
+
+        if let Err(err) = result {
+            tracing::error!("Failed to save first run progress: {err}");
+        }
+    }
+
+    /// Mark all the steps before the given one as completed
+    pub fn reach(step: FirstRunStep) {
+        Self::update(|progress| {
+            for completed in FirstRunStep::list().iter().take_while(|completed| **completed < step) {
+                if !progress.completed.contains(completed) {
+                    progress.completed.push(*completed);
+                }
+            }
+        });
+    }
+
+    /// Get the first step which is not completed yet
+    pub fn next_step(&self) -> FirstRunStep {
+        FirstRunStep::list().iter()
+            .copied()
+            .find(|step| !self.completed.contains(step))
+            .unwrap_or(FirstRunStep::DownloadComponents)
+    }
+}
@@ -1,8 +1,6 @@
 use relm4::prelude::*;
 use adw::prelude::*;
 
-use anime_launcher_sdk::is_available;
-
 use crate::*;
 
 use super::main::FirstRunAppMsg;
@@ -103,12 +101,7 @@ impl SimpleAsyncComponent for TosWarningApp {
                         "exit" => relm4::main_application().quit(),
 
                         "continue" => {
-                            let installed =
-                                is_available("git") &&
-                                is_available("dwebp") &&
-                                (is_available("7z") || is_available("7za"));
-
-                            if installed {
+                            if crate::dependencies::missing().is_empty() {
                                 sender.output(Self::Output::ScrollToDefaultPaths);
                             } else {
                                 sender.output(Self::Output::ScrollToDependencies);
@@ -0,0 +1,543 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use relm4::prelude::*;
+use gtk::prelude::*;
+use adw::prelude::*;
+
+use gtk::glib::clone;
+
+use anime_launcher_sdk::config::ConfigExt;
+use anime_launcher_sdk::wuwa::config::Config;
+
+use crate::*;
+use crate::import::*;
+use crate::move_files::Journal;
+use crate::ui::components::progress_bar::*;
+
+pub struct ImportInstallationApp {
+    progress_bar: AsyncController<ProgressBar>,
+
+    toast_overlay: adw::ToastOverlay,
+
+    installations_group: adw::PreferencesGroup,
+    installations_rows: Vec<adw::ActionRow>,
+
+    /// Found installations with their game versions. `None` means that version can't be verified
+    installations: Vec<(FoundInstallation, Option<String>)>,
+    selected: Option<usize>,
+
+    import_prefix: bool,
+    move_files: bool,
+
+    searching: bool,
+    importing: bool,
+
+    /// Set to cancel running import
+    cancel: Arc<AtomicBool>
+}
+
+#[derive(Debug, Clone)]
+pub enum ImportInstallationAppMsg {
+    Detect,
+    SetInstallations(Vec<(FoundInstallation, Option<String>)>),
+    ChooseFolder,
+    Select(usize),
+    SetImportPrefix(bool),
+    SetMoveFiles(bool),
+    Import,
+    Cancel,
+    ImportFinished(Result<(), String>),
+
+    Toast {
+        title: String,
+        description: Option<String>
+    }
+}
+
+impl ImportInstallationApp {
+    fn selected(&self) -> Option<&(FoundInstallation, Option<String>)> {
+        self.selected.and_then(|i| self.installations.get(i))
+    }
+
+    fn selected_prefix(&self) -> bool {
+        self.selected()
+            .map(|(installation, _)| installation.prefix.is_some())
+            .unwrap_or(false)
+    }
+
+    fn update_rows(&mut self, sender: &AsyncComponentSender<Self>) {
+        for row in self.installations_rows.drain(..) {
+            self.installations_group.remove(&row);
+        }
+
+        let mut group: Option<gtk::CheckButton> = None;
+
+        for (i, (installation, version)) in self.installations.iter().enumerate() {
+            let row = adw::ActionRow::new();
+
+            row.set_title(&match version {
+                Some(version) => format!("{} ({version})", installation.source.title()),
+                None => format!("{} ({})", installation.source.title(), tr!("import-not-verified"))
+            });
+
+            row.set_subtitle(&gtk::glib::markup_escape_text(&installation.game.to_string_lossy()));
+
+            let check = gtk::CheckButton::new();
+
+            check.set_active(self.selected == Some(i));
+            check.set_sensitive(version.is_some());
+            check.set_group(group.as_ref());
+
+            check.connect_toggled(clone!(
+                #[strong]
+                sender,
+
+                move |check| {
+                    if check.is_active() {
+                        sender.input(ImportInstallationAppMsg::Select(i));
+                    }
+                }
+            ));
+
+            row.add_prefix(&check);
+            row.set_activatable_widget(Some(&check));
+
+            group.get_or_insert(check);
+
+            self.installations_group.add(&row);
+            self.installations_rows.push(row);
+        }
+    }
+
+    /// Update config to use the selected installation and, in move mode,
+    /// move its files to the folders from the config
+    fn import(&mut self, sender: AsyncComponentSender<Self>) -> anyhow::Result<()> {
+        let Some((installation, _)) = self.selected().cloned() else {
+            return Ok(());
+        };
+
+        let mut config = Config::get()?;
+
+        let edition = config.launcher.edition;
+        let prefix = installation.prefix.filter(|_| self.import_prefix);
+
+        if !self.move_files {
+            match edition {
+                GameEdition::Global => config.game.path.global = installation.game,
+                GameEdition::China  => config.game.path.china  = installation.game
+            }
+
+            if let Some(prefix) = prefix {
+                config.game.wine.prefix = prefix;
+            }
+
+            Config::update_raw(config)?;
+
+            sender.input(ImportInstallationAppMsg::ImportFinished(Ok(())));
+
+            return Ok(());
+        }
+
+        let mut folders = vec![(installation.game, config.game.path.for_edition(edition).to_path_buf())];
+
+        if let Some(prefix) = prefix {
+            folders.push((prefix, config.game.wine.prefix.clone()));
+        }
+
+        // Folders from config are used as the targets, so config doesn't change.
+        // Import has its own journal because interrupted import is not resumed on the launcher start
+        let journal = Journal::new(folders, serde_json::to_value(&config)?)
+            .with_file(CACHE_FOLDER.join(".import"));
+
+        // Files are not merged into the existing installation
+        for folder in &journal.folders {
+            let not_empty = folder.to.read_dir()
+                .map(|mut entries| entries.next().is_some())
+                .unwrap_or(false);
+
+            if not_empty {
+                anyhow::bail!(tr!("import-target-not-empty", {
+                    "path" = folder.to.to_string_lossy().to_string()
+                }));
+            }
+        }
+
+        journal.check_free_space()?;
+
+        self.importing = true;
+
+        self.cancel.store(false, Ordering::Relaxed);
+
+        let progress_bar = self.progress_bar.sender().to_owned();
+
+        #[allow(unused_must_use)]
+        {
+            progress_bar.send(ProgressBarMsg::Reset);
+            progress_bar.send(ProgressBarMsg::UpdateCaption(Some(tr!("migrating-folders"))));
+        }
+
+        let cancel = self.cancel.clone();
+
+        std::thread::spawn(move || {
+            #[allow(unused_must_use)]
+            let result = journal.migrate(&cancel, move |moved, total| {
+                progress_bar.send(ProgressBarMsg::UpdateProgress(moved, total));
+            });
+
+            let result = match result {
+                Ok(_) => Ok(()),
+
+                // Return already moved files back
+                Err(_) if cancel.load(Ordering::Relaxed) => journal.revert(|_, _| {})
+                    .map_err(|err| err.to_string())
+                    .and(Err(tr!("import-cancelled"))),
+
+                Err(err) => Err(err.to_string())
+            };
+
+            sender.input(ImportInstallationAppMsg::ImportFinished(result));
+        });
+
+        Ok(())
+    }
+}
+
+#[relm4::component(async, pub)]
+impl SimpleAsyncComponent for ImportInstallationApp {
+    type Init = ();
+    type Input = ImportInstallationAppMsg;
+
+    /// Sent when installation was imported
+    type Output = ();
+
+    view! {
+        adw::Window {
+            set_default_size: (780, 560),
+            set_modal: true,
+            set_hide_on_close: true,
+
+            #[watch]
+            set_title: Some(&tr!("import-installation")),
+
+            #[local_ref]
+            toast_overlay -> adw::ToastOverlay {
+                gtk::Box {
+                    set_orientation: gtk::Orientation::Vertical,
+
+                    adw::HeaderBar {
+                        add_css_class: "flat"
+                    },
+
+                    adw::PreferencesPage {
+                        #[watch]
+                        set_sensitive: !model.importing,
+
+                        #[local_ref]
+                        add = installations_group -> adw::PreferencesGroup {
+                            set_title: &tr!("found-installations"),
+                            set_description: Some(&tr!("found-installations-description")),
+
+                            #[wrap(Some)]
+                            set_header_suffix = &gtk::Box {
+                                set_spacing: 8,
+
+                                gtk::Spinner {
+                                    #[watch]
+                                    set_spinning: model.searching,
+
+                                    #[watch]
+                                    set_visible: model.searching
+                                },
+
+                                gtk::Button {
+                                    set_icon_name: "view-refresh-symbolic",
+                                    set_tooltip_text: Some(&tr!("search-again")),
+                                    add_css_class: "flat",
+
+                                    #[watch]
+                                    set_sensitive: !model.searching,
+
+                                    connect_clicked => ImportInstallationAppMsg::Detect
+                                },
+
+                                gtk::Button {
+                                    set_icon_name: "folder-symbolic",
+                                    set_tooltip_text: Some(&tr!("choose-folder")),
+                                    add_css_class: "flat",
+
+                                    connect_clicked => ImportInstallationAppMsg::ChooseFolder
+                                }
+                            }
+                        },
+
+                        add = &adw::PreferencesGroup {
+                            adw::SwitchRow {
+                                set_title: &tr!("import-prefix"),
+                                set_subtitle: &tr!("import-prefix-description"),
+
+                                #[watch]
+                                set_sensitive: model.selected_prefix(),
+
+                                #[watch]
+                                #[block_signal(import_prefix_handler)]
+                                set_active: model.import_prefix && model.selected_prefix(),
+
+                                connect_active_notify[sender] => move |row| {
+                                    sender.input(ImportInstallationAppMsg::SetImportPrefix(row.is_active()));
+                                } @import_prefix_handler
+                            },
+
+                            adw::SwitchRow {
+                                set_title: &tr!("import-move-files"),
+                                set_subtitle: &tr!("import-move-files-description"),
+
+                                #[watch]
+                                #[block_signal(move_files_handler)]
+                                set_active: model.move_files,
+
+                                connect_active_notify[sender] => move |row| {
+                                    sender.input(ImportInstallationAppMsg::SetMoveFiles(row.is_active()));
+                                } @move_files_handler
+                            }
+                        },
+
+                        add = &adw::PreferencesGroup {
+                            gtk::Button {
+                                set_label: &tr!("import"),
+                                set_css_classes: &["suggested-action", "pill"],
+                                set_halign: gtk::Align::Center,
+
+                                #[watch]
+                                set_sensitive: model.selected().is_some(),
+
+                                connect_clicked => ImportInstallationAppMsg::Import
+                            }
+                        }
+                    },
+
+                    gtk::Box {
+                        set_orientation: gtk::Orientation::Vertical,
+                        set_halign: gtk::Align::Center,
+                        set_spacing: 16,
+                        set_margin_bottom: 24,
+
+                        #[watch]
+                        set_visible: model.importing,
+
+                        append = model.progress_bar.widget(),
+
+                        gtk::Button {
+                            set_label: &tr!("cancel"),
+                            set_halign: gtk::Align::Center,
+                            add_css_class: "pill",
+
+                            connect_clicked => ImportInstallationAppMsg::Cancel
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    async fn init(_init: Self::Init, root: Self::Root, sender: AsyncComponentSender<Self>) -> AsyncComponentParts<Self> {
+        tracing::info!("Initializing installation import window");
+
+        let model = Self {
+            progress_bar: ProgressBar::builder()
+                .launch(ProgressBarInit {
+                    caption: None,
+                    display_progress: true,
+                    display_fraction: true,
+                    visible: true
+                })
+                .detach(),
+
+            toast_overlay: adw::ToastOverlay::new(),
+
+            installations_group: adw::PreferencesGroup::new(),
+            installations_rows: Vec::new(),
+
+            installations: Vec::new(),
+            selected: None,
+
+            import_prefix: true,
+            move_files: false,
+
+            searching: false,
+            importing: false,
+
+            cancel: Arc::new(AtomicBool::new(false))
+        };
+
+        model.progress_bar.widget().set_width_request(360);
+
+        let toast_overlay = &model.toast_overlay;
+        let installations_group = &model.installations_group;
+
+        let widgets = view_output!();
+
+        sender.input(ImportInstallationAppMsg::Detect);
+
+        AsyncComponentParts { model, widgets }
+    }
+
+    async fn update(&mut self, msg: Self::Input, sender: AsyncComponentSender<Self>) {
+        tracing::debug!("Called installation import window event: {:?}", msg);
+
+        match msg {
+            ImportInstallationAppMsg::Detect => {
+                self.searching = true;
+
+                let edition = Config::get()
+                    .map(|config| config.launcher.edition)
+                    .unwrap_or(CONFIG.launcher.edition);
+
+                std::thread::spawn(clone!(
+                    #[strong]
+                    sender,
+
+                    move || {
+                        let installations = detect().into_iter()
+                            .map(|installation| {
+                                let version = installation.verify(edition)
+                                    .map(|version| version.to_string())
+                                    .ok();
+
+                                (installation, version)
+                            })
+                            .collect();
+
+                        sender.input(ImportInstallationAppMsg::SetInstallations(installations));
+                    }
+                ));
+            }
+
+            ImportInstallationAppMsg::SetInstallations(installations) => {
+                self.searching = false;
+
+                // Keep manually chosen folders
+                let chosen = self.installations.drain(..)
+                    .filter(|(installation, _)| installation.source == ImportSource::Folder)
+                    .collect::<Vec<_>>();
+
+                self.installations = installations;
+                self.installations.extend(chosen);
+
+                self.selected = self.installations.iter().position(|(_, version)| version.is_some());
+
+                self.update_rows(&sender);
+            }
+
+            ImportInstallationAppMsg::ChooseFolder => {
+                let Some(folder) = rfd::AsyncFileDialog::new().pick_folder().await else {
+                    return;
+                };
+
+                let Some(installation) = from_folder(folder.path()) else {
+                    sender.input(ImportInstallationAppMsg::Toast {
+                        title: tr!("import-not-game-folder"),
+                        description: None
+                    });
+
+                    return;
+                };
+
+                let edition = Config::get()
+                    .map(|config| config.launcher.edition)
+                    .unwrap_or(CONFIG.launcher.edition);
+
+                match installation.verify(edition) {
+                    Ok(version) => {
+                        self.installations.push((installation, Some(version.to_string())));
+                        self.selected = Some(self.installations.len() - 1);
+
+                        self.update_rows(&sender);
+                    }
+
+                    Err(err) => {
+                        tracing::error!("Failed to verify game version: {err}");
+
+                        sender.input(ImportInstallationAppMsg::Toast {
+                            title: tr!("import-not-game-folder"),
+                            description: Some(err.to_string())
+                        });
+                    }
+                }
+            }
+
+            ImportInstallationAppMsg::Select(i) => self.selected = Some(i),
+
+            ImportInstallationAppMsg::SetImportPrefix(value) => self.import_prefix = value,
+            ImportInstallationAppMsg::SetMoveFiles(value) => self.move_files = value,
+
+            ImportInstallationAppMsg::Import => {
+                if let Err(err) = self.import(sender.clone()) {
+                    tracing::error!("Failed to import installation: {err}");
+
+                    sender.input(ImportInstallationAppMsg::Toast {
+                        title: tr!("import-failed"),
+                        description: Some(err.to_string())
+                    });
+                }
+            }
+
+            #[allow(unused_must_use)]
+            ImportInstallationAppMsg::Cancel => {
+                self.cancel.store(true, Ordering::Relaxed);
+
+                self.progress_bar.sender().send(ProgressBarMsg::UpdateCaption(Some(tr!("cancelling"))));
+            }
+
+            #[allow(unused_must_use)]
+            ImportInstallationAppMsg::ImportFinished(result) => {
+                self.importing = false;
+
+                match result {
+                    Ok(_) => {
+                        tracing::info!("Installation imported");
+
+                        if let Some(window) = self.toast_overlay.root().and_downcast::<adw::Window>() {
+                            window.close();
+                        }
+
+                        sender.output(());
+                    }
+
+                    Err(err) => {
+                        tracing::error!("Failed to import installation: {err}");
+
+                        sender.input(ImportInstallationAppMsg::Toast {
+                            title: tr!("import-failed"),
+                            description: Some(err)
+                        });
+                    }
+                }
+            }
+
+            ImportInstallationAppMsg::Toast { title, description } => {
+                let toast = adw::Toast::new(&title);
+
+                toast.set_timeout(4);
+
+                if let Some(description) = description {
+                    toast.set_button_label(Some(&tr!("details")));
+
+                    let dialog = adw::MessageDialog::new(
+                        self.toast_overlay.root().and_downcast::<gtk::Window>().as_ref(),
+                        Some(&title),
+                        Some(&description)
+                    );
+
+                    dialog.add_response("close", &tr!("close", { "form" = "noun" }));
+
+                    toast.connect_button_clicked(move |_| {
+                        dialog.present();
+                    });
+                }
+
+                self.toast_overlay.add_toast(toast);
+            }
+        }
+    }
+}
@@ -12,7 +12,7 @@ use super::{App, AppMsg};
 pub fn create_prefix(sender: ComponentSender<App>) {
     let config = Config::get().unwrap();
 
-    match config.get_selected_wine() {
+    match crate::custom_wine::get_selected(&config) {
         Ok(Some(wine)) => {
             sender.input(AppMsg::DisableButtons(true));
 
@@ -9,6 +9,7 @@ use anime_launcher_sdk::components::wine;
 
 use crate::*;
 use crate::ui::components::*;
+use crate::checksums::{self, ComponentKind, VerifyError};
 
 use super::{App, AppMsg};
 
@@ -44,7 +45,7 @@ pub fn download_wine(sender: ComponentSender<App>, progress_bar_input: Sender<Pr
                 };
 
                 // Download wine version
-                match Installer::new(wine.uri) {
+                match Installer::new(&wine.uri) {
                     Ok(mut installer) => {
                         if let Some(temp_folder) = &config.launcher.temp {
                             installer.temp_folder = temp_folder.to_path_buf();
@@ -53,6 +54,44 @@ pub fn download_wine(sender: ComponentSender<App>, progress_bar_input: Sender<Pr
                         sender.input(AppMsg::SetDownloading(true));
 
                         std::thread::spawn(clone!(@strong sender => move || {
+                            let temp_folder = installer.temp_folder.clone();
+
+                            // Verify the archive before unpacking it
+                            let result = checksums::verify_archive(installer, &config.components.path, ComponentKind::Wine, &wine.name, &wine.uri, &temp_folder, clone!(@strong progress_bar_input => move |curr, total| {
+                                #[allow(unused_must_use)] {
+                                    progress_bar_input.send(ProgressBarMsg::UpdateProgress(curr, total));
+                                }
+                            }));
+
+                            let (mut installer, checksum) = match result {
+                                Ok(result) => result,
+
+                                Err(err) => {
+                                    tracing::error!("Failed to verify wine archive: {err}");
+
+                                    sender.input(AppMsg::Toast {
+                                        title: match err {
+                                            VerifyError::Mismatch { .. } => tr!("component-checksum-mismatch"),
+                                            VerifyError::Download(_) => tr!("downloading-failed")
+                                        },
+                                        description: Some(err.to_string())
+                                    });
+
+                                    sender.input(AppMsg::SetDownloading(false));
+
+                                    return;
+                                }
+                            };
+
+                            if checksum.is_none() {
+                                sender.input(AppMsg::Toast {
+                                    title: tr!("component-checksum-unavailable"),
+                                    description: None
+                                });
+                            }
+
+                            let wine_folder = config.game.wine.builds.join(&wine.name);
+
                             installer.install(&config.game.wine.builds, clone!(@strong sender => move |state| {
                                 match &state {
                                     InstallerUpdate::DownloadingError(err) => {
@@ -73,6 +112,12 @@ pub fn download_wine(sender: ComponentSender<App>, progress_bar_input: Sender<Pr
                                         });
                                     }
 
+                                    InstallerUpdate::UnpackingFinished => {
+                                        if let Err(err) = checksums::write_manifest(&wine_folder, checksum.clone()) {
+                                            tracing::warn!("Failed to write wine files manifest: {err}");
+                                        }
+                                    }
+
                                     _ => ()
                                 }
 
@@ -1,8 +1,6 @@
 use relm4::prelude::*;
 use gtk::prelude::*;
 
-use anime_launcher_sdk::wincompatlib::prelude::*;
-
 use anime_launcher_sdk::config::ConfigExt;
 use anime_launcher_sdk::wuwa::config::Config;
 use anime_launcher_sdk::wuwa::config::schema::prelude::LauncherBehavior;
@@ -26,37 +24,16 @@ pub fn launch(sender: ComponentSender<App>) {
     }
 
     std::thread::spawn(move || {
-        // I honestly don't care anymore
-        let wine = config.get_selected_wine().unwrap().unwrap();
-
-        let wine = wine
-            .to_wine(config.components.path, Some(config.game.wine.builds.join(&wine.name)))
-            .with_loader(WineLoader::Current)
-            .with_arch(WineArch::Win64)
-            .with_prefix(&config.game.wine.prefix);
+        if let Err(err) = crate::registry::apply(&config) {
+            tracing::error!("Failed to apply registry tweaks: {err}");
 
-        // Could definitely make this better
-        if config.game.enhancements.fix_launch_dialog {
-            let command = ["reg", "add", "HKCU\\Software\\Wine\\DllOverrides", "/v", "krsdkexternal.exe", "/f"];
-            wine.run_args_with_env(command, config.game.wine.sync.get_env_vars())
-                .expect("Failed to run wine server")
-                .wait()
-                .expect(&format!("Failed to run command {:?}", command));
-        } else {
-            let command = ["reg", "delete", "HKCU\\Software\\Wine\\DllOverrides", "/v", "krsdkexternal.exe", "/f"];
-            wine.run_args_with_env(command, config.game.wine.sync.get_env_vars())
-                .expect("Failed to run wine server")
-                .wait()
-                .expect(&format!("Failed to run command {:?}", command));
+            sender.input(AppMsg::Toast {
+                title: tr!("registry-tweaks-failed"),
+                description: Some(err.to_string())
+            });
         }
 
-        // Fix for the in-game browser being a black window
-        wine.run_args_with_env(["winecfg", "-v", "win7"], config.game.wine.sync.get_env_vars())
-            .expect("Failed to run wine server")
-            .wait()
-            .expect("Failed to run winecfg -v win7");
-
-        wine.shutdown().expect("Failed to shutdown wineserver");
+        let custom_wine = crate::custom_wine::register();
 
         if let Err(err) = anime_launcher_sdk::wuwa::game::run() {
             tracing::error!("Failed to launch game: {err}");
@@ -67,6 +44,8 @@ pub fn launch(sender: ComponentSender<App>) {
             });
         }
 
+        drop(custom_wine);
+
         match config.launcher.behavior {
             // Enable launch button and hide kill game button if behavior set to "Nothing" after the game has closed
             LauncherBehavior::Nothing => {
@@ -1,3 +1,6 @@
+use std::path::PathBuf;
+use std::sync::atomic::AtomicBool;
+
 use relm4::{
     prelude::*,
     actions::*,
@@ -16,6 +19,7 @@ mod create_prefix;
 mod download_diff;
 mod disable_telemetry;
 mod launch;
+mod upgrade_components;
 
 use anime_launcher_sdk::components::loader::ComponentsLoader;
 
@@ -50,6 +54,10 @@ pub struct App {
     progress_bar: AsyncController<ProgressBar>,
 
     toast_overlay: adw::ToastOverlay,
+    background_video: gtk::Picture,
+
+    /// Background video played behind the classic style controls
+    video: Option<gtk::MediaFile>,
 
     loading: Option<Option<String>>,
     style: LauncherStyle,
@@ -91,6 +99,15 @@ pub enum AppMsg {
     SetKillGameButton(bool),
     DisableKillGameButton(bool),
 
+    /// Re-read processed background picture
+    ReloadBackground,
+
+    /// Check background video settings and start or stop playing it
+    UpdateBackgroundVideo,
+
+    /// Play given video file behind the classic style controls, or stop playing it
+    SetBackgroundVideo(Option<PathBuf>),
+
     OpenPreferences,
     RepairGame,
 
@@ -99,6 +116,13 @@ pub enum AppMsg {
     HideWindow,
     ShowWindow,
 
+    /// Resume or revert interrupted installation migration and restart the launcher
+    FinishMigration {
+        revert: bool
+    },
+
+    RestartLauncher,
+
     Toast {
         title: String,
         description: Option<String>
@@ -164,358 +188,375 @@ impl SimpleComponent for App {
 
             #[local_ref]
             toast_overlay -> adw::ToastOverlay {
-                gtk::Box {
-                    set_orientation: gtk::Orientation::Vertical,
-
-                    adw::HeaderBar {
-                        #[watch]
-                        set_css_classes: match model.style {
-                            LauncherStyle::Modern => &[""],
-                            LauncherStyle::Classic => &["flat"]
-                        },
-
-                        #[wrap(Some)]
-                        set_title_widget = &adw::WindowTitle {
-                            #[watch]
-                            set_title: match model.style {
-                                LauncherStyle::Modern => "Wavey Launcher",
-                                LauncherStyle::Classic => ""
-                            }
-                        },
-
-                        pack_end = &gtk::MenuButton {
-                            set_icon_name: "open-menu-symbolic",
-                            set_menu_model: Some(&main_menu)
-                        }
-                    },
-
-                    adw::StatusPage {
-                        set_title: &tr!("loading-data"),
-                        set_icon_name: Some(APP_ID),
-                        set_vexpand: true,
-
-                        #[watch]
-                        set_description: match &model.loading {
-                            Some(Some(desc)) => Some(desc),
-                            Some(None) | None => None
-                        },
+                #[name = "background_overlay"]
+                gtk::Overlay {
+                    // Static background picture is shown through the window
+                    // while the video is not loaded or can't be played
+                    #[local_ref]
+                    #[wrap(Some)]
+                    set_child = background_video -> gtk::Picture {
+                        set_content_fit: gtk::ContentFit::Cover,
+                        set_can_shrink: true,
+                        set_can_target: false,
 
                         #[watch]
-                        set_visible: model.loading.is_some()
+                        set_visible: model.style == LauncherStyle::Classic && model.loading.is_none() && model.video.is_some()
                     },
 
-                    adw::PreferencesPage {
-                        #[watch]
-                        set_visible: model.loading.is_none(),
-
-                        add = &adw::PreferencesGroup {
-                            set_margin_top: 48,
+                    #[name = "controls"]
+                    add_overlay = &gtk::Box {
+                        set_orientation: gtk::Orientation::Vertical,
 
+                        adw::HeaderBar {
                             #[watch]
-                            set_visible: model.style == LauncherStyle::Modern,
+                            set_css_classes: match model.style {
+                                LauncherStyle::Modern => &[""],
+                                LauncherStyle::Classic => &["flat"]
+                            },
 
-                            gtk::Picture {
-                                set_resource: Some(&format!("{APP_RESOURCE_PATH}/icons/hicolor/scalable/apps/{APP_ID}.png")),
-                                set_vexpand: true,
-                                set_content_fit: gtk::ContentFit::ScaleDown
+                            #[wrap(Some)]
+                            set_title_widget = &adw::WindowTitle {
+                                #[watch]
+                                set_title: match model.style {
+                                    LauncherStyle::Modern => "Wavey Launcher",
+                                    LauncherStyle::Classic => ""
+                                }
                             },
 
-                            gtk::Label {
-                                set_label: "Wavey Launcher",
-                                set_margin_top: 32,
-                                add_css_class: "title-1"
+                            pack_end = &gtk::MenuButton {
+                                set_icon_name: "open-menu-symbolic",
+                                set_menu_model: Some(&main_menu)
                             }
                         },
 
-                        add = &adw::PreferencesGroup {
+                        adw::StatusPage {
+                            set_title: &tr!("loading-data"),
+                            set_icon_name: Some(APP_ID),
+                            set_vexpand: true,
+
                             #[watch]
-                            set_valign: match model.style {
-                                LauncherStyle::Modern => gtk::Align::Center,
-                                LauncherStyle::Classic => gtk::Align::End
+                            set_description: match &model.loading {
+                                Some(Some(desc)) => Some(desc),
+                                Some(None) | None => None
                             },
 
                             #[watch]
-                            set_width_request: match model.style {
-                                LauncherStyle::Modern => -1,
-                                LauncherStyle::Classic => 800
-                            },
+                            set_visible: model.loading.is_some()
+                        },
 
+                        adw::PreferencesPage {
                             #[watch]
-                            set_visible: model.downloading,
+                            set_visible: model.loading.is_none(),
 
-                            set_vexpand: true,
-                            set_margin_top: 48,
-                            set_margin_bottom: 48,
+                            add = &adw::PreferencesGroup {
+                                set_margin_top: 48,
 
-                            add = model.progress_bar.widget(),
-                        },
+                                #[watch]
+                                set_visible: model.style == LauncherStyle::Modern,
 
-                        add = &adw::PreferencesGroup {
-                            #[watch]
-                            set_valign: match model.style {
-                                LauncherStyle::Modern => gtk::Align::Center,
-                                LauncherStyle::Classic => gtk::Align::End
-                            },
+                                gtk::Picture {
+                                    set_resource: Some(&format!("{APP_RESOURCE_PATH}/icons/hicolor/scalable/apps/{APP_ID}.png")),
+                                    set_vexpand: true,
+                                    set_content_fit: gtk::ContentFit::ScaleDown
+                                },
 
-                            #[watch]
-                            set_width_request: match model.style {
-                                LauncherStyle::Modern => -1,
-                                LauncherStyle::Classic => 800
+                                gtk::Label {
+                                    set_label: "Wavey Launcher",
+                                    set_margin_top: 32,
+                                    add_css_class: "title-1"
+                                }
                             },
 
-                            #[watch]
-                            set_visible: !model.downloading,
+                            add = &adw::PreferencesGroup {
+                                #[watch]
+                                set_valign: match model.style {
+                                    LauncherStyle::Modern => gtk::Align::Center,
+                                    LauncherStyle::Classic => gtk::Align::End
+                                },
 
-                            #[watch]
-                            set_margin_bottom: match model.style {
-                                LauncherStyle::Modern => 48,
-                                LauncherStyle::Classic => 0
-                            },
+                                #[watch]
+                                set_width_request: match model.style {
+                                    LauncherStyle::Modern => -1,
+                                    LauncherStyle::Classic => 800
+                                },
 
-                            set_vexpand: true,
+                                #[watch]
+                                set_visible: model.downloading,
 
-                            gtk::Box {
+                                set_vexpand: true,
+                                set_margin_top: 48,
+                                set_margin_bottom: 48,
+
+                                add = model.progress_bar.widget(),
+                            },
+
+                            add = &adw::PreferencesGroup {
                                 #[watch]
-                                set_halign: match model.style {
+                                set_valign: match model.style {
                                     LauncherStyle::Modern => gtk::Align::Center,
                                     LauncherStyle::Classic => gtk::Align::End
                                 },
 
                                 #[watch]
-                                set_height_request: match model.style {
+                                set_width_request: match model.style {
                                     LauncherStyle::Modern => -1,
-                                    LauncherStyle::Classic => 40
+                                    LauncherStyle::Classic => 800
                                 },
 
-                                set_margin_top: 64,
-                                set_spacing: 8,
+                                #[watch]
+                                set_visible: !model.downloading,
 
-                                adw::Bin {
-                                    set_css_classes: &["background", "round-bin"],
+                                #[watch]
+                                set_margin_bottom: match model.style {
+                                    LauncherStyle::Modern => 48,
+                                    LauncherStyle::Classic => 0
+                                },
 
+                                set_vexpand: true,
+
+                                gtk::Box {
                                     #[watch]
-                                    set_visible: !model.kill_game_button,
+                                    set_halign: match model.style {
+                                        LauncherStyle::Modern => gtk::Align::Center,
+                                        LauncherStyle::Classic => gtk::Align::End
+                                    },
 
-                                    gtk::Button {
-                                        adw::ButtonContent {
-                                            #[watch]
-                                            set_icon_name: match &model.state {
-                                                Some(LauncherState::Launch) |
-                                                Some(LauncherState::PatchNotVerified) |
-                                                Some(LauncherState::PatchConcerning) => "media-playback-start-symbolic",
+                                    #[watch]
+                                    set_height_request: match model.style {
+                                        LauncherStyle::Modern => -1,
+                                        LauncherStyle::Classic => 40
+                                    },
 
-                                                Some(LauncherState::PatchNotInstalled) |
-                                                Some(LauncherState::PatchUpdateAvailable) => "document-save-symbolic",
+                                    set_margin_top: 64,
+                                    set_spacing: 8,
 
-                                                Some(LauncherState::TelemetryNotDisabled) => "security-high-symbolic",
+                                    adw::Bin {
+                                        set_css_classes: &["background", "round-bin"],
 
-                                                Some(LauncherState::WineNotInstalled) |
-                                                Some(LauncherState::PrefixNotExists) => "document-save-symbolic",
+                                        #[watch]
+                                        set_visible: !model.kill_game_button,
 
-                                                Some(LauncherState::GameUpdateAvailable(_)) |
-                                                Some(LauncherState::GameNotInstalled(_)) => "document-save-symbolic",
+                                        gtk::Button {
+                                            adw::ButtonContent {
+                                                #[watch]
+                                                set_icon_name: match &model.state {
+                                                    Some(LauncherState::Launch) |
+                                                    Some(LauncherState::PatchNotVerified) |
+                                                    Some(LauncherState::PatchConcerning) => "media-playback-start-symbolic",
 
-                                                Some(LauncherState::PatchBroken) |
-                                                Some(LauncherState::PatchUnsafe) |
-                                                None => "window-close-symbolic"
-                                            },
+                                                    Some(LauncherState::PatchNotInstalled) |
+                                                    Some(LauncherState::PatchUpdateAvailable) => "document-save-symbolic",
 
-                                            #[watch]
-                                            set_label: &match &model.state {
-                                                Some(LauncherState::Launch) |
-                                                Some(LauncherState::PatchNotVerified) |
-                                                Some(LauncherState::PatchConcerning) => tr!("launch"),
+                                                    Some(LauncherState::TelemetryNotDisabled) => "security-high-symbolic",
 
-                                                Some(LauncherState::PatchNotInstalled) |
-                                                Some(LauncherState::PatchUpdateAvailable) => tr!("download-patch"),
+                                                    Some(LauncherState::WineNotInstalled) |
+                                                    Some(LauncherState::PrefixNotExists) => "document-save-symbolic",
 
-                                                Some(LauncherState::PatchBroken) => tr!("patch-broken"),
-                                                Some(LauncherState::PatchUnsafe) => tr!("patch-unsafe"),
+                                                    Some(LauncherState::GameUpdateAvailable(_)) |
+                                                    Some(LauncherState::GameNotInstalled(_)) => "document-save-symbolic",
 
-                                                Some(LauncherState::TelemetryNotDisabled) => tr!("disable-telemetry"),
+                                                    Some(LauncherState::PatchBroken) |
+                                                    Some(LauncherState::PatchUnsafe) |
+                                                    None => "window-close-symbolic"
+                                                },
 
-                                                Some(LauncherState::WineNotInstalled) => tr!("download-wine"),
-                                                Some(LauncherState::PrefixNotExists)  => tr!("create-prefix"),
+                                                #[watch]
+                                                set_label: &match &model.state {
+                                                    Some(LauncherState::Launch) |
+                                                    Some(LauncherState::PatchNotVerified) |
+                                                    Some(LauncherState::PatchConcerning) => tr!("launch"),
 
-                                                Some(LauncherState::GameUpdateAvailable(diff)) => {
-                                                    match (Config::get(), diff.file_name()) {
-                                                        (Ok(config), Some(filename)) => {
-                                                            let temp = config.launcher.temp.unwrap_or_else(std::env::temp_dir);
+                                                    Some(LauncherState::PatchNotInstalled) |
+                                                    Some(LauncherState::PatchUpdateAvailable) => tr!("download-patch"),
 
-                                                            if temp.join(filename).exists() {
-                                                                tr!("resume")
-                                                            }
+                                                    Some(LauncherState::PatchBroken) => tr!("patch-broken"),
+                                                    Some(LauncherState::PatchUnsafe) => tr!("patch-unsafe"),
 
-                                                            else {
-                                                                tr!("update")
-                                                            }
-                                                        }
+                                                    Some(LauncherState::TelemetryNotDisabled) => tr!("disable-telemetry"),
 
-                                                        _ => tr!("update")
-                                                    }
-                                                },
+                                                    Some(LauncherState::WineNotInstalled) => tr!("download-wine"),
+                                                    Some(LauncherState::PrefixNotExists)  => tr!("create-prefix"),
 
-                                                Some(LauncherState::GameNotInstalled(_)) => tr!("download"),
+                                                    Some(LauncherState::GameUpdateAvailable(diff)) => {
+                                                        match (Config::get(), diff.file_name()) {
+                                                            (Ok(config), Some(filename)) => {
+                                                                let temp = config.launcher.temp.unwrap_or_else(std::env::temp_dir);
 
-                                                None => String::from("...")
-                                            }
-                                        },
+                                                                if temp.join(filename).exists() {
+                                                                    tr!("resume")
+                                                                }
 
-                                        #[watch]
-                                        set_sensitive: !model.disabled_buttons && match &model.state {
-                                            Some(LauncherState::PatchBroken) |
-                                            Some(LauncherState::PatchUnsafe) => false,
+                                                                else {
+                                                                    tr!("update")
+                                                                }
+                                                            }
 
-                                            Some(_) => true,
-                                            None => false
-                                        },
+                                                            _ => tr!("update")
+                                                        }
+                                                    },
 
-                                        #[watch]
-                                        set_css_classes: match &model.state {
-                                            Some(LauncherState::PatchNotVerified) => &["warning", "pill"],
+                                                    Some(LauncherState::GameNotInstalled(_)) => tr!("download"),
+
+                                                    None => String::from("...")
+                                                }
+                                            },
 
-                                            Some(LauncherState::PatchBroken) |
-                                            Some(LauncherState::PatchUnsafe) |
-                                            Some(LauncherState::PatchConcerning)
-                                                => &["error", "pill"],
+                                            #[watch]
+                                            set_sensitive: !model.disabled_buttons && match &model.state {
+                                                Some(LauncherState::PatchBroken) |
+                                                Some(LauncherState::PatchUnsafe) => false,
 
-                                            Some(_) => &["suggested-action", "pill"],
-                                            None => &["pill"]
-                                        },
+                                                Some(_) => true,
+                                                None => false
+                                            },
 
-                                        #[watch]
-                                        set_tooltip_text: Some(&match &model.state {
-                                            Some(LauncherState::PatchNotVerified) => tr!("patch-testing-tooltip"),
-                                            Some(LauncherState::PatchBroken) => tr!("patch-broken-tooltip"),
-                                            Some(LauncherState::PatchUnsafe) => tr!("patch-unsafe-tooltip"),
-                                            Some(LauncherState::PatchConcerning) => tr!("patch-concerning-tooltip"),
+                                            #[watch]
+                                            set_css_classes: match &model.state {
+                                                Some(LauncherState::PatchNotVerified) => &["warning", "pill"],
+
+                                                Some(LauncherState::PatchBroken) |
+                                                Some(LauncherState::PatchUnsafe) |
+                                                Some(LauncherState::PatchConcerning)
+                                                    => &["error", "pill"],
 
-                                            _ => String::new()
-                                        }),
+                                                Some(_) => &["suggested-action", "pill"],
+                                                None => &["pill"]
+                                            },
 
-                                        set_hexpand: false,
-                                        set_width_request: 200,
+                                            #[watch]
+                                            set_tooltip_text: Some(&match &model.state {
+                                                Some(LauncherState::PatchNotVerified) => tr!("patch-testing-tooltip"),
+                                                Some(LauncherState::PatchBroken) => tr!("patch-broken-tooltip"),
+                                                Some(LauncherState::PatchUnsafe) => tr!("patch-unsafe-tooltip"),
+                                                Some(LauncherState::PatchConcerning) => tr!("patch-concerning-tooltip"),
 
-                                        connect_clicked => AppMsg::PerformAction
-                                    }
-                                },
+                                                _ => String::new()
+                                            }),
 
-                                adw::Bin {
-                                    set_css_classes: &["background", "round-bin"],
+                                            set_hexpand: false,
+                                            set_width_request: 200,
 
-                                    #[watch]
-                                    set_visible: model.kill_game_button,
+                                            connect_clicked => AppMsg::PerformAction
+                                        }
+                                    },
 
-                                    gtk::Button {
-                                        adw::ButtonContent {
-                                            set_icon_name: "violence-symbolic", // window-close-symbolic
-                                            set_label: &tr!("kill-game-process")
-                                        },
+                                    adw::Bin {
+                                        set_css_classes: &["background", "round-bin"],
 
                                         #[watch]
-                                        set_sensitive: !model.disabled_kill_game_button,
+                                        set_visible: model.kill_game_button,
 
-                                        set_css_classes: &["error", "pill"],
+                                        gtk::Button {
+                                            adw::ButtonContent {
+                                                set_icon_name: "violence-symbolic", // window-close-symbolic
+                                                set_label: &tr!("kill-game-process")
+                                            },
+
+                                            #[watch]
+                                            set_sensitive: !model.disabled_kill_game_button,
+
+                                            set_css_classes: &["error", "pill"],
 
-                                        set_hexpand: false,
-                                        set_width_request: 200,
+                                            set_hexpand: false,
+                                            set_width_request: 200,
 
-                                        connect_clicked[sender] => move |_| {
-                                            sender.input(AppMsg::DisableKillGameButton(true));
+                                            connect_clicked[sender] => move |_| {
+                                                sender.input(AppMsg::DisableKillGameButton(true));
 
-                                            std::thread::spawn(clone!(
-                                                #[strong]
-                                                sender,
+                                                std::thread::spawn(clone!(
+                                                    #[strong]
+                                                    sender,
 
-                                                move || {
-                                                    std::thread::sleep(std::time::Duration::from_secs(3));
+                                                    move || {
+                                                        std::thread::sleep(std::time::Duration::from_secs(3));
 
-                                                    sender.input(AppMsg::DisableKillGameButton(false));
+                                                        sender.input(AppMsg::DisableKillGameButton(false));
+                                                    }
+                                                ));
+
+                                                let result = std::process::Command::new("pkill")
+                                                    .arg("-f") // full text search
+                                                    .arg("-i") // case-insensitive
+                                                    .arg("Client-Win64-Sh")
+                                                    .spawn();
+
+                                                if let Err(err) = result {
+                                                    sender.input(AppMsg::Toast {
+                                                        title: tr!("kill-game-process-failed"),
+                                                        description: Some(err.to_string())
+                                                    });
                                                 }
-                                            ));
-
-                                            let result = std::process::Command::new("pkill")
-                                                .arg("-f") // full text search
-                                                .arg("-i") // case-insensitive
-                                                .arg("Client-Win64-Sh")
-                                                .spawn();
-
-                                            if let Err(err) = result {
-                                                sender.input(AppMsg::Toast {
-                                                    title: tr!("kill-game-process-failed"),
-                                                    description: Some(err.to_string())
-                                                });
-                                            }
 
-                                            // Old warning message which I don't really understand now:
-                                            //
-                                            // Doesn't work on all the systems
-                                            // e.g. won't work if you didn't install wine system-wide
-                                            // there's some reasons for it
-                                            //
-                                            // UPD: I've tried this, and the problem is that it's completely pointless
-                                            //      For whatever reason it just doesn't work
-
-                                            // match Config::get() {
-                                            //     Ok(config) => {
-                                            //         match config.get_selected_wine() {
-                                            //             Ok(Some(version)) => {
-                                            //                 let result = version
-                                            //                     .to_wine(&config.components.path, Some(&config.game.wine.builds.join(&version.name)))
-                                            //                     .with_prefix(config.get_wine_prefix_path())
-                                            //                     .stop_processes(true);
-
-                                            //                 dbg!(String::from_utf8_lossy(&result.as_ref().ok().unwrap().stdout));
-                                            //                 dbg!(String::from_utf8_lossy(&result.as_ref().ok().unwrap().stderr));
-
-                                            //                 if let Err(err) = result {
-                                            //                     sender.input(AppMsg::Toast {
-                                            //                         title: tr!("kill-game-process-failed"),
-                                            //                         description: Some(err.to_string())
-                                            //                     });
-                                            //                 }
-                                            //             }
-
-                                            //             Ok(None) => {
-                                            //                 sender.input(AppMsg::Toast {
-                                            //                     title: tr!("failed-get-selected-wine"),
-                                            //                     description: None
-                                            //                 });
-                                            //             }
-
-                                            //             Err(err) => {
-                                            //                 sender.input(AppMsg::Toast {
-                                            //                     title: tr!("failed-get-selected-wine"),
-                                            //                     description: Some(err.to_string())
-                                            //                 });
-                                            //             }
-                                            //         }
-                                            //     }
-
-                                            //     Err(err) => {
-                                            //         sender.input(AppMsg::Toast {
-                                            //             title: tr!("config-file-opening-error"),
-                                            //             description: Some(err.to_string())
-                                            //         });
-                                            //     }
-                                            // }
+                                                // Old warning message which I don't really understand now:
+                                                //
+                                                // Doesn't work on all the systems
+                                                // e.g. won't work if you didn't install wine system-wide
+                                                // there's some reasons for it
+                                                //
+                                                // UPD: I've tried this, and the problem is that it's completely pointless
+                                                //      For whatever reason it just doesn't work
+
+                                                // match Config::get() {
+                                                //     Ok(config) => {
+                                                //         match config.get_selected_wine() {
+                                                //             Ok(Some(version)) => {
+                                                //                 let result = version
+                                                //                     .to_wine(&config.components.path, Some(&config.game.wine.builds.join(&version.name)))
+                                                //                     .with_prefix(config.get_wine_prefix_path())
+                                                //                     .stop_processes(true);
+
+                                                //                 dbg!(String::from_utf8_lossy(&result.as_ref().ok().unwrap().stdout));
+                                                //                 dbg!(String::from_utf8_lossy(&result.as_ref().ok().unwrap().stderr));
+
+                                                //                 if let Err(err) = result {
+                                                //                     sender.input(AppMsg::Toast {
+                                                //                         title: tr!("kill-game-process-failed"),
+                                                //                         description: Some(err.to_string())
+                                                //                     });
+                                                //                 }
+                                                //             }
+
+                                                //             Ok(None) => {
+                                                //                 sender.input(AppMsg::Toast {
+                                                //                     title: tr!("failed-get-selected-wine"),
+                                                //                     description: None
+                                                //                 });
+                                                //             }
+
+                                                //             Err(err) => {
+                                                //                 sender.input(AppMsg::Toast {
+                                                //                     title: tr!("failed-get-selected-wine"),
+                                                //                     description: Some(err.to_string())
+                                                //                 });
+                                                //             }
+                                                //         }
+                                                //     }
+
+                                                //     Err(err) => {
+                                                //         sender.input(AppMsg::Toast {
+                                                //             title: tr!("config-file-opening-error"),
+                                                //             description: Some(err.to_string())
+                                                //         });
+                                                //     }
+                                                // }
+                                            }
                                         }
-                                    }
-                                },
+                                    },
 
-                                adw::Bin {
-                                    set_css_classes: &["background", "round-bin"],
+                                    adw::Bin {
+                                        set_css_classes: &["background", "round-bin"],
 
-                                    gtk::Button {
-                                        #[watch]
-                                        set_sensitive: !model.disabled_buttons,
+                                        gtk::Button {
+                                            #[watch]
+                                            set_sensitive: !model.disabled_buttons,
 
-                                        set_width_request: 44,
+                                            set_width_request: 44,
 
-                                        add_css_class: "circular",
-                                        set_icon_name: "emblem-system-symbolic",
+                                            add_css_class: "circular",
+                                            set_icon_name: "emblem-system-symbolic",
 
-                                        connect_clicked => AppMsg::OpenPreferences
+                                            connect_clicked => AppMsg::OpenPreferences
+                                        }
                                     }
                                 }
                             }
@@ -551,6 +592,8 @@ impl SimpleComponent for App {
                 .detach(),
 
             toast_overlay: adw::ToastOverlay::new(),
+            background_video: gtk::Picture::new(),
+            video: None,
 
             loading: Some(None),
             style: CONFIG.launcher.style,
@@ -566,9 +609,13 @@ impl SimpleComponent for App {
         model.progress_bar.widget().set_width_request(360);
 
         let toast_overlay = &model.toast_overlay;
+        let background_video = &model.background_video;
 
         let widgets = view_output!();
 
+        // Controls define the window size, not the background video
+        widgets.background_overlay.set_measure_overlay(&widgets.controls, true);
+
         let about_dialog_broker: MessageBroker<AboutDialogMsg> = MessageBroker::new();
 
         unsafe {
@@ -667,24 +714,59 @@ impl SimpleComponent for App {
 
         tracing::info!("Main window initialized");
 
-        let download_picture = model.style == LauncherStyle::Classic && !KEEP_BACKGROUND_FILE.exists();
+        // Game files can't be touched until interrupted migration is finished
+        match move_files::Journal::get() {
+            Ok(Some(_)) => {
+                tracing::warn!("Found interrupted installation migration");
 
-        // Initialize some heavy tasks
-        std::thread::spawn(move || {
-            tracing::info!("Initializing heavy tasks");
+                let dialog = adw::MessageDialog::new(
+                    Some(&widgets.main_window),
+                    Some(&tr!("migration-interrupted")),
+                    Some(&tr!("migration-interrupted-description"))
+                );
 
-            let mut tasks = Vec::new();
+                dialog.add_response("revert", &tr!("revert"));
+                dialog.add_response("resume", &tr!("resume"));
 
-            // Download background picture if needed
+                dialog.set_response_appearance("resume", adw::ResponseAppearance::Suggested);
+                dialog.set_close_response("resume");
 
-            if download_picture {
-                tasks.push(std::thread::spawn(clone!(
+                dialog.connect_response(None, clone!(
                     #[strong]
                     sender,
 
-                    move || {
-                        if let Err(err) = crate::background::download_background() {
-                            tracing::error!("Failed to download background picture: {err}");
+                    move |_, response| {
+                        sender.input(AppMsg::FinishMigration {
+                            revert: response == "revert"
+                        });
+                    }
+                ));
+
+                gtk::glib::idle_add_local_once(move || dialog.present());
+
+                return ComponentParts { model, widgets };
+            }
+
+            Ok(None) => (),
+
+            Err(err) => tracing::error!("Failed to read installation migration journal: {err}")
+        }
+
+        // Update background picture if needed
+        // Launcher doesn't wait for it and reloads the picture once it's ready
+        if model.style == LauncherStyle::Classic {
+            let size = crate::background::target_size();
+
+            std::thread::spawn(clone!(
+                #[strong]
+                sender,
+
+                move || {
+                    match crate::background::update_background(true, size) {
+                        Ok(_) => sender.input(AppMsg::ReloadBackground),
+
+                        Err(err) => {
+                            tracing::error!("Failed to update background picture: {err}");
 
                             sender.input(AppMsg::Toast {
                                 title: tr!("background-downloading-failed"),
@@ -692,8 +774,17 @@ impl SimpleComponent for App {
                             });
                         }
                     }
-                )));
-            }
+
+                    sender.input(AppMsg::UpdateBackgroundVideo);
+                }
+            ));
+        }
+
+        // Initialize some heavy tasks
+        std::thread::spawn(move || {
+            tracing::info!("Initializing heavy tasks");
+
+            let mut tasks = Vec::new();
 
             // Update components index
 
@@ -709,7 +800,7 @@ impl SimpleComponent for App {
 
                         Ok(None) => {
                             for host in &CONFIG.components.servers {
-                                match components.sync(host) {
+                                match crate::components_index::sync(&components, host) {
                                     Ok(changes) => {
                                         sender.input(AppMsg::Toast {
                                             title: tr!("components-index-updated"),
@@ -823,6 +914,27 @@ impl SimpleComponent for App {
             crate::READY.store(true, Ordering::Relaxed);
 
             tracing::info!("App is ready");
+
+            // Missing prefix is handled by the launcher state
+            match crate::prefix_health::check() {
+                Ok(issues) if !issues.is_empty() && !issues.contains(&crate::prefix_health::HealthIssue::PrefixNotExists) => {
+                    sender.input(AppMsg::Toast {
+                        title: tr!("wine-prefix-issues-found"),
+                        description: Some(issues.iter()
+                            .map(|issue| format!("- {issue}"))
+                            .collect::<Vec<_>>()
+                            .join("\n"))
+                    });
+                }
+
+                Ok(_) => (),
+
+                Err(err) => tracing::error!("Failed to check wine prefix: {err}")
+            }
+
+            // Check for new recommended components builds
+            // after the components index was synced
+            upgrade_components::upgrade_components(sender);
         });
 
         ComponentParts { model, widgets }
@@ -864,6 +976,8 @@ impl SimpleComponent for App {
                     }
                 );
 
+                let custom_wine = crate::custom_wine::register();
+
                 let state = match LauncherState::get_from_config(updater) {
                     Ok(state) => Some(state),
                     Err(err) => {
@@ -875,6 +989,8 @@ impl SimpleComponent for App {
                     }
                 };
 
+                drop(custom_wine);
+
                 sender.input(AppMsg::SetLauncherState(state.clone()));
 
                 if show_status_page {
@@ -915,6 +1031,8 @@ impl SimpleComponent for App {
 
             AppMsg::SetLauncherStyle(style) => {
                 self.style = style;
+
+                sender.input(AppMsg::UpdateBackgroundVideo);
             }
 
             AppMsg::SetDownloading(state) => {
@@ -927,12 +1045,86 @@ impl SimpleComponent for App {
 
             AppMsg::SetKillGameButton(state) => {
                 self.kill_game_button = state;
+
+                // Don't waste resources on the video while the game is running
+                if let Some(video) = &self.video {
+                    video.set_playing(!state);
+                }
             }
 
             AppMsg::DisableKillGameButton(state) => {
                 self.disabled_kill_game_button = state;
             }
 
+            AppMsg::ReloadBackground => crate::background::reload_css(),
+
+            AppMsg::UpdateBackgroundVideo => {
+                if self.style != LauncherStyle::Classic {
+                    sender.input(AppMsg::SetBackgroundVideo(None));
+
+                    return;
+                }
+
+                std::thread::spawn(clone!(
+                    #[strong]
+                    sender,
+
+                    move || {
+                        match crate::background::prepare_video() {
+                            Ok(video) => sender.input(AppMsg::SetBackgroundVideo(video)),
+
+                            Err(err) => {
+                                tracing::error!("Failed to prepare background video: {err}");
+
+                                sender.input(AppMsg::SetBackgroundVideo(None));
+
+                                sender.input(AppMsg::Toast {
+                                    title: tr!("background-video-failed"),
+                                    description: Some(err.to_string())
+                                });
+                            }
+                        }
+                    }
+                ));
+            }
+
+            AppMsg::SetBackgroundVideo(path) => {
+                if let Some(video) = self.video.take() {
+                    video.set_playing(false);
+                }
+
+                self.background_video.set_paintable(None::<&gtk::gdk::Paintable>);
+
+                if let Some(path) = path {
+                    tracing::debug!("Playing background video: {:?}", path);
+
+                    let video = gtk::MediaFile::for_filename(path);
+
+                    video.set_loop(true);
+                    video.set_muted(true);
+
+                    // Fallback to the static picture if the video can't be played
+                    video.connect_error_notify(clone!(
+                        #[strong]
+                        sender,
+
+                        move |video| {
+                            if let Some(err) = video.error() {
+                                tracing::error!("Failed to play background video: {err}");
+
+                                sender.input(AppMsg::SetBackgroundVideo(None));
+                            }
+                        }
+                    ));
+
+                    video.set_playing(!self.kill_game_button);
+
+                    self.background_video.set_paintable(Some(&video));
+
+                    self.video = Some(video);
+                }
+            }
+
             AppMsg::OpenPreferences => unsafe {
                 PREFERENCES_WINDOW.as_ref().unwrap_unchecked().widget().present();
             }
@@ -965,13 +1157,90 @@ impl SimpleComponent for App {
             }
 
             AppMsg::HideWindow => unsafe {
+                if let Some(video) = &self.video {
+                    video.set_playing(false);
+                }
+
                 MAIN_WINDOW.as_ref().unwrap_unchecked().set_visible(false);
             }
 
             AppMsg::ShowWindow => unsafe {
+                if let Some(video) = &self.video {
+                    video.set_playing(!self.kill_game_button);
+                }
+
                 MAIN_WINDOW.as_ref().unwrap_unchecked().present();
             }
 
+            AppMsg::FinishMigration { revert } => {
+                self.loading = Some(Some(tr!("migrating-folders")));
+
+                std::thread::spawn(clone!(
+                    #[strong]
+                    sender,
+
+                    move || {
+                        let updater = clone!(
+                            #[strong]
+                            sender,
+
+                            move |curr: u64, total: u64| {
+                                let progress = if total > 0 {
+                                    curr as f64 / total as f64 * 100.0
+                                } else {
+                                    100.0
+                                };
+
+                                sender.input(AppMsg::SetLoadingStatus(Some(Some(format!("{}: {progress:.2}%", tr!("migrating-folders"))))));
+                            }
+                        );
+
+                        let result = move_files::Journal::get().and_then(|journal| match journal {
+                            Some(journal) if revert => journal.revert(updater),
+
+                            Some(journal) => journal.check_free_space()
+                                .and_then(|_| journal.migrate(&AtomicBool::new(false), updater)),
+
+                            None => Ok(())
+                        });
+
+                        match result {
+                            Ok(_) => sender.input(AppMsg::RestartLauncher),
+
+                            Err(err) => {
+                                tracing::error!("Failed to finish installation migration: {err}");
+
+                                sender.input(AppMsg::SetLoadingStatus(Some(Some(err.to_string()))));
+
+                                sender.input(AppMsg::Toast {
+                                    title: if revert {
+                                        tr!("migration-revert-failed")
+                                    } else {
+                                        tr!("migration-failed")
+                                    },
+                                    description: Some(err.to_string())
+                                });
+                            }
+                        }
+                    }
+                ));
+            }
+
+            AppMsg::RestartLauncher => {
+                let result = std::env::current_exe()
+                    .and_then(|launcher| std::process::Command::new(launcher).spawn());
+
+                match result {
+                    Ok(_) => relm4::main_application().quit(),
+
+                    Err(err) => {
+                        tracing::error!("Failed to restart launcher: {err}");
+
+                        self.toast(tr!("launcher-restart-failed"), Some(err.to_string()));
+                    }
+                }
+            }
+
             AppMsg::Toast { title, description } => self.toast(title, description)
         }
     }
@@ -0,0 +1,79 @@
+use relm4::prelude::*;
+
+use crate::settings::prelude::*;
+use crate::*;
+
+use super::{App, AppMsg};
+
+/// Check for new recommended wine and DXVK builds and handle them
+/// according to the components upgrade policy
+pub fn upgrade_components(sender: ComponentSender<App>) {
+    let policy = Settings::get()
+        .map(|settings| settings.components_upgrade.policy)
+        .unwrap_or_default();
+
+    if policy == UpgradePolicy::Disabled {
+        return;
+    }
+
+    std::thread::spawn(move || {
+        let upgrades = match crate::upgrade::find() {
+            Ok(upgrades) if !upgrades.is_empty() => upgrades,
+            Ok(_) => return,
+
+            Err(err) => {
+                tracing::error!("Failed to find components upgrades: {err}");
+
+                return;
+            }
+        };
+
+        let builds = upgrades.titles()
+            .into_iter()
+            .map(|title| format!("- {title}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        if policy == UpgradePolicy::Notify {
+            sender.input(AppMsg::Toast {
+                title: tr!("components-upgrade-available"),
+                description: Some(builds)
+            });
+
+            return;
+        }
+
+        sender.input(AppMsg::DisableButtons(true));
+
+        if let Err(err) = crate::snapshots::create_automatic(upgrades.titles().join(", ")) {
+            sender.input(AppMsg::Toast {
+                title: tr!("automatic-prefix-snapshot-failed"),
+                description: Some(err.to_string())
+            });
+        }
+
+        match crate::upgrade::upgrade(&upgrades) {
+            Ok(_) => {
+                sender.input(AppMsg::Toast {
+                    title: tr!("components-upgraded"),
+                    description: Some(builds)
+                });
+            }
+
+            Err(err) => {
+                tracing::error!("Failed to upgrade components: {err}");
+
+                sender.input(AppMsg::Toast {
+                    title: tr!("components-upgrade-failed"),
+                    description: Some(err.to_string())
+                });
+            }
+        }
+
+        sender.input(AppMsg::DisableButtons(false));
+        sender.input(AppMsg::UpdateLauncherState {
+            perform_on_download_needed: false,
+            show_status_page: false
+        });
+    });
+}
@@ -1,19 +1,26 @@
 use relm4::prelude::*;
 use gtk::prelude::*;
+use adw::prelude::*;
 
 use crate::tr;
 
+use super::first_run::main::FirstRunAppMsg;
 use super::first_run::default_paths::DefaultPathsApp;
+use super::preferences::general::GeneralAppMsg;
 
 pub struct MigrateInstallationApp {
     default_paths: AsyncController<DefaultPathsApp>,
+
+    toast_overlay: adw::ToastOverlay
 }
 
 #[relm4::component(pub)]
 impl SimpleComponent for MigrateInstallationApp {
     type Init = ();
-    type Input = ();
-    type Output = ();
+
+    /// Default paths page sends first run messages
+    type Input = FirstRunAppMsg;
+    type Output = GeneralAppMsg;
 
     view! {
         adw::Window {
@@ -24,29 +31,87 @@ impl SimpleComponent for MigrateInstallationApp {
             #[watch]
             set_title: Some(&tr!("migrate-installation")),
 
-            gtk::Box {
-                set_orientation: gtk::Orientation::Vertical,
+            #[local_ref]
+            toast_overlay -> adw::ToastOverlay {
+                gtk::Box {
+                    set_orientation: gtk::Orientation::Vertical,
 
-                adw::HeaderBar {
-                    add_css_class: "flat"
-                },
+                    adw::HeaderBar {
+                        add_css_class: "flat"
+                    },
 
-                append = model.default_paths.widget(),
+                    append = model.default_paths.widget(),
+                }
             }
         }
     }
 
-    fn init(_init: Self::Init, root: Self::Root, _sender: ComponentSender<Self>) -> ComponentParts<Self> {
+    fn init(_init: Self::Init, root: Self::Root, sender: ComponentSender<Self>) -> ComponentParts<Self> {
         tracing::info!("Initializing migration window");
 
         let model = Self {
             default_paths: DefaultPathsApp::builder()
                 .launch(true)
-                .detach()
+                .forward(sender.input_sender(), std::convert::identity),
+
+            toast_overlay: adw::ToastOverlay::new()
         };
 
+        let toast_overlay = &model.toast_overlay;
+
         let widgets = view_output!();
 
         ComponentParts { model, widgets }
     }
+
+    fn update(&mut self, msg: Self::Input, sender: ComponentSender<Self>) {
+        tracing::debug!("Called migration window event: {:?}", msg);
+
+        match msg {
+            #[allow(unused_must_use)]
+            FirstRunAppMsg::MigrationFinished(status) => {
+                if let Some(window) = self.toast_overlay.root().and_downcast::<adw::Window>() {
+                    window.close();
+                }
+
+                sender.output(GeneralAppMsg::MigrationFinished(status));
+            }
+
+            FirstRunAppMsg::Toast { title, description } => {
+                let toast = adw::Toast::new(&title);
+
+                toast.set_timeout(4);
+
+                if let Some(description) = description {
+                    toast.set_button_label(Some(&tr!("details")));
+
+                    let dialog = adw::MessageDialog::new(
+                        self.toast_overlay.root().and_downcast::<gtk::Window>().as_ref(),
+                        Some(&title),
+                        Some(&description)
+                    );
+
+                    dialog.add_response("close", &tr!("close", { "form" = "noun" }));
+                    dialog.add_response("save", &tr!("save"));
+
+                    dialog.set_response_appearance("save", adw::ResponseAppearance::Suggested);
+
+                    dialog.connect_response(Some("save"), |_, _| {
+                        if let Err(err) = open::that(crate::DEBUG_FILE.as_os_str()) {
+                            tracing::error!("Failed to open debug file: {err}");
+                        }
+                    });
+
+                    toast.connect_button_clicked(move |_| {
+                        dialog.present();
+                    });
+                }
+
+                self.toast_overlay.add_toast(toast);
+            }
+
+            // Other first run messages are not used in the migration mode
+            _ => ()
+        }
+    }
 }
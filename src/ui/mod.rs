@@ -4,3 +4,4 @@ pub mod preferences;
 pub mod components;
 pub mod first_run;
 pub mod migrate_installation;
+pub mod import_installation;
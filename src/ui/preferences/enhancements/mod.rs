@@ -26,6 +26,7 @@ pub mod environment;
 use sandbox::*;
 use environment::*;
 
+use crate::settings::prelude::*;
 use crate::*;
 
 use super::gamescope::*;
@@ -105,6 +106,9 @@ pub enum EnhancementsAppMsg {
     OpenSandboxSettingsPage,
     OpenEnvironmentSettingsPage,
 
+    /// Update DirectX version row after VKD3D-Proton was changed on the components page
+    UpdateDirectX,
+
     Toast {
         title: String,
         description: Option<String>
@@ -351,41 +355,29 @@ impl SimpleAsyncComponent for EnhancementsApp {
             add = &adw::PreferencesGroup {
                 set_title: &tr!("game"),
 
-                adw::ActionRow {
-                    set_title: &tr!("run-with-dx11"),
-                    set_subtitle: &tr!("run-with-dx11-description"),
-                    add_suffix = &gtk::Switch {
-                        set_valign: gtk::Align::Center,
-
-                        set_active: CONFIG.game.enhancements.dx11,
-
-                        connect_state_notify => |switch| {
-                            if is_ready() {
-                                if let Ok(mut config) = Config::get() {
-                                    config.game.enhancements.dx11 = switch.is_active();
+                adw::ComboRow {
+                    set_title: &tr!("directx-version"),
 
-                                    Config::update(config);
-                                }
-                            }
-                        }
-                    }
-                },
+                    #[watch]
+                    set_subtitle: &match Settings::get().ok().and_then(|settings| settings.directx.vkd3d) {
+                        Some(vkd3d) => tr!("directx-version-vkd3d", { "version" = vkd3d }),
+                        None => tr!("directx-version-description")
+                    },
 
-                adw::ActionRow {
-                    set_title: &tr!("fix-launch-dialog"),
-                    set_subtitle: &tr!("fix-launch-dialog-description"),
-                    add_suffix = &gtk::Switch {
-                        set_valign: gtk::Align::Center,
+                    #[wrap(Some)]
+                    set_model = &gtk::StringList::new(&[
+                        "DirectX 11",
+                        "DirectX 12"
+                    ]),
 
-                        set_active: CONFIG.game.enhancements.fix_launch_dialog,
+                    set_selected: if CONFIG.game.enhancements.dx11 { 0 } else { 1 },
 
-                        connect_state_notify => |switch| {
-                            if is_ready() {
-                                if let Ok(mut config) = Config::get() {
-                                    config.game.enhancements.fix_launch_dialog = switch.is_active();
+                    connect_selected_notify => |row| {
+                        if is_ready() {
+                            if let Ok(mut config) = Config::get() {
+                                config.game.enhancements.dx11 = row.selected() == 0;
 
-                                    Config::update(config);
-                                }
+                                Config::update(config);
                             }
                         }
                     }
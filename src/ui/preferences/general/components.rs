@@ -2,29 +2,55 @@ use relm4::prelude::*;
 use gtk::prelude::*;
 use adw::prelude::*;
 
-use anime_launcher_sdk::wincompatlib::prelude::*;
-
 use anime_launcher_sdk::components::*;
-use anime_launcher_sdk::components::wine::UnifiedWine;
 
 use super::GeneralAppMsg;
 
 use crate::ui::components::*;
+use crate::settings::prelude::*;
+use crate::checksums::{self, ComponentKind};
+use crate::disk_usage::{self, DiskUsage, TempLeftover};
+use crate::directx;
 use crate::*;
 
 pub struct ComponentsPage {
     wine_components: AsyncController<ComponentsList<ComponentsPageMsg>>,
     dxvk_components: AsyncController<ComponentsList<ComponentsPageMsg>>,
+    vkd3d_components: AsyncController<ComponentsList<ComponentsPageMsg>>,
+    nvapi_components: AsyncController<ComponentsList<ComponentsPageMsg>>,
+
+    custom_wine_group: adw::PreferencesGroup,
+    custom_wine_rows: Vec<adw::ActionRow>,
+    processing_custom_wine: bool,
+
+    disk_usage: DiskUsage,
+    disk_usage_group: adw::PreferencesGroup,
+    disk_usage_rows: Vec<adw::ActionRow>,
+    processing_disk_usage: bool,
+
+    upgrade_policy: UpgradePolicy,
+    can_rollback: bool,
+    processing_rollback: bool,
 
     downloaded_wine_versions: Vec<(wine::Version, wine::Features)>,
     downloaded_dxvk_versions: Vec<dxvk::Version>,
+    downloaded_vkd3d_versions: Vec<directx::Version>,
+    downloaded_nvapi_versions: Vec<directx::Version>,
     allow_dxvk_selection: bool,
 
     selected_wine_version: u32,
     selected_dxvk_version: u32,
 
+    /// Index in the downloaded versions list shifted by one, 0 is none
+    selected_vkd3d_version: u32,
+    selected_nvapi_version: u32,
+
     selecting_wine_version: bool,
-    selecting_dxvk_version: bool
+    selecting_dxvk_version: bool,
+    selecting_vkd3d_version: bool,
+    selecting_nvapi_version: bool,
+
+    processing_bundle: bool
 }
 
 #[derive(Debug, Clone)]
@@ -36,10 +62,167 @@ pub enum ComponentsPageMsg {
     UpdateDownloadedDxvk,
 
     SelectWine(usize),
+
+    AddCustomWine(String),
+    RemoveCustomWine(String),
+
+    /// Toast title or error message
+    CustomWineFinished(Result<String, String>),
+
     SelectDxvk(usize),
 
     ResetWineSelection(usize),
-    ResetDxvkSelection(usize)
+    ResetDxvkSelection(usize),
+
+    UpdateDownloadedDirectX(ComponentKind),
+
+    /// Install downloaded VKD3D-Proton or DXVK-NVAPI version
+    /// with the given index shifted by one, 0 removes installed one
+    SelectDirectX(ComponentKind, usize),
+
+    ResetDirectXSelection(ComponentKind),
+
+    UpdateDiskUsage,
+    DiskUsageUpdated(DiskUsage),
+    RemoveUnusedBuilds,
+    RemoveTempLeftover(TempLeftover),
+
+    /// Toast title or error message
+    CleanupFinished(Result<String, String>),
+
+    SetUpgradePolicy(UpgradePolicy),
+    Rollback,
+
+    /// Toast title or error message
+    RollbackFinished(Result<String, String>),
+
+    ExportBundle,
+    ImportBundle,
+
+    /// Toast title or error message
+    BundleFinished(Result<String, String>),
+
+    Toast {
+        title: String,
+        description: Option<String>
+    }
+}
+
+impl ComponentsPage {
+    /// Make automatic wine prefix snapshot before changing the component
+    ///
+    /// Must be called from a worker thread. Failed snapshot is shown in a toast
+    /// and doesn't stop the component's selection
+    fn create_automatic_snapshot(component: impl AsRef<str>, sender: &AsyncComponentSender<Self>) {
+        if let Err(err) = crate::snapshots::create_automatic(component) {
+            sender.output(GeneralAppMsg::Toast {
+                title: tr!("automatic-prefix-snapshot-failed"),
+                description: Some(err.to_string())
+            }).unwrap();
+        }
+    }
+
+    /// Build VKD3D-Proton or DXVK-NVAPI versions list with "none" as the first item
+    fn directx_model(versions: &[directx::Version]) -> gtk::StringList {
+        let model = gtk::StringList::new(&[&tr!("none")]);
+
+        for version in versions {
+            model.append(&version.title);
+        }
+
+        model
+    }
+
+    fn update_custom_wine_rows(&mut self, sender: &AsyncComponentSender<Self>) {
+        for row in self.custom_wine_rows.drain(..) {
+            self.custom_wine_group.remove(&row);
+        }
+
+        let custom_wine = Settings::get()
+            .map(|settings| settings.custom_wine)
+            .unwrap_or_default();
+
+        for wine in custom_wine {
+            let row = adw::ActionRow::new();
+
+            row.set_title(&wine.name);
+            row.set_subtitle(&format!("{}\n{}", wine.version, wine.path.to_string_lossy()));
+
+            let button = gtk::Button::new();
+
+            button.set_icon_name("user-trash-symbolic");
+            button.set_tooltip_text(Some(&tr!("custom-wine-remove")));
+            button.set_valign(gtk::Align::Center);
+            button.add_css_class("flat");
+
+            let sender = sender.clone();
+
+            button.connect_clicked(move |_| {
+                sender.input(ComponentsPageMsg::RemoveCustomWine(wine.name.clone()));
+            });
+
+            row.add_suffix(&button);
+
+            self.custom_wine_group.add(&row);
+            self.custom_wine_rows.push(row);
+        }
+    }
+
+    fn update_disk_usage_rows(&mut self, sender: &AsyncComponentSender<Self>) {
+        for row in self.disk_usage_rows.drain(..) {
+            self.disk_usage_group.remove(&row);
+        }
+
+        for build in &self.disk_usage.builds {
+            let row = adw::ActionRow::new();
+
+            let kind = build.kind.title();
+
+            let size = if build.custom {
+                tr!("custom-build")
+            } else {
+                prettify_bytes(build.size)
+            };
+
+            row.set_title(&build.name);
+
+            row.set_subtitle(&if build.selected {
+                format!("{kind} · {size} · {}", tr!("selected-build"))
+            } else if build.rollback {
+                format!("{kind} · {size} · {}", tr!("rollback-build"))
+            } else {
+                format!("{kind} · {size}")
+            });
+
+            self.disk_usage_group.add(&row);
+            self.disk_usage_rows.push(row);
+        }
+
+        for leftover in &self.disk_usage.leftovers {
+            let row = adw::ActionRow::new();
+
+            row.set_title(&leftover.path.file_name().unwrap_or_default().to_string_lossy());
+            row.set_subtitle(&format!("{} · {}", tr!("temp-leftover"), prettify_bytes(leftover.size)));
+
+            let button = gtk::Button::new();
+
+            button.set_icon_name("user-trash-symbolic");
+            button.set_valign(gtk::Align::Center);
+            button.add_css_class("flat");
+
+            let sender = sender.clone();
+            let leftover = leftover.clone();
+
+            button.connect_clicked(move |_| {
+                sender.input(ComponentsPageMsg::RemoveTempLeftover(leftover.clone()));
+            });
+
+            row.add_suffix(&button);
+
+            self.disk_usage_group.add(&row);
+            self.disk_usage_rows.push(row);
+        }
+    }
 }
 
 #[relm4::component(async, pub)]
@@ -116,6 +299,33 @@ impl SimpleAsyncComponent for ComponentsPage {
                         add = model.wine_components.widget(),
                     },
 
+                    #[local_ref]
+                    add = custom_wine_group -> adw::PreferencesGroup {
+                        set_title: &tr!("custom-wine"),
+                        set_description: Some(&tr!("custom-wine-description")),
+
+                        #[watch]
+                        set_sensitive: !model.processing_custom_wine,
+
+                        #[wrap(Some)]
+                        set_header_suffix = &gtk::Spinner {
+                            #[watch]
+                            set_spinning: model.processing_custom_wine,
+
+                            #[watch]
+                            set_visible: model.processing_custom_wine
+                        },
+
+                        adw::EntryRow {
+                            set_title: &tr!("custom-wine-name"),
+                            set_show_apply_button: true,
+
+                            connect_apply[sender] => move |row| {
+                                sender.input(ComponentsPageMsg::AddCustomWine(row.text().to_string()));
+                            }
+                        }
+                    },
+
                     add = &adw::PreferencesGroup {
                         set_title: &tr!("wine-options"),
 
@@ -230,6 +440,220 @@ impl SimpleAsyncComponent for ComponentsPage {
 
                         add = model.dxvk_components.widget(),
                     },
+
+                    add = &adw::PreferencesGroup {
+                        set_title: &tr!("vkd3d-version"),
+                        set_description: Some(&tr!("vkd3d-version-description")),
+
+                        adw::ComboRow {
+                            set_title: &tr!("selected-version"),
+
+                            #[watch]
+                            #[block_signal(vkd3d_selected_notify)]
+                            set_model: Some(&ComponentsPage::directx_model(&model.downloaded_vkd3d_versions)),
+
+                            #[watch]
+                            #[block_signal(vkd3d_selected_notify)]
+                            set_selected: model.selected_vkd3d_version,
+
+                            #[watch]
+                            set_activatable: !model.selecting_vkd3d_version,
+
+                            connect_selected_notify[sender] => move |row| {
+                                if is_ready() {
+                                    sender.input(ComponentsPageMsg::SelectDirectX(ComponentKind::Vkd3d, row.selected() as usize));
+                                }
+                            } @vkd3d_selected_notify,
+
+                            add_suffix = &gtk::Spinner {
+                                set_spinning: true,
+
+                                #[watch]
+                                set_visible: model.selecting_vkd3d_version
+                            }
+                        }
+                    },
+
+                    add = &adw::PreferencesGroup {
+                        add = model.vkd3d_components.widget(),
+                    },
+
+                    add = &adw::PreferencesGroup {
+                        set_title: &tr!("nvapi-version"),
+                        set_description: Some(&tr!("nvapi-version-description")),
+
+                        #[watch]
+                        set_sensitive: model.allow_dxvk_selection,
+
+                        adw::ComboRow {
+                            set_title: &tr!("selected-version"),
+
+                            #[watch]
+                            #[block_signal(nvapi_selected_notify)]
+                            set_model: Some(&ComponentsPage::directx_model(&model.downloaded_nvapi_versions)),
+
+                            #[watch]
+                            #[block_signal(nvapi_selected_notify)]
+                            set_selected: model.selected_nvapi_version,
+
+                            #[watch]
+                            set_activatable: !model.selecting_nvapi_version,
+
+                            connect_selected_notify[sender] => move |row| {
+                                if is_ready() {
+                                    sender.input(ComponentsPageMsg::SelectDirectX(ComponentKind::Nvapi, row.selected() as usize));
+                                }
+                            } @nvapi_selected_notify,
+
+                            add_suffix = &gtk::Spinner {
+                                set_spinning: true,
+
+                                #[watch]
+                                set_visible: model.selecting_nvapi_version
+                            }
+                        }
+                    },
+
+                    add = &adw::PreferencesGroup {
+                        #[watch]
+                        set_sensitive: model.allow_dxvk_selection,
+
+                        add = model.nvapi_components.widget(),
+                    },
+
+                    add = &adw::PreferencesGroup {
+                        set_title: &tr!("components-upgrade"),
+
+                        adw::ComboRow {
+                            set_title: &tr!("components-upgrade-policy"),
+                            set_subtitle: &tr!("components-upgrade-policy-description"),
+
+                            #[wrap(Some)]
+                            set_model = &gtk::StringList::new(&[
+                                &tr!("components-upgrade-disabled"),
+                                &tr!("components-upgrade-notify"),
+                                &tr!("components-upgrade-install")
+                            ]),
+
+                            set_selected: UpgradePolicy::list().iter()
+                                .position(|policy| *policy == model.upgrade_policy)
+                                .unwrap_or_default() as u32,
+
+                            connect_selected_notify[sender] => move |row| {
+                                if is_ready() {
+                                    if let Some(policy) = UpgradePolicy::list().get(row.selected() as usize) {
+                                        sender.input(ComponentsPageMsg::SetUpgradePolicy(*policy));
+                                    }
+                                }
+                            }
+                        },
+
+                        adw::ActionRow {
+                            set_title: &tr!("components-rollback"),
+                            set_subtitle: &tr!("components-rollback-description"),
+
+                            #[watch]
+                            set_visible: model.can_rollback,
+
+                            add_suffix = &gtk::Spinner {
+                                #[watch]
+                                set_spinning: model.processing_rollback,
+
+                                #[watch]
+                                set_visible: model.processing_rollback
+                            },
+
+                            add_suffix = &gtk::Button {
+                                set_icon_name: "edit-undo-symbolic",
+                                add_css_class: "flat",
+                                set_valign: gtk::Align::Center,
+
+                                #[watch]
+                                set_sensitive: !model.processing_rollback,
+
+                                connect_clicked => ComponentsPageMsg::Rollback
+                            }
+                        }
+                    },
+
+                    #[local_ref]
+                    add = disk_usage_group -> adw::PreferencesGroup {
+                        set_title: &tr!("disk-usage"),
+                        set_description: Some(&tr!("disk-usage-description")),
+
+                        #[watch]
+                        set_sensitive: !model.processing_disk_usage,
+
+                        #[wrap(Some)]
+                        set_header_suffix = &gtk::Spinner {
+                            #[watch]
+                            set_spinning: model.processing_disk_usage,
+
+                            #[watch]
+                            set_visible: model.processing_disk_usage
+                        },
+
+                        adw::ActionRow {
+                            set_title: &tr!("remove-unused-builds"),
+
+                            #[watch]
+                            set_subtitle: &tr!("remove-unused-builds-description", {
+                                "size" = prettify_bytes(model.disk_usage.unused_size())
+                            }),
+
+                            add_suffix = &gtk::Button {
+                                set_icon_name: "user-trash-symbolic",
+                                add_css_class: "flat",
+                                set_valign: gtk::Align::Center,
+
+                                #[watch]
+                                set_sensitive: model.disk_usage.unused_size() > 0,
+
+                                connect_clicked => ComponentsPageMsg::RemoveUnusedBuilds
+                            }
+                        }
+                    },
+
+                    add = &adw::PreferencesGroup {
+                        set_title: &tr!("components-bundle"),
+                        set_description: Some(&tr!("components-bundle-description")),
+
+                        #[watch]
+                        set_sensitive: !model.processing_bundle,
+
+                        #[wrap(Some)]
+                        set_header_suffix = &gtk::Spinner {
+                            #[watch]
+                            set_spinning: model.processing_bundle,
+
+                            #[watch]
+                            set_visible: model.processing_bundle
+                        },
+
+                        adw::ActionRow {
+                            set_title: &tr!("export-components-bundle"),
+                            set_subtitle: &tr!("export-components-bundle-description"),
+                            set_activatable: true,
+
+                            add_suffix = &gtk::Image {
+                                set_icon_name: Some("document-save-symbolic")
+                            },
+
+                            connect_activated => ComponentsPageMsg::ExportBundle
+                        },
+
+                        adw::ActionRow {
+                            set_title: &tr!("import-components-bundle"),
+                            set_subtitle: &tr!("import-components-bundle-description"),
+                            set_activatable: true,
+
+                            add_suffix = &gtk::Image {
+                                set_icon_name: Some("document-open-symbolic")
+                            },
+
+                            connect_activated => ComponentsPageMsg::ImportBundle
+                        }
+                    }
                 }
             }
         }
@@ -238,13 +662,21 @@ impl SimpleAsyncComponent for ComponentsPage {
     async fn init(_init: Self::Init, root: Self::Root, sender: AsyncComponentSender<Self>) -> AsyncComponentParts<Self> {
         tracing::info!("Initializing general settings -> components page");
 
-        let model = Self {
+        let config = Config::get().unwrap_or_else(|_| CONFIG.clone());
+
+        let upgrade = Settings::get()
+            .map(|settings| settings.components_upgrade)
+            .unwrap_or_default();
+
+        let mut model = Self {
             wine_components: ComponentsList::builder()
                 .launch(ComponentsListInit {
                     pattern: ComponentsListPattern {
-                        download_folder: CONFIG.game.wine.builds.clone(),
-                        groups: wine::get_groups(&CONFIG.components.path).unwrap_or_default()
+                        download_folder: config.game.wine.builds.clone(),
+                        groups: wine::get_groups(&config.components.path).unwrap_or_default()
                             .into_iter()
+                            // Custom builds can't be downloaded
+                            .filter(|group| group.name != crate::custom_wine::GROUP_NAME)
                             .map(|mut group| {
                                 group.versions = group.versions.into_iter().take(12).collect();
 
@@ -252,6 +684,8 @@ impl SimpleAsyncComponent for ComponentsPage {
                                 let mut recommended = 6;
 
                                 for i in 0..group.versions.len() {
+                                    group.versions[i].checksum = checksums::get(&config.components.path, ComponentKind::Wine, &group.versions[i].name);
+
                                     if recommended > 0 && group.versions[i].recommended {
                                         recommended -= 1;
                                     }
@@ -266,15 +700,16 @@ impl SimpleAsyncComponent for ComponentsPage {
                             .collect()
                     },
                     on_downloaded: Some(ComponentsPageMsg::UpdateDownloadedWine),
-                    on_deleted: Some(ComponentsPageMsg::UpdateDownloadedWine)
+                    on_deleted: Some(ComponentsPageMsg::UpdateDownloadedWine),
+                    on_toast: Some(|title, description| ComponentsPageMsg::Toast { title, description })
                 })
                 .forward(sender.input_sender(), std::convert::identity),
 
             dxvk_components: ComponentsList::builder()
                 .launch(ComponentsListInit {
                     pattern: ComponentsListPattern {
-                        download_folder: CONFIG.game.dxvk.builds.clone(),
-                        groups: dxvk::get_groups(&CONFIG.components.path).unwrap_or_default()
+                        download_folder: config.game.dxvk.builds.clone(),
+                        groups: dxvk::get_groups(&config.components.path).unwrap_or_default()
                             .into_iter()
                             .map(|mut group| {
                                 group.versions = group.versions.into_iter().take(12).collect();
@@ -283,6 +718,8 @@ impl SimpleAsyncComponent for ComponentsPage {
                                 let mut recommended = 6;
 
                                 for i in 0..group.versions.len() {
+                                    group.versions[i].checksum = checksums::get(&config.components.path, ComponentKind::Dxvk, &group.versions[i].name);
+
                                     if recommended > 0 && group.versions[i].recommended {
                                         recommended -= 1;
                                     }
@@ -297,15 +734,81 @@ impl SimpleAsyncComponent for ComponentsPage {
                             .collect()
                     },
                     on_downloaded: Some(ComponentsPageMsg::UpdateDownloadedDxvk),
-                    on_deleted: Some(ComponentsPageMsg::UpdateDownloadedDxvk)
+                    on_deleted: Some(ComponentsPageMsg::UpdateDownloadedDxvk),
+                    on_toast: Some(|title, description| ComponentsPageMsg::Toast { title, description })
+                })
+                .forward(sender.input_sender(), std::convert::identity),
+
+            vkd3d_components: ComponentsList::builder()
+                .launch(ComponentsListInit {
+                    pattern: ComponentsListPattern {
+                        download_folder: directx::builds_folder(ComponentKind::Vkd3d),
+                        groups: directx::get_groups(&config.components.path, ComponentKind::Vkd3d).unwrap_or_default()
+                            .into_iter()
+                            .map(|mut group| {
+                                group.versions = group.versions.into_iter().take(12).collect();
+
+                                let mut group: ComponentsListGroup = group.into();
+
+                                for version in &mut group.versions {
+                                    version.checksum = checksums::get(&config.components.path, ComponentKind::Vkd3d, &version.name);
+                                }
+
+                                group
+                            })
+                            .collect()
+                    },
+                    on_downloaded: Some(ComponentsPageMsg::UpdateDownloadedDirectX(ComponentKind::Vkd3d)),
+                    on_deleted: Some(ComponentsPageMsg::UpdateDownloadedDirectX(ComponentKind::Vkd3d)),
+                    on_toast: Some(|title, description| ComponentsPageMsg::Toast { title, description })
                 })
                 .forward(sender.input_sender(), std::convert::identity),
 
+            nvapi_components: ComponentsList::builder()
+                .launch(ComponentsListInit {
+                    pattern: ComponentsListPattern {
+                        download_folder: directx::builds_folder(ComponentKind::Nvapi),
+                        groups: directx::get_groups(&config.components.path, ComponentKind::Nvapi).unwrap_or_default()
+                            .into_iter()
+                            .map(|mut group| {
+                                group.versions = group.versions.into_iter().take(12).collect();
+
+                                let mut group: ComponentsListGroup = group.into();
+
+                                for version in &mut group.versions {
+                                    version.checksum = checksums::get(&config.components.path, ComponentKind::Nvapi, &version.name);
+                                }
+
+                                group
+                            })
+                            .collect()
+                    },
+                    on_downloaded: Some(ComponentsPageMsg::UpdateDownloadedDirectX(ComponentKind::Nvapi)),
+                    on_deleted: Some(ComponentsPageMsg::UpdateDownloadedDirectX(ComponentKind::Nvapi)),
+                    on_toast: Some(|title, description| ComponentsPageMsg::Toast { title, description })
+                })
+                .forward(sender.input_sender(), std::convert::identity),
+
+            custom_wine_group: adw::PreferencesGroup::new(),
+            custom_wine_rows: Vec::new(),
+            processing_custom_wine: false,
+
+            disk_usage: DiskUsage::default(),
+            disk_usage_group: adw::PreferencesGroup::new(),
+            disk_usage_rows: Vec::new(),
+            processing_disk_usage: false,
+
+            upgrade_policy: upgrade.policy,
+            can_rollback: upgrade.previous_wine.is_some() || upgrade.previous_dxvk.is_some(),
+            processing_rollback: false,
+
             downloaded_wine_versions: vec![],
             downloaded_dxvk_versions: vec![],
+            downloaded_vkd3d_versions: vec![],
+            downloaded_nvapi_versions: vec![],
 
-            allow_dxvk_selection: match &CONFIG.game.wine.selected {
-                Some(version) => match wine::Group::find_in(&CONFIG.components.path, version) {
+            allow_dxvk_selection: match &config.game.wine.selected {
+                Some(version) => match wine::Group::find_in(&config.components.path, version) {
                     Ok(Some(group)) => group.features.unwrap_or_default().need_dxvk,
                     _ => true
                 }
@@ -315,13 +818,27 @@ impl SimpleAsyncComponent for ComponentsPage {
 
             selected_wine_version: 0,
             selected_dxvk_version: 0,
+            selected_vkd3d_version: 0,
+            selected_nvapi_version: 0,
 
             selecting_wine_version: false,
-            selecting_dxvk_version: false
+            selecting_dxvk_version: false,
+            selecting_vkd3d_version: false,
+            selecting_nvapi_version: false,
+
+            processing_bundle: false
         };
 
+        let custom_wine_group = &model.custom_wine_group;
+        let disk_usage_group = &model.disk_usage_group;
+
         let widgets = view_output!();
 
+        model.update_custom_wine_rows(&sender);
+
+        sender.input(ComponentsPageMsg::UpdateDownloadedDirectX(ComponentKind::Vkd3d));
+        sender.input(ComponentsPageMsg::UpdateDownloadedDirectX(ComponentKind::Nvapi));
+
         AsyncComponentParts { model, widgets }
     }
 
@@ -340,7 +857,9 @@ impl SimpleAsyncComponent for ComponentsPage {
             }
 
             ComponentsPageMsg::UpdateDownloadedWine => {
-                self.downloaded_wine_versions = wine::get_downloaded(&CONFIG.components.path, &CONFIG.game.wine.builds)
+                let config = Config::get().unwrap_or_else(|_| CONFIG.clone());
+
+                self.downloaded_wine_versions = wine::get_downloaded(&config.components.path, &config.game.wine.builds)
                     .unwrap_or_default()
                     .into_iter()
                     .flat_map(|group| group.versions.clone().into_iter()
@@ -349,9 +868,16 @@ impl SimpleAsyncComponent for ComponentsPage {
 
                             (version, features)
                         })
-                    ).collect();
+                    )
+                    // Custom builds are stored in the launcher settings
+                    .chain(crate::custom_wine::versions(&config.game.wine.builds)
+                        .into_iter()
+                        .map(|version| (version, wine::Features::default())))
+                    .collect();
 
-                self.selected_wine_version = if let Some(selected) = &CONFIG.game.wine.selected {
+                sender.input(ComponentsPageMsg::UpdateDiskUsage);
+
+                self.selected_wine_version = if let Some(selected) = &config.game.wine.selected {
                     let mut index = 0;
 
                     for (i, (version, _)) in self.downloaded_wine_versions.iter().enumerate() {
@@ -371,13 +897,17 @@ impl SimpleAsyncComponent for ComponentsPage {
             }
 
             ComponentsPageMsg::UpdateDownloadedDxvk => {
-                self.downloaded_dxvk_versions = dxvk::get_downloaded(&CONFIG.components.path, &CONFIG.game.dxvk.builds)
+                let config = Config::get().unwrap_or_else(|_| CONFIG.clone());
+
+                self.downloaded_dxvk_versions = dxvk::get_downloaded(&config.components.path, &config.game.dxvk.builds)
                     .unwrap_or_default()
                     .into_iter()
                     .flat_map(|group| group.versions)
                     .collect();
 
-                self.selected_dxvk_version = if let Ok(Some(selected)) = CONFIG.get_selected_dxvk() {
+                sender.input(ComponentsPageMsg::UpdateDiskUsage);
+
+                self.selected_dxvk_version = if let Ok(Some(selected)) = config.get_selected_dxvk() {
                     let mut index = 0;
 
                     for (i, version) in self.downloaded_dxvk_versions.iter().enumerate() {
@@ -397,34 +927,22 @@ impl SimpleAsyncComponent for ComponentsPage {
             }
 
             ComponentsPageMsg::SelectWine(index) => {
-                if let Ok(mut config) = Config::get() {
+                if let Ok(config) = Config::get() {
                     if let Some((version, features)) = self.downloaded_wine_versions.get(index) {
                         if config.game.wine.selected.as_ref() != Some(&version.title) {
                             self.selecting_wine_version = true;
                             self.allow_dxvk_selection = features.need_dxvk;
 
-                            let wine = version
-                                .to_wine(&config.components.path, Some(&config.game.wine.builds.join(&version.name)))
-                                .with_prefix(&config.game.wine.prefix)
-                                .with_loader(WineLoader::Current)
-                                .with_arch(WineArch::Win64);
-
-                            let wine_name = version.name.to_string();
+                            let version = version.clone();
 
                             std::thread::spawn(move || {
-                                match wine.update_prefix(None::<&str>) {
-                                    Ok(_) => {
-                                        config.game.wine.selected = Some(wine_name); 
-
-                                        Config::update(config);
-                                    }
+                                Self::create_automatic_snapshot(&version.name, &sender);
 
-                                    Err(err) => {
-                                        sender.output(GeneralAppMsg::Toast {
-                                            title: tr!("wine-prefix-update-failed"),
-                                            description: Some(err.to_string())
-                                        }).unwrap();
-                                    }
+                                if let Err(err) = crate::upgrade::select_wine(&version) {
+                                    sender.output(GeneralAppMsg::Toast {
+                                        title: tr!("wine-prefix-update-failed"),
+                                        description: Some(err.to_string())
+                                    }).unwrap();
                                 }
 
                                 sender.input(ComponentsPageMsg::ResetWineSelection(index));
@@ -434,6 +952,66 @@ impl SimpleAsyncComponent for ComponentsPage {
                 }
             }
 
+            ComponentsPageMsg::AddCustomWine(name) => {
+                if name.trim().is_empty() {
+                    return;
+                }
+
+                let Some(folder) = rfd::AsyncFileDialog::new().pick_folder().await else {
+                    return;
+                };
+
+                self.processing_custom_wine = true;
+
+                let path = folder.path().to_path_buf();
+
+                std::thread::spawn(move || {
+                    let result = crate::custom_wine::add(name, path)
+                        .map(|_| tr!("custom-wine-added"))
+                        .map_err(|err| err.to_string());
+
+                    sender.input(ComponentsPageMsg::CustomWineFinished(result));
+                });
+            }
+
+            ComponentsPageMsg::RemoveCustomWine(name) => {
+                self.processing_custom_wine = true;
+
+                std::thread::spawn(move || {
+                    let result = crate::custom_wine::remove(name)
+                        .map(|_| tr!("custom-wine-removed"))
+                        .map_err(|err| err.to_string());
+
+                    sender.input(ComponentsPageMsg::CustomWineFinished(result));
+                });
+            }
+
+            ComponentsPageMsg::CustomWineFinished(result) => {
+                self.processing_custom_wine = false;
+
+                self.update_custom_wine_rows(&sender);
+
+                sender.input(ComponentsPageMsg::UpdateDownloadedWine);
+
+                let toast = match result {
+                    Ok(title) => GeneralAppMsg::Toast {
+                        title,
+                        description: None
+                    },
+
+                    Err(err) => {
+                        tracing::error!("Failed to update custom wine builds: {err}");
+
+                        GeneralAppMsg::Toast {
+                            title: tr!("custom-wine-failed"),
+                            description: Some(err)
+                        }
+                    }
+                };
+
+                sender.output(toast).unwrap();
+            }
+
             ComponentsPageMsg::ResetWineSelection(index) => {
                 self.selecting_wine_version = false;
                 self.selected_wine_version = index as u32;
@@ -446,23 +1024,12 @@ impl SimpleAsyncComponent for ComponentsPage {
                             if selected.is_none() || selected.unwrap().name != version.name {
                                 self.selecting_dxvk_version = true;
 
-                                let mut wine = match config.get_selected_wine() {
-                                    Ok(Some(version)) => {
-                                        match version.to_wine(config.components.path, Some(config.game.wine.builds.join(&version.name))) {
-                                            UnifiedWine::Default(wine) => wine,
-                                            UnifiedWine::Proton(_) => return
-                                        }
-                                    }
-
-                                    _ => Wine::default()
-                                };
-
-                                wine = wine.with_prefix(config.game.wine.prefix);
-
-                                let dxvk_folder = config.game.dxvk.builds.join(&version.name);
+                                let version = version.clone();
 
                                 std::thread::spawn(move || {
-                                    if let Err(err) = Dxvk::install(&wine, dxvk_folder, InstallParams::default()) {
+                                    Self::create_automatic_snapshot(&version.name, &sender);
+
+                                    if let Err(err) = crate::upgrade::select_dxvk(&version) {
                                         sender.output(GeneralAppMsg::Toast {
                                             title: tr!("dxvk-install-failed"),
                                             description: Some(err.to_string())
@@ -481,6 +1048,307 @@ impl SimpleAsyncComponent for ComponentsPage {
                 self.selecting_dxvk_version = false;
                 self.selected_dxvk_version = index as u32;
             }
+
+            ComponentsPageMsg::UpdateDownloadedDirectX(kind) => {
+                let config = Config::get().unwrap_or_else(|_| CONFIG.clone());
+                let installed = Settings::get().map(|settings| settings.directx).unwrap_or_default();
+
+                let versions = directx::get_downloaded(&config.components.path, kind).unwrap_or_default();
+
+                let installed = match kind {
+                    ComponentKind::Nvapi => installed.nvapi,
+                    _ => installed.vkd3d
+                };
+
+                let selected = installed
+                    .and_then(|name| versions.iter().position(|version| version.name == name))
+                    .map(|index| index as u32 + 1)
+                    .unwrap_or(0);
+
+                match kind {
+                    ComponentKind::Nvapi => {
+                        self.downloaded_nvapi_versions = versions;
+                        self.selected_nvapi_version = selected;
+                    }
+
+                    _ => {
+                        self.downloaded_vkd3d_versions = versions;
+                        self.selected_vkd3d_version = selected;
+                    }
+                }
+
+                sender.input(ComponentsPageMsg::UpdateDiskUsage);
+            }
+
+            ComponentsPageMsg::SelectDirectX(kind, index) => {
+                let selected = match kind {
+                    ComponentKind::Nvapi => self.selected_nvapi_version,
+                    _ => self.selected_vkd3d_version
+                };
+
+                if index as u32 == selected {
+                    return;
+                }
+
+                let version = match kind {
+                    ComponentKind::Nvapi => index.checked_sub(1).and_then(|index| self.downloaded_nvapi_versions.get(index)),
+                    _ => index.checked_sub(1).and_then(|index| self.downloaded_vkd3d_versions.get(index))
+                };
+
+                let name = version.map(|version| version.name.clone());
+
+                match kind {
+                    ComponentKind::Nvapi => self.selecting_nvapi_version = true,
+                    _ => self.selecting_vkd3d_version = true
+                }
+
+                std::thread::spawn(move || {
+                    Self::create_automatic_snapshot(name.as_deref().unwrap_or(kind.title()), &sender);
+
+                    let result = match name {
+                        Some(name) => directx::install(kind, name),
+                        None => directx::uninstall(kind)
+                    };
+
+                    if let Err(err) = result {
+                        tracing::error!("Failed to update {}: {err}", kind.title());
+
+                        sender.output(GeneralAppMsg::Toast {
+                            title: tr!("directx-install-failed", {
+                                "component" = kind.title()
+                            }),
+                            description: Some(err.to_string())
+                        }).unwrap();
+                    }
+
+                    sender.input(ComponentsPageMsg::ResetDirectXSelection(kind));
+
+                    sender.output(GeneralAppMsg::UpdateDirectX).unwrap();
+                });
+            }
+
+            ComponentsPageMsg::ResetDirectXSelection(kind) => {
+                match kind {
+                    ComponentKind::Nvapi => self.selecting_nvapi_version = false,
+                    _ => self.selecting_vkd3d_version = false
+                }
+
+                sender.input(ComponentsPageMsg::UpdateDownloadedDirectX(kind));
+            }
+
+            ComponentsPageMsg::UpdateDiskUsage => {
+                // Wine and DXVK lists are usually updated together
+                if self.processing_disk_usage {
+                    return;
+                }
+
+                self.processing_disk_usage = true;
+
+                std::thread::spawn(move || {
+                    let usage = disk_usage::get().unwrap_or_else(|err| {
+                        tracing::error!("Failed to get components disk usage: {err}");
+
+                        DiskUsage::default()
+                    });
+
+                    sender.input(ComponentsPageMsg::DiskUsageUpdated(usage));
+                });
+            }
+
+            ComponentsPageMsg::DiskUsageUpdated(usage) => {
+                self.processing_disk_usage = false;
+                self.disk_usage = usage;
+
+                self.update_disk_usage_rows(&sender);
+            }
+
+            ComponentsPageMsg::RemoveUnusedBuilds => {
+                self.processing_disk_usage = true;
+
+                std::thread::spawn(move || {
+                    let result = disk_usage::remove_unused()
+                        .map(|freed| tr!("unused-builds-removed", {
+                            "size" = prettify_bytes(freed)
+                        }))
+                        .map_err(|err| err.to_string());
+
+                    sender.input(ComponentsPageMsg::CleanupFinished(result));
+                });
+            }
+
+            ComponentsPageMsg::RemoveTempLeftover(leftover) => {
+                self.processing_disk_usage = true;
+
+                std::thread::spawn(move || {
+                    let result = disk_usage::remove_leftover(&leftover)
+                        .map(|_| tr!("temp-leftover-removed"))
+                        .map_err(|err| err.to_string());
+
+                    sender.input(ComponentsPageMsg::CleanupFinished(result));
+                });
+            }
+
+            ComponentsPageMsg::CleanupFinished(result) => {
+                self.processing_disk_usage = false;
+
+                let toast = match result {
+                    Ok(title) => GeneralAppMsg::Toast {
+                        title,
+                        description: None
+                    },
+
+                    Err(err) => {
+                        tracing::error!("Failed to clean up components: {err}");
+
+                        GeneralAppMsg::Toast {
+                            title: tr!("components-cleanup-failed"),
+                            description: Some(err)
+                        }
+                    }
+                };
+
+                // Downloaded versions lists update disk usage as well
+                sender.input(ComponentsPageMsg::UpdateDownloadedWine);
+                sender.input(ComponentsPageMsg::UpdateDownloadedDxvk);
+
+                sender.output(toast).unwrap();
+            }
+
+            ComponentsPageMsg::SetUpgradePolicy(policy) => {
+                self.upgrade_policy = policy;
+
+                let result = Settings::modify(|settings| settings.components_upgrade.policy = policy);
+
+                if let Err(err) = result {
+                    tracing::error!("Failed to save components upgrade policy: {err}");
+                }
+            }
+
+            ComponentsPageMsg::Rollback => {
+                self.processing_rollback = true;
+
+                std::thread::spawn(move || {
+                    let previous = Settings::get()
+                        .map(|settings| [settings.components_upgrade.previous_wine, settings.components_upgrade.previous_dxvk])
+                        .unwrap_or_default();
+
+                    let previous = previous.into_iter()
+                        .flatten()
+                        .collect::<Vec<_>>()
+                        .join(", ");
+
+                    Self::create_automatic_snapshot(previous, &sender);
+
+                    let result = crate::upgrade::rollback()
+                        .map(|_| tr!("components-rolled-back"))
+                        .map_err(|err| err.to_string());
+
+                    sender.input(ComponentsPageMsg::RollbackFinished(result));
+                });
+            }
+
+            ComponentsPageMsg::RollbackFinished(result) => {
+                self.processing_rollback = false;
+
+                self.can_rollback = Settings::get()
+                    .map(|settings| settings.components_upgrade.previous_wine.is_some() || settings.components_upgrade.previous_dxvk.is_some())
+                    .unwrap_or_default();
+
+                sender.input(ComponentsPageMsg::UpdateDownloadedWine);
+                sender.input(ComponentsPageMsg::UpdateDownloadedDxvk);
+
+                let toast = match result {
+                    Ok(title) => GeneralAppMsg::Toast {
+                        title,
+                        description: None
+                    },
+
+                    Err(err) => {
+                        tracing::error!("Failed to roll components back: {err}");
+
+                        GeneralAppMsg::Toast {
+                            title: tr!("components-rollback-failed"),
+                            description: Some(err)
+                        }
+                    }
+                };
+
+                sender.output(toast).unwrap();
+            }
+
+            ComponentsPageMsg::ExportBundle => {
+                let config = Config::get().unwrap_or_else(|_| CONFIG.clone());
+
+                let name = match &config.game.wine.selected {
+                    Some(wine) => format!("wavey-components-{wine}.tar.gz"),
+                    None => String::from("wavey-components.tar.gz")
+                };
+
+                let Some(file) = rfd::AsyncFileDialog::new().set_file_name(name).save_file().await else {
+                    return;
+                };
+
+                self.processing_bundle = true;
+
+                let path = file.path().to_path_buf();
+
+                std::thread::spawn(move || {
+                    let result = crate::bundle::export(path)
+                        .map(|_| tr!("components-bundle-exported"))
+                        .map_err(|err| err.to_string());
+
+                    sender.input(ComponentsPageMsg::BundleFinished(result));
+                });
+            }
+
+            ComponentsPageMsg::ImportBundle => {
+                let Some(file) = rfd::AsyncFileDialog::new().add_filter("tar.gz", &["gz", "tgz"]).pick_file().await else {
+                    return;
+                };
+
+                self.processing_bundle = true;
+
+                let path = file.path().to_path_buf();
+
+                std::thread::spawn(move || {
+                    let result = crate::bundle::import(path)
+                        .map(|_| tr!("components-bundle-imported"))
+                        .map_err(|err| err.to_string());
+
+                    if result.is_ok() {
+                        sender.input(ComponentsPageMsg::UpdateDownloadedWine);
+                        sender.input(ComponentsPageMsg::UpdateDownloadedDxvk);
+                    }
+
+                    sender.input(ComponentsPageMsg::BundleFinished(result));
+                });
+            }
+
+            ComponentsPageMsg::BundleFinished(result) => {
+                self.processing_bundle = false;
+
+                let toast = match result {
+                    Ok(title) => GeneralAppMsg::Toast {
+                        title,
+                        description: None
+                    },
+
+                    Err(err) => {
+                        tracing::error!("Failed to process components bundle: {err}");
+
+                        GeneralAppMsg::Toast {
+                            title: tr!("components-bundle-failed"),
+                            description: Some(err)
+                        }
+                    }
+                };
+
+                sender.output(toast).unwrap();
+            }
+
+            ComponentsPageMsg::Toast { title, description } => {
+                sender.output(GeneralAppMsg::Toast { title, description }).unwrap();
+            }
         }
     }
 }
@@ -0,0 +1,310 @@
+use relm4::prelude::*;
+use gtk::prelude::*;
+use adw::prelude::*;
+
+use super::GeneralAppMsg;
+
+use crate::settings::prelude::*;
+use crate::*;
+
+pub struct IndexServersPage {
+    servers: Vec<String>,
+    syncs: std::collections::HashMap<String, IndexSync>,
+
+    servers_group: adw::PreferencesGroup,
+    servers_rows: Vec<adw::ActionRow>,
+
+    syncing: bool
+}
+
+#[derive(Debug, Clone)]
+pub enum IndexServersPageMsg {
+    AddServer(String),
+    RemoveServer(usize),
+
+    /// Move server with the given index one position up or down
+    MoveServer {
+        index: usize,
+        up: bool
+    },
+
+    ShowChanges(String),
+
+    SyncNow,
+
+    /// Server and changes list, or error message
+    SyncFinished(Result<(String, Vec<String>), String>)
+}
+
+impl IndexServersPage {
+    fn update_rows(&mut self, sender: &AsyncComponentSender<Self>) {
+        for row in self.servers_rows.drain(..) {
+            self.servers_group.remove(&row);
+        }
+
+        for (i, server) in self.servers.iter().enumerate() {
+            let row = adw::ActionRow::new();
+
+            row.set_title(server);
+
+            row.set_subtitle(&match self.syncs.get(server) {
+                Some(sync) => {
+                    let time = gtk::glib::DateTime::from_unix_local(sync.synced_at as i64)
+                        .and_then(|time| time.format("%x %X"))
+                        .map(|time| time.to_string())
+                        .unwrap_or_default();
+
+                    tr!("index-server-synced", {
+                        "time" = time,
+                        "changes" = sync.changes.len()
+                    })
+                }
+
+                None => tr!("index-server-not-synced")
+            });
+
+            row.set_activatable(self.syncs.contains_key(server));
+
+            let server_name = server.clone();
+            let row_sender = sender.clone();
+
+            row.connect_activated(move |_| {
+                row_sender.input(IndexServersPageMsg::ShowChanges(server_name.clone()));
+            });
+
+            let buttons = [
+                ("go-up-symbolic", i > 0, IndexServersPageMsg::MoveServer { index: i, up: true }),
+                ("go-down-symbolic", i + 1 < self.servers.len(), IndexServersPageMsg::MoveServer { index: i, up: false }),
+                ("user-trash-symbolic", self.servers.len() > 1, IndexServersPageMsg::RemoveServer(i))
+            ];
+
+            for (icon, sensitive, msg) in buttons {
+                let button = gtk::Button::new();
+
+                button.set_icon_name(icon);
+                button.set_valign(gtk::Align::Center);
+                button.set_sensitive(sensitive);
+                button.add_css_class("flat");
+
+                let sender = sender.clone();
+
+                button.connect_clicked(move |_| {
+                    sender.input(msg.clone());
+                });
+
+                row.add_suffix(&button);
+            }
+
+            self.servers_group.add(&row);
+            self.servers_rows.push(row);
+        }
+    }
+
+    /// Save servers list to the config and update rows
+    fn save(&mut self, sender: &AsyncComponentSender<Self>) {
+        if let Ok(mut config) = Config::get() {
+            config.components.servers = self.servers.clone();
+
+            Config::update(config);
+        }
+
+        self.update_rows(sender);
+    }
+}
+
+#[relm4::component(async, pub)]
+impl SimpleAsyncComponent for IndexServersPage {
+    type Init = ();
+    type Input = IndexServersPageMsg;
+    type Output = GeneralAppMsg;
+
+    view! {
+        adw::NavigationPage {
+            #[wrap(Some)]
+            set_child = &gtk::Box {
+                set_orientation: gtk::Orientation::Vertical,
+
+                adw::HeaderBar {
+                    #[wrap(Some)]
+                    set_title_widget = &adw::WindowTitle {
+                        set_title: &tr!("components-index")
+                    }
+                },
+
+                adw::PreferencesPage {
+                    #[local_ref]
+                    add = servers_group -> adw::PreferencesGroup {
+                        set_title: &tr!("index-servers"),
+                        set_description: Some(&tr!("index-servers-description")),
+
+                        adw::EntryRow {
+                            set_title: &tr!("add-index-server"),
+                            set_show_apply_button: true,
+
+                            connect_apply[sender] => move |row| {
+                                sender.input(IndexServersPageMsg::AddServer(row.text().to_string()));
+
+                                row.set_text("");
+                            }
+                        }
+                    },
+
+                    add = &adw::PreferencesGroup {
+                        adw::ActionRow {
+                            set_title: &tr!("sync-components-index"),
+                            set_subtitle: &tr!("sync-components-index-description"),
+
+                            add_suffix = &gtk::Spinner {
+                                #[watch]
+                                set_spinning: model.syncing,
+
+                                #[watch]
+                                set_visible: model.syncing
+                            },
+
+                            add_suffix = &gtk::Button {
+                                set_icon_name: "view-refresh-symbolic",
+                                add_css_class: "flat",
+                                set_valign: gtk::Align::Center,
+
+                                #[watch]
+                                set_sensitive: !model.syncing,
+
+                                connect_clicked => IndexServersPageMsg::SyncNow
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    async fn init(_init: Self::Init, root: Self::Root, sender: AsyncComponentSender<Self>) -> AsyncComponentParts<Self> {
+        tracing::info!("Initializing general settings -> components index page");
+
+        let config = Config::get().unwrap_or_else(|_| CONFIG.clone());
+
+        let mut model = Self {
+            servers: config.components.servers,
+
+            syncs: Settings::get()
+                .map(|settings| settings.index_syncs)
+                .unwrap_or_default(),
+
+            servers_group: adw::PreferencesGroup::new(),
+            servers_rows: Vec::new(),
+
+            syncing: false
+        };
+
+        let servers_group = &model.servers_group;
+
+        let widgets = view_output!();
+
+        model.update_rows(&sender);
+
+        AsyncComponentParts { model, widgets }
+    }
+
+    async fn update(&mut self, msg: Self::Input, sender: AsyncComponentSender<Self>) {
+        tracing::debug!("Called components index settings event: {:?}", msg);
+
+        match msg {
+            IndexServersPageMsg::AddServer(server) => {
+                match components_index::normalize_server(&server) {
+                    Ok(server) if !self.servers.contains(&server) => {
+                        self.servers.push(server);
+
+                        self.save(&sender);
+                    }
+
+                    Ok(_) => (),
+
+                    Err(err) => {
+                        sender.output(GeneralAppMsg::Toast {
+                            title: tr!("index-server-invalid"),
+                            description: Some(err.to_string())
+                        }).unwrap();
+                    }
+                }
+            }
+
+            IndexServersPageMsg::RemoveServer(index) => {
+                // At least one server is needed to sync the index
+                if index < self.servers.len() && self.servers.len() > 1 {
+                    self.servers.remove(index);
+
+                    self.save(&sender);
+                }
+            }
+
+            IndexServersPageMsg::MoveServer { index, up } => {
+                let other = if up {
+                    index.checked_sub(1)
+                } else {
+                    Some(index + 1)
+                };
+
+                if let Some(other) = other.filter(|other| *other < self.servers.len()) {
+                    self.servers.swap(index, other);
+
+                    self.save(&sender);
+                }
+            }
+
+            IndexServersPageMsg::ShowChanges(server) => {
+                if let Some(sync) = self.syncs.get(&server) {
+                    sender.output(GeneralAppMsg::Toast {
+                        title: tr!("components-index-updated"),
+                        description: if sync.changes.is_empty() {
+                            Some(tr!("index-server-no-changes"))
+                        } else {
+                            Some(sync.changes.iter()
+                                .map(|line| format!("- {line}"))
+                                .collect::<Vec<_>>()
+                                .join("\n"))
+                        }
+                    }).unwrap();
+                }
+            }
+
+            IndexServersPageMsg::SyncNow => {
+                self.syncing = true;
+
+                std::thread::spawn(move || {
+                    let result = components_index::sync_now()
+                        .map_err(|err| err.to_string());
+
+                    sender.input(IndexServersPageMsg::SyncFinished(result));
+                });
+            }
+
+            IndexServersPageMsg::SyncFinished(result) => {
+                self.syncing = false;
+
+                match result {
+                    Ok((server, _)) => {
+                        self.syncs = Settings::get()
+                            .map(|settings| settings.index_syncs)
+                            .unwrap_or_default();
+
+                        self.update_rows(&sender);
+
+                        sender.output(GeneralAppMsg::ComponentsIndexSynced).unwrap();
+
+                        sender.input(IndexServersPageMsg::ShowChanges(server));
+                    }
+
+                    Err(err) => {
+                        tracing::error!("Failed to sync components index: {err}");
+
+                        sender.output(GeneralAppMsg::Toast {
+                            title: tr!("components-index-sync-failed"),
+                            description: Some(err)
+                        }).unwrap();
+                    }
+                }
+            }
+        }
+    }
+}
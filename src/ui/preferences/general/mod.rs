@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 use relm4::prelude::*;
 
 use gtk::prelude::*;
@@ -10,24 +12,52 @@ use anime_launcher_sdk::wuwa::config::Config;
 use anime_launcher_sdk::wuwa::config::schema::launcher::{LauncherStyle, LauncherBehavior};
 
 pub mod components;
+pub mod index_servers;
+pub mod wine_prefix;
+pub mod winetricks;
+pub mod run_executable;
+pub mod registry;
 
 use components::*;
+use index_servers::*;
+use wine_prefix::*;
+use winetricks::*;
+use run_executable::*;
+use registry::*;
 
 use crate::ui::migrate_installation::MigrateInstallationApp;
+use crate::ui::import_installation::ImportInstallationApp;
+use crate::ui::first_run::default_paths::MigrationStatus;
 use crate::ui::preferences::main::PreferencesAppMsg;
 
+use crate::settings::prelude::*;
+
 use crate::i18n::*;
 use crate::*;
 
 pub struct GeneralApp {
     migrate_installation: Controller<MigrateInstallationApp>,
+    import_installation: AsyncController<ImportInstallationApp>,
     components_page: AsyncController<ComponentsPage>,
+    index_servers_page: AsyncController<IndexServersPage>,
+    wine_prefix_page: AsyncController<WinePrefixPage>,
+    winetricks_page: AsyncController<WinetricksPage>,
+    run_executable_page: AsyncController<RunExecutablePage>,
+    registry_page: AsyncController<RegistryPage>,
 
     game_diff: Option<VersionDiff>,
     main_patch: Option<(Version, JadeitePatchStatusVariant)>,
 
     style: LauncherStyle,
-    languages: Vec<String>
+    languages: Vec<String>,
+
+    background: Background,
+
+    background_crop_row: adw::ExpanderRow,
+    background_crop_x: adw::SpinRow,
+    background_crop_y: adw::SpinRow,
+    background_crop_width: adw::SpinRow,
+    background_crop_height: adw::SpinRow
 }
 
 #[derive(Debug, Clone)]
@@ -44,13 +74,50 @@ pub enum GeneralAppMsg {
     UpdateDownloadedDxvk,
 
     OpenMigrateInstallation,
+
+    /// Sent by the migrate installation window when it's closed
+    MigrationFinished(MigrationStatus),
+
+    OpenImportInstallation,
+
+    /// Sent by the import installation window when installation was imported
+    InstallationImported,
+
     RepairGame,
 
     OpenMainPage,
     OpenComponentsPage,
+    OpenIndexServersPage,
+    OpenWinePrefixPage,
+    OpenWinetricksPage,
+    OpenRunExecutablePage,
+    OpenRegistryPage,
+
+    /// Sent by the components index page when the index was synced manually
+    ComponentsIndexSynced,
+
+    /// Sent by the components page when VKD3D-Proton or DXVK-NVAPI was changed
+    UpdateDirectX,
 
     UpdateLauncherStyle(LauncherStyle),
 
+    SetBackgroundSource(u32),
+    ChooseBackground {
+        folder: bool
+    },
+    SetBackgroundScaling(u32),
+    ToggleBackgroundCrop(bool),
+    UpdateBackgroundCrop,
+    SetBackgroundBlur(f32),
+    SetBackgroundDim(f32),
+    ToggleBackgroundVideo(bool),
+    ChooseBackgroundVideo,
+    ResetBackgroundVideo,
+    SetBackgroundVideoOnBattery(bool),
+
+    /// Sent by the background processing thread
+    BackgroundProcessed(Result<(), String>),
+
     WineOpen(&'static [&'static str]),
 
     Toast {
@@ -138,10 +205,238 @@ impl SimpleAsyncComponent for GeneralApp {
                 #[watch]
                 set_visible: model.style == LauncherStyle::Classic,
 
+                adw::ComboRow {
+                    set_title: &tr!("background-source"),
+
+                    set_model: Some(&gtk::StringList::new(&[
+                        &tr!("background-official"),
+                        &tr!("background-file"),
+                        &tr!("background-folder")
+                    ])),
+
+                    #[watch]
+                    #[block_signal(background_source_notify)]
+                    set_selected: match &model.background.source {
+                        BackgroundSource::Official  => 0,
+                        BackgroundSource::File(_)   => 1,
+                        BackgroundSource::Folder(_) => 2
+                    },
+
+                    connect_selected_notify[sender] => move |row| {
+                        if is_ready() {
+                            sender.input(GeneralAppMsg::SetBackgroundSource(row.selected()));
+                        }
+                    } @background_source_notify
+                },
+
+                adw::ActionRow {
+                    set_title: &tr!("background-path"),
+                    set_activatable: true,
+
+                    #[watch]
+                    set_visible: model.background.source != BackgroundSource::Official,
+
+                    #[watch]
+                    set_subtitle: &match &model.background.source {
+                        BackgroundSource::File(path) |
+                        BackgroundSource::Folder(path) => path.to_string_lossy().to_string(),
+
+                        BackgroundSource::Official => String::new()
+                    },
+
+                    add_prefix = &gtk::Image {
+                        set_icon_name: Some("folder-symbolic")
+                    },
+
+                    connect_activated[sender] => move |_| {
+                        if let Ok(settings) = Settings::get() {
+                            sender.input(GeneralAppMsg::ChooseBackground {
+                                folder: matches!(settings.background.source, BackgroundSource::Folder(_))
+                            });
+                        }
+                    }
+                },
+
+                adw::ComboRow {
+                    set_title: &tr!("background-scaling"),
+                    set_subtitle: &tr!("background-scaling-description"),
+
+                    #[watch]
+                    set_visible: model.background_image().is_some(),
+
+                    set_model: Some(&gtk::StringList::new(&[
+                        &tr!("background-scaling-cover"),
+                        &tr!("background-scaling-contain"),
+                        &tr!("background-scaling-stretch")
+                    ])),
+
+                    #[watch]
+                    #[block_signal(background_scaling_notify)]
+                    set_selected: model.background_image()
+                        .and_then(|path| BackgroundScaling::list().iter().position(|scaling| *scaling == model.background.image(&path).scaling))
+                        .unwrap_or(0) as u32,
+
+                    connect_selected_notify[sender] => move |row| {
+                        if is_ready() {
+                            sender.input(GeneralAppMsg::SetBackgroundScaling(row.selected()));
+                        }
+                    } @background_scaling_notify
+                },
+
+                #[local_ref]
+                background_crop_row -> adw::ExpanderRow {
+                    set_title: &tr!("background-crop"),
+                    set_subtitle: &tr!("background-crop-description"),
+
+                    set_show_enable_switch: true,
+
+                    #[watch]
+                    set_visible: model.background_image().is_some(),
+
+                    #[watch]
+                    #[block_signal(background_crop_notify)]
+                    set_enable_expansion: model.background_image()
+                        .map(|path| model.background.image(&path).crop.is_some())
+                        .unwrap_or(false),
+
+                    connect_enable_expansion_notify[sender] => move |row| {
+                        if is_ready() {
+                            sender.input(GeneralAppMsg::ToggleBackgroundCrop(row.enables_expansion()));
+                        }
+                    } @background_crop_notify,
+
+                    #[local_ref]
+                    add_row = background_crop_x -> adw::SpinRow {
+                        set_title: "X"
+                    },
+
+                    #[local_ref]
+                    add_row = background_crop_y -> adw::SpinRow {
+                        set_title: "Y"
+                    },
+
+                    #[local_ref]
+                    add_row = background_crop_width -> adw::SpinRow {
+                        set_title: &tr!("width")
+                    },
+
+                    #[local_ref]
+                    add_row = background_crop_height -> adw::SpinRow {
+                        set_title: &tr!("height")
+                    },
+
+                    add_row = &adw::ActionRow {
+                        set_title: &tr!("apply"),
+                        set_activatable: true,
+
+                        add_suffix = &gtk::Image {
+                            set_icon_name: Some("emblem-ok-symbolic")
+                        },
+
+                        connect_activated => GeneralAppMsg::UpdateBackgroundCrop
+                    }
+                },
+
+                adw::SpinRow {
+                    set_title: &tr!("background-blur"),
+                    set_subtitle: &tr!("background-blur-description"),
+
+                    set_adjustment: Some(&gtk::Adjustment::new(0.0, 0.0, 50.0, 1.0, 5.0, 0.0)),
+
+                    #[block_signal(background_blur_notify)]
+                    set_value: model.background.effects.blur as f64,
+
+                    connect_value_notify[sender] => move |row| {
+                        if is_ready() {
+                            sender.input(GeneralAppMsg::SetBackgroundBlur(row.value() as f32));
+                        }
+                    } @background_blur_notify
+                },
+
+                adw::SpinRow {
+                    set_title: &tr!("background-dim"),
+                    set_subtitle: &tr!("background-dim-description"),
+
+                    set_adjustment: Some(&gtk::Adjustment::new(0.0, 0.0, 90.0, 5.0, 10.0, 0.0)),
+
+                    #[block_signal(background_dim_notify)]
+                    set_value: (model.background.effects.dim * 100.0).round() as f64,
+
+                    connect_value_notify[sender] => move |row| {
+                        if is_ready() {
+                            sender.input(GeneralAppMsg::SetBackgroundDim(row.value() as f32 / 100.0));
+                        }
+                    } @background_dim_notify
+                },
+
+                adw::ExpanderRow {
+                    set_title: &tr!("background-video"),
+                    set_subtitle: &tr!("background-video-description"),
+
+                    set_show_enable_switch: true,
+
+                    #[block_signal(background_video_notify)]
+                    set_enable_expansion: model.background.video.enabled,
+
+                    connect_enable_expansion_notify[sender] => move |row| {
+                        if is_ready() {
+                            sender.input(GeneralAppMsg::ToggleBackgroundVideo(row.enables_expansion()));
+                        }
+                    } @background_video_notify,
+
+                    add_row = &adw::ActionRow {
+                        set_title: &tr!("background-video-file"),
+                        set_activatable: true,
+
+                        #[watch]
+                        set_subtitle: &match &model.background.video.path {
+                            Some(path) => path.to_string_lossy().to_string(),
+                            None => tr!("background-official")
+                        },
+
+                        add_prefix = &gtk::Image {
+                            set_icon_name: Some("folder-symbolic")
+                        },
+
+                        add_suffix = &gtk::Button {
+                            set_icon_name: "edit-clear-symbolic",
+                            set_tooltip_text: Some(&tr!("background-official")),
+                            add_css_class: "flat",
+                            set_valign: gtk::Align::Center,
+
+                            #[watch]
+                            set_visible: model.background.video.path.is_some(),
+
+                            connect_clicked => GeneralAppMsg::ResetBackgroundVideo
+                        },
+
+                        connect_activated => GeneralAppMsg::ChooseBackgroundVideo
+                    },
+
+                    add_row = &adw::ActionRow {
+                        set_title: &tr!("background-video-battery"),
+                        set_subtitle: &tr!("background-video-battery-description"),
+
+                        add_suffix = &gtk::Switch {
+                            set_valign: gtk::Align::Center,
+                            set_active: model.background.video.disable_on_battery,
+
+                            connect_state_notify[sender] => move |switch| {
+                                if is_ready() {
+                                    sender.input(GeneralAppMsg::SetBackgroundVideoOnBattery(switch.is_active()));
+                                }
+                            }
+                        }
+                    }
+                },
+
                 adw::ActionRow {
                     set_title: &tr!("update-background"),
                     set_subtitle: &tr!("update-background-description"),
 
+                    #[watch]
+                    set_visible: model.background.source == BackgroundSource::Official,
+
                     add_suffix = &gtk::Switch {
                         set_valign: gtk::Align::Center,
                         set_active: !KEEP_BACKGROUND_FILE.exists(),
@@ -226,6 +521,13 @@ impl SimpleAsyncComponent for GeneralApp {
                         connect_clicked => GeneralAppMsg::OpenMigrateInstallation
                     },
 
+                    gtk::Button {
+                        set_label: &tr!("import-installation"),
+                        set_tooltip_text: Some(&tr!("import-installation-description")),
+
+                        connect_clicked => GeneralAppMsg::OpenImportInstallation
+                    },
+
                     gtk::Button {
                         set_label: &tr!("repair-game"),
 
@@ -372,6 +674,45 @@ impl SimpleAsyncComponent for GeneralApp {
                     connect_activated => GeneralAppMsg::OpenComponentsPage
                 },
 
+                adw::ActionRow {
+                    set_title: &tr!("components-index"),
+                    set_subtitle: &tr!("components-index-description"),
+
+                    add_suffix = &gtk::Image {
+                        set_icon_name: Some("go-next-symbolic")
+                    },
+
+                    set_activatable: true,
+
+                    connect_activated => GeneralAppMsg::OpenIndexServersPage
+                },
+
+                adw::ActionRow {
+                    set_title: &tr!("wine-prefix"),
+                    set_subtitle: &tr!("wine-prefix-description"),
+
+                    add_suffix = &gtk::Image {
+                        set_icon_name: Some("go-next-symbolic")
+                    },
+
+                    set_activatable: true,
+
+                    connect_activated => GeneralAppMsg::OpenWinePrefixPage
+                },
+
+                adw::ActionRow {
+                    set_title: &tr!("registry-tweaks"),
+                    set_subtitle: &tr!("registry-tweaks-description"),
+
+                    add_suffix = &gtk::Image {
+                        set_icon_name: Some("go-next-symbolic")
+                    },
+
+                    set_activatable: true,
+
+                    connect_activated => GeneralAppMsg::OpenRegistryPage
+                },
+
                 adw::ExpanderRow {
                     set_title: &tr!("wine-tools"),
 
@@ -427,6 +768,32 @@ impl SimpleAsyncComponent for GeneralApp {
                         set_activatable: true,
 
                         connect_activated => GeneralAppMsg::WineOpen(&["start", "winedbg"])
+                    },
+
+                    add_row = &adw::ActionRow {
+                        set_title: "Winetricks",
+                        set_subtitle: &tr!("winetricks-description"),
+
+                        add_suffix = &gtk::Image {
+                            set_icon_name: Some("go-next-symbolic")
+                        },
+
+                        set_activatable: true,
+
+                        connect_activated => GeneralAppMsg::OpenWinetricksPage
+                    },
+
+                    add_row = &adw::ActionRow {
+                        set_title: &tr!("run-executable"),
+                        set_subtitle: &tr!("run-executable-description"),
+
+                        add_suffix = &gtk::Image {
+                            set_icon_name: Some("go-next-symbolic")
+                        },
+
+                        set_activatable: true,
+
+                        connect_activated => GeneralAppMsg::OpenRunExecutablePage
                     }
                 }
             }
@@ -434,6 +801,21 @@ impl SimpleAsyncComponent for GeneralApp {
 
         #[local_ref]
         components_page -> adw::NavigationPage,
+
+        #[local_ref]
+        index_servers_page -> adw::NavigationPage,
+
+        #[local_ref]
+        wine_prefix_page -> adw::NavigationPage,
+
+        #[local_ref]
+        winetricks_page -> adw::NavigationPage,
+
+        #[local_ref]
+        run_executable_page -> adw::NavigationPage,
+
+        #[local_ref]
+        registry_page -> adw::NavigationPage,
     }
 
     async fn init(
@@ -446,20 +828,67 @@ impl SimpleAsyncComponent for GeneralApp {
         let model = Self {
             migrate_installation: MigrateInstallationApp::builder()
                 .launch(())
-                .detach(),
+                .forward(sender.input_sender(), std::convert::identity),
+
+            import_installation: ImportInstallationApp::builder()
+                .launch(())
+                .forward(sender.input_sender(), |_| GeneralAppMsg::InstallationImported),
 
             components_page: ComponentsPage::builder()
                 .launch(())
                 .forward(sender.input_sender(), std::convert::identity),
 
+            index_servers_page: IndexServersPage::builder()
+                .launch(())
+                .forward(sender.input_sender(), std::convert::identity),
+
+            wine_prefix_page: WinePrefixPage::builder()
+                .launch(())
+                .forward(sender.input_sender(), std::convert::identity),
+
+            winetricks_page: WinetricksPage::builder()
+                .launch(())
+                .forward(sender.input_sender(), std::convert::identity),
+
+            run_executable_page: RunExecutablePage::builder()
+                .launch(())
+                .forward(sender.input_sender(), std::convert::identity),
+
+            registry_page: RegistryPage::builder()
+                .launch(())
+                .forward(sender.input_sender(), std::convert::identity),
+
             game_diff: None,
             main_patch: None,
 
             style: CONFIG.launcher.style,
-            languages: SUPPORTED_LANGUAGES.iter().map(|lang| tr!(format_lang(lang).as_str())).collect()
+            languages: SUPPORTED_LANGUAGES.iter().map(|lang| tr!(format_lang(lang).as_str())).collect(),
+
+            background: Settings::get()
+                .map(|settings| settings.background)
+                .unwrap_or_default(),
+
+            background_crop_row: adw::ExpanderRow::new(),
+            background_crop_x: adw::SpinRow::with_range(0.0, 16384.0, 1.0),
+            background_crop_y: adw::SpinRow::with_range(0.0, 16384.0, 1.0),
+            background_crop_width: adw::SpinRow::with_range(1.0, 16384.0, 1.0),
+            background_crop_height: adw::SpinRow::with_range(1.0, 16384.0, 1.0)
         };
 
+        model.update_crop_rows();
+
         let components_page = model.components_page.widget();
+        let index_servers_page = model.index_servers_page.widget();
+        let wine_prefix_page = model.wine_prefix_page.widget();
+        let winetricks_page = model.winetricks_page.widget();
+        let run_executable_page = model.run_executable_page.widget();
+        let registry_page = model.registry_page.widget();
+
+        let background_crop_row = &model.background_crop_row;
+        let background_crop_x = &model.background_crop_x;
+        let background_crop_y = &model.background_crop_y;
+        let background_crop_width = &model.background_crop_width;
+        let background_crop_height = &model.background_crop_height;
 
         let widgets = view_output!();
 
@@ -498,6 +927,60 @@ impl SimpleAsyncComponent for GeneralApp {
                 self.migrate_installation.widget().present();
             }
 
+            #[allow(unused_must_use)]
+            GeneralAppMsg::MigrationFinished(status) => match status {
+                MigrationStatus::Migrated => {
+                    tracing::info!("Installation migrated");
+
+                    // Components page reads folders paths on init
+                    self.components_page = ComponentsPage::builder()
+                        .launch(())
+                        .forward(sender.input_sender(), std::convert::identity);
+
+                    sender.input(GeneralAppMsg::UpdateDownloadedWine);
+                    sender.input(GeneralAppMsg::UpdateDownloadedDxvk);
+
+                    sender.output(PreferencesAppMsg::UpdateLauncherState);
+
+                    sender.input(GeneralAppMsg::Toast {
+                        title: tr!("migration-finished"),
+                        description: None
+                    });
+                }
+
+                MigrationStatus::Cancelled => tracing::info!("Installation migration was cancelled"),
+
+                MigrationStatus::Failed(reason) => {
+                    sender.input(GeneralAppMsg::Toast {
+                        title: tr!("migration-failed"),
+                        description: Some(reason)
+                    });
+                }
+            }
+
+            GeneralAppMsg::OpenImportInstallation => unsafe {
+                if let Some(window) = crate::ui::main::PREFERENCES_WINDOW.as_ref() {
+                    self.import_installation.widget().set_transient_for(Some(window.widget()));
+                }
+
+                self.import_installation.widget().present();
+            }
+
+            #[allow(unused_must_use)]
+            GeneralAppMsg::InstallationImported => {
+                // Components page reads folders paths on init
+                self.components_page = ComponentsPage::builder()
+                    .launch(())
+                    .forward(sender.input_sender(), std::convert::identity);
+
+                sender.output(PreferencesAppMsg::UpdateLauncherState);
+
+                sender.input(GeneralAppMsg::Toast {
+                    title: tr!("installation-imported"),
+                    description: None
+                });
+            }
+
             GeneralAppMsg::RepairGame => {
                 sender.output(Self::Output::RepairGame).unwrap();
             }
@@ -516,19 +999,75 @@ impl SimpleAsyncComponent for GeneralApp {
                     .push_subpage(self.components_page.widget());
             }
 
+            GeneralAppMsg::OpenIndexServersPage => unsafe {
+                PREFERENCES_WINDOW.as_ref()
+                    .unwrap_unchecked()
+                    .widget()
+                    .push_subpage(self.index_servers_page.widget());
+            }
+
+            GeneralAppMsg::OpenWinePrefixPage => unsafe {
+                self.wine_prefix_page.sender()
+                    .send(WinePrefixPageMsg::CheckHealth)
+                    .unwrap();
+
+                self.wine_prefix_page.sender()
+                    .send(WinePrefixPageMsg::UpdateSnapshots)
+                    .unwrap();
+
+                PREFERENCES_WINDOW.as_ref()
+                    .unwrap_unchecked()
+                    .widget()
+                    .push_subpage(self.wine_prefix_page.widget());
+            }
+
+            GeneralAppMsg::OpenWinetricksPage => unsafe {
+                PREFERENCES_WINDOW.as_ref()
+                    .unwrap_unchecked()
+                    .widget()
+                    .push_subpage(self.winetricks_page.widget());
+            }
+
+            GeneralAppMsg::OpenRunExecutablePage => unsafe {
+                PREFERENCES_WINDOW.as_ref()
+                    .unwrap_unchecked()
+                    .widget()
+                    .push_subpage(self.run_executable_page.widget());
+            }
+
+            GeneralAppMsg::OpenRegistryPage => unsafe {
+                self.registry_page.sender()
+                    .send(RegistryPageMsg::UpdateTweaks)
+                    .unwrap();
+
+                PREFERENCES_WINDOW.as_ref()
+                    .unwrap_unchecked()
+                    .widget()
+                    .push_subpage(self.registry_page.widget());
+            }
+
             #[allow(unused_must_use)]
-            GeneralAppMsg::UpdateLauncherStyle(style) => {
-                if style == LauncherStyle::Classic && !KEEP_BACKGROUND_FILE.exists() {
-                    if let Err(err) = crate::background::download_background() {
-                        tracing::error!("Failed to download background picture");
+            GeneralAppMsg::ComponentsIndexSynced => {
+                // Components page reads versions lists on init
+                self.components_page = ComponentsPage::builder()
+                    .launch(())
+                    .forward(sender.input_sender(), std::convert::identity);
 
-                        sender.input(GeneralAppMsg::Toast {
-                            title: tr!("background-downloading-failed"),
-                            description: Some(err.to_string())
-                        });
+                sender.input(GeneralAppMsg::UpdateDownloadedWine);
+                sender.input(GeneralAppMsg::UpdateDownloadedDxvk);
 
-                        return;
-                    }
+                sender.output(PreferencesAppMsg::UpdateLauncherState);
+            }
+
+            #[allow(unused_must_use)]
+            GeneralAppMsg::UpdateDirectX => {
+                sender.output(PreferencesAppMsg::UpdateDirectX);
+            }
+
+            #[allow(unused_must_use)]
+            GeneralAppMsg::UpdateLauncherStyle(style) => {
+                if style == LauncherStyle::Classic {
+                    Self::process_background(&sender);
                 }
 
                 if let Ok(mut config) = Config::get() {
@@ -542,10 +1081,139 @@ impl SimpleAsyncComponent for GeneralApp {
                 sender.output(Self::Output::SetLauncherStyle(style));
             }
 
+            GeneralAppMsg::SetBackgroundSource(index) => match index {
+                0 => {
+                    self.background.source = BackgroundSource::Official;
+
+                    self.update_background(&sender);
+                }
+
+                1 => sender.input(GeneralAppMsg::ChooseBackground { folder: false }),
+                2 => sender.input(GeneralAppMsg::ChooseBackground { folder: true }),
+
+                _ => ()
+            }
+
+            GeneralAppMsg::ChooseBackground { folder } => {
+                let dialog = rfd::AsyncFileDialog::new();
+
+                let path = if folder {
+                    dialog.pick_folder().await
+                } else {
+                    dialog.add_filter(tr!("images"), crate::background::IMAGE_EXTENSIONS)
+                        .pick_file().await
+                };
+
+                if let Some(path) = path {
+                    let path = path.path().to_path_buf();
+
+                    self.background.source = if folder {
+                        BackgroundSource::Folder(path)
+                    } else {
+                        BackgroundSource::File(path)
+                    };
+
+                    self.background.current = None;
+
+                    self.update_background(&sender);
+                    self.update_crop_rows();
+                }
+            }
+
+            GeneralAppMsg::SetBackgroundScaling(index) => {
+                if let (Some(path), Some(scaling)) = (self.background_image(), BackgroundScaling::list().get(index as usize)) {
+                    self.background.images.entry(path).or_default().scaling = *scaling;
+
+                    self.update_background(&sender);
+                }
+            }
+
+            GeneralAppMsg::ToggleBackgroundCrop(enabled) => {
+                if let Some(path) = self.background_image() {
+                    let crop = self.crop_rows_value();
+
+                    self.background.images.entry(path).or_default().crop = enabled.then_some(crop);
+
+                    self.update_background(&sender);
+                }
+            }
+
+            GeneralAppMsg::SetBackgroundBlur(blur) => {
+                self.background.effects.blur = blur;
+
+                self.update_background(&sender);
+            }
+
+            GeneralAppMsg::SetBackgroundDim(dim) => {
+                self.background.effects.dim = dim;
+
+                self.update_background(&sender);
+            }
+
+            GeneralAppMsg::ToggleBackgroundVideo(enabled) => {
+                self.background.video.enabled = enabled;
+
+                self.update_background_video(&sender);
+            }
+
+            GeneralAppMsg::ChooseBackgroundVideo => {
+                let path = rfd::AsyncFileDialog::new()
+                    .add_filter(tr!("videos"), crate::background::VIDEO_EXTENSIONS)
+                    .pick_file().await;
+
+                if let Some(path) = path {
+                    self.background.video.path = Some(path.path().to_path_buf());
+
+                    self.update_background_video(&sender);
+                }
+            }
+
+            GeneralAppMsg::ResetBackgroundVideo => {
+                self.background.video.path = None;
+
+                self.update_background_video(&sender);
+            }
+
+            GeneralAppMsg::SetBackgroundVideoOnBattery(state) => {
+                self.background.video.disable_on_battery = state;
+
+                self.update_background_video(&sender);
+            }
+
+            GeneralAppMsg::UpdateBackgroundCrop => {
+                if let Some(path) = self.background_image() {
+                    let crop = self.crop_rows_value();
+
+                    self.background.images.entry(path).or_default().crop = Some(crop);
+
+                    self.update_background(&sender);
+                }
+            }
+
+            GeneralAppMsg::BackgroundProcessed(result) => {
+                match result {
+                    Ok(_) => crate::background::reload_css(),
+
+                    Err(err) => {
+                        tracing::error!("Failed to update background picture: {err}");
+
+                        sender.input(GeneralAppMsg::Toast {
+                            title: tr!("background-processing-failed"),
+                            description: Some(err)
+                        });
+                    }
+                }
+
+                // Folder source could pick its first image
+                if let Ok(settings) = Settings::get() {
+                    self.background.current = settings.background.current;
+                }
+            }
+
             GeneralAppMsg::WineOpen(executable) => {
                 let config = Config::get().unwrap_or_else(|_| CONFIG.clone());
 
-                if let Ok(Some(wine)) = config.get_selected_wine() {
+                if let Ok(Some(wine)) = crate::custom_wine::get_selected(&config) {
                     let result = wine
                         .to_wine(config.components.path, Some(config.game.wine.builds.join(&wine.name)))
                         .with_prefix(config.game.wine.prefix)
@@ -573,3 +1241,116 @@ impl SimpleAsyncComponent for GeneralApp {
         }
     }
 }
+
+impl GeneralApp {
+    /// Get path to the local image currently used as background picture
+    fn background_image(&self) -> Option<PathBuf> {
+        match &self.background.source {
+            BackgroundSource::File(path) => Some(path.clone()),
+            BackgroundSource::Folder(_) => self.background.current.clone(),
+            BackgroundSource::Official => None
+        }
+    }
+
+    fn crop_rows_value(&self) -> BackgroundCrop {
+        BackgroundCrop {
+            x: self.background_crop_x.value() as u32,
+            y: self.background_crop_y.value() as u32,
+            width: self.background_crop_width.value() as u32,
+            height: self.background_crop_height.value() as u32
+        }
+    }
+
+    /// Set crop rows values from the current background image
+    fn update_crop_rows(&self) {
+        let Some(path) = self.background_image() else {
+            return;
+        };
+
+        let crop = match self.background.image(&path).crop {
+            Some(crop) => crop,
+
+            // Use the whole image if it wasn't cropped yet
+            None => match image::image_dimensions(&path) {
+                Ok((width, height)) => BackgroundCrop {
+                    x: 0,
+                    y: 0,
+                    width,
+                    height
+                },
+
+                Err(_) => return
+            }
+        };
+
+        self.background_crop_x.set_value(crop.x as f64);
+        self.background_crop_y.set_value(crop.y as f64);
+        self.background_crop_width.set_value(crop.width as f64);
+        self.background_crop_height.set_value(crop.height as f64);
+    }
+
+    /// Save background settings, keeping the values changed by the main window
+    fn save_background(&mut self) -> anyhow::Result<()> {
+        Settings::modify(|settings| {
+            // Folder source could've been rotated by the main window
+            if settings.background.source == self.background.source && self.background.current.is_none() {
+                self.background.current.clone_from(&settings.background.current);
+            }
+
+            // Official files could've been downloaded by the main window
+            self.background.official.clone_from(&settings.background.official);
+            self.background.video.official.clone_from(&settings.background.video.official);
+
+            settings.background = self.background.clone();
+        })
+    }
+
+    /// Save background settings and make the main window restart the background video
+    fn update_background_video(&mut self, sender: &AsyncComponentSender<Self>) {
+        if let Err(err) = self.save_background() {
+            tracing::error!("Failed to save launcher settings: {err}");
+
+            sender.input(GeneralAppMsg::Toast {
+                title: tr!("config-update-error"),
+                description: Some(err.to_string())
+            });
+
+            return;
+        }
+
+        #[allow(unused_must_use)]
+        sender.output(PreferencesAppMsg::UpdateBackgroundVideo);
+    }
+
+    /// Save background settings and re-process the background picture
+    fn update_background(&mut self, sender: &AsyncComponentSender<Self>) {
+        if let Err(err) = self.save_background() {
+            tracing::error!("Failed to save launcher settings: {err}");
+
+            sender.input(GeneralAppMsg::Toast {
+                title: tr!("config-update-error"),
+                description: Some(err.to_string())
+            });
+
+            return;
+        }
+
+        if self.style == LauncherStyle::Classic {
+            Self::process_background(sender);
+        }
+    }
+
+    /// Download and process the background picture in a separate thread
+    /// so the picture's decoding doesn't block the UI
+    fn process_background(sender: &AsyncComponentSender<Self>) {
+        let size = crate::background::target_size();
+        let sender = sender.clone();
+
+        std::thread::spawn(move || {
+            let result = crate::background::update_background(false, size)
+                .map_err(|err| err.to_string());
+
+            sender.input(GeneralAppMsg::BackgroundProcessed(result));
+        });
+    }
+}
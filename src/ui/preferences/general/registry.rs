@@ -0,0 +1,284 @@
+use relm4::prelude::*;
+use gtk::prelude::*;
+use adw::prelude::*;
+
+use super::GeneralAppMsg;
+
+use crate::settings::prelude::*;
+use crate::*;
+
+pub struct RegistryPage {
+    tweaks: Vec<RegistryTweak>,
+
+    tweaks_group: adw::PreferencesGroup,
+    tweaks_rows: Vec<adw::ActionRow>,
+
+    key_entry: adw::EntryRow,
+    name_entry: adw::EntryRow,
+    kind_row: adw::ComboRow,
+    data_entry: adw::EntryRow
+}
+
+#[derive(Debug, Clone)]
+pub enum RegistryPageMsg {
+    UpdateTweaks,
+
+    Add,
+    Toggle(usize, bool),
+    Remove(usize)
+}
+
+impl RegistryPage {
+    fn update_tweaks_rows(&mut self, sender: &AsyncComponentSender<Self>) {
+        for row in self.tweaks_rows.drain(..) {
+            self.tweaks_group.remove(&row);
+        }
+
+        let prefix = Config::get()
+            .map(|config| config.game.wine.prefix)
+            .unwrap_or_else(|_| CONFIG.game.wine.prefix.clone());
+
+        for (i, tweak) in self.tweaks.iter().enumerate() {
+            let row = adw::ActionRow::new();
+
+            row.set_title(&if tweak.name.is_empty() {
+                tr!("registry-default-value")
+            } else {
+                tweak.name.clone()
+            });
+
+            let mut subtitle = format!("{} · {} = \"{}\"", tweak.key, tweak.kind.reg_type(), tweak.data);
+
+            if !crate::registry::is_applied(&prefix, tweak) {
+                subtitle = format!("{subtitle} · {}", tr!("registry-tweak-not-applied"));
+            }
+
+            row.set_subtitle(&subtitle);
+
+            let switch = gtk::Switch::new();
+
+            switch.set_active(tweak.enabled);
+            switch.set_valign(gtk::Align::Center);
+
+            let switch_sender = sender.clone();
+
+            switch.connect_state_notify(move |switch| {
+                switch_sender.input(RegistryPageMsg::Toggle(i, switch.is_active()));
+            });
+
+            let button = gtk::Button::new();
+
+            button.set_icon_name("user-trash-symbolic");
+            button.set_tooltip_text(Some(&tr!("remove-registry-tweak")));
+            button.set_valign(gtk::Align::Center);
+            button.add_css_class("flat");
+
+            let button_sender = sender.clone();
+
+            button.connect_clicked(move |_| {
+                button_sender.input(RegistryPageMsg::Remove(i));
+            });
+
+            row.add_suffix(&switch);
+            row.add_suffix(&button);
+
+            self.tweaks_group.add(&row);
+            self.tweaks_rows.push(row);
+        }
+    }
+
+    fn save(&self) -> anyhow::Result<()> {
+        Settings::modify(|settings| settings.registry.tweaks = self.tweaks.clone())
+    }
+}
+
+#[relm4::component(async, pub)]
+impl SimpleAsyncComponent for RegistryPage {
+    type Init = ();
+    type Input = RegistryPageMsg;
+    type Output = GeneralAppMsg;
+
+    view! {
+        adw::NavigationPage {
+            #[wrap(Some)]
+            set_child = &gtk::Box {
+                set_orientation: gtk::Orientation::Vertical,
+
+                adw::HeaderBar {
+                    #[wrap(Some)]
+                    set_title_widget = &adw::WindowTitle {
+                        set_title: &tr!("registry-tweaks")
+                    }
+                },
+
+                adw::PreferencesPage {
+                    #[local_ref]
+                    add = tweaks_group -> adw::PreferencesGroup {
+                        set_title: &tr!("registry-tweaks"),
+                        set_description: Some(&tr!("registry-tweaks-list-description"))
+                    },
+
+                    add = &adw::PreferencesGroup {
+                        set_title: &tr!("new-registry-tweak"),
+
+                        #[wrap(Some)]
+                        set_header_suffix = &gtk::Button {
+                            add_css_class: "flat",
+
+                            set_valign: gtk::Align::Center,
+
+                            adw::ButtonContent {
+                                set_icon_name: "list-add-symbolic",
+                                set_label: &tr!("add")
+                            },
+
+                            connect_clicked => RegistryPageMsg::Add
+                        },
+
+                        #[local_ref]
+                        key_entry -> adw::EntryRow {
+                            set_title: &tr!("registry-key")
+                        },
+
+                        #[local_ref]
+                        name_entry -> adw::EntryRow {
+                            set_title: &tr!("registry-value-name")
+                        },
+
+                        #[local_ref]
+                        kind_row -> adw::ComboRow {
+                            set_title: &tr!("registry-value-type"),
+
+                            #[wrap(Some)]
+                            set_model = &gtk::StringList::new(&RegistryValueKind::list()
+                                .iter()
+                                .map(|kind| kind.reg_type())
+                                .collect::<Vec<_>>())
+                        },
+
+                        #[local_ref]
+                        data_entry -> adw::EntryRow {
+                            set_title: &tr!("registry-value-data")
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    async fn init(_init: Self::Init, root: Self::Root, sender: AsyncComponentSender<Self>) -> AsyncComponentParts<Self> {
+        tracing::info!("Initializing general settings -> registry tweaks page");
+
+        let mut model = Self {
+            tweaks: Settings::get()
+                .map(|settings| settings.registry.tweaks)
+                .unwrap_or_default(),
+
+            tweaks_group: adw::PreferencesGroup::new(),
+            tweaks_rows: Vec::new(),
+
+            key_entry: adw::EntryRow::new(),
+            name_entry: adw::EntryRow::new(),
+            kind_row: adw::ComboRow::new(),
+            data_entry: adw::EntryRow::new()
+        };
+
+        let tweaks_group = &model.tweaks_group;
+
+        let key_entry = &model.key_entry;
+        let name_entry = &model.name_entry;
+        let kind_row = &model.kind_row;
+        let data_entry = &model.data_entry;
+
+        let widgets = view_output!();
+
+        model.update_tweaks_rows(&sender);
+
+        AsyncComponentParts { model, widgets }
+    }
+
+    async fn update(&mut self, msg: Self::Input, sender: AsyncComponentSender<Self>) {
+        match msg {
+            RegistryPageMsg::UpdateTweaks => {
+                self.tweaks = Settings::get()
+                    .map(|settings| settings.registry.tweaks)
+                    .unwrap_or_default();
+
+                self.update_tweaks_rows(&sender);
+            }
+
+            RegistryPageMsg::Add => {
+                let key = self.key_entry.text().trim().to_string();
+                let name = self.name_entry.text().trim().to_string();
+                let data = self.data_entry.text().trim().to_string();
+
+                let Some(kind) = RegistryValueKind::list().get(self.kind_row.selected() as usize).copied() else {
+                    return;
+                };
+
+                if key.is_empty() {
+                    return;
+                }
+
+                if kind == RegistryValueKind::Dword && crate::registry::parse_dword(&data).is_none() {
+                    sender.output(GeneralAppMsg::Toast {
+                        title: tr!("registry-invalid-dword"),
+                        description: Some(data)
+                    }).unwrap();
+
+                    return;
+                }
+
+                // Replace the tweak for the same value if it exists
+                self.tweaks.retain(|tweak| !(tweak.key.eq_ignore_ascii_case(&key) && tweak.name.eq_ignore_ascii_case(&name)));
+
+                self.tweaks.push(RegistryTweak {
+                    key,
+                    name,
+                    kind,
+                    data,
+                    enabled: true
+                });
+
+                if let Err(err) = self.save() {
+                    tracing::error!("Failed to save registry tweaks: {err}");
+
+                    sender.output(GeneralAppMsg::Toast {
+                        title: tr!("registry-tweaks-failed"),
+                        description: Some(err.to_string())
+                    }).unwrap();
+
+                    return;
+                }
+
+                self.key_entry.set_text("");
+                self.name_entry.set_text("");
+                self.data_entry.set_text("");
+
+                self.update_tweaks_rows(&sender);
+            }
+
+            RegistryPageMsg::Toggle(index, enabled) => {
+                if let Some(tweak) = self.tweaks.get_mut(index) {
+                    tweak.enabled = enabled;
+
+                    if let Err(err) = self.save() {
+                        tracing::error!("Failed to save registry tweaks: {err}");
+                    }
+                }
+            }
+
+            RegistryPageMsg::Remove(index) => {
+                if index < self.tweaks.len() {
+                    self.tweaks.remove(index);
+
+                    if let Err(err) = self.save() {
+                        tracing::error!("Failed to save registry tweaks: {err}");
+                    }
+
+                    self.update_tweaks_rows(&sender);
+                }
+            }
+        }
+    }
+}
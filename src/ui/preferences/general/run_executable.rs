@@ -0,0 +1,268 @@
+use std::path::PathBuf;
+
+use relm4::prelude::*;
+use gtk::prelude::*;
+use adw::prelude::*;
+
+use super::GeneralAppMsg;
+
+use crate::*;
+
+pub struct RunExecutablePage {
+    executable: Option<PathBuf>,
+    arguments: String,
+    working_dir: Option<PathBuf>,
+
+    output: gtk::TextBuffer,
+
+    running: bool
+}
+
+#[derive(Debug, Clone)]
+pub enum RunExecutablePageMsg {
+    ChooseExecutable,
+    SetArguments(String),
+    ChooseWorkingDir,
+    ResetWorkingDir,
+
+    Run,
+
+    /// Line of the executable's output
+    Output(String),
+
+    /// Toast title or error message
+    Finished(Result<String, String>)
+}
+
+#[relm4::component(async, pub)]
+impl SimpleAsyncComponent for RunExecutablePage {
+    type Init = ();
+    type Input = RunExecutablePageMsg;
+    type Output = GeneralAppMsg;
+
+    view! {
+        adw::NavigationPage {
+            #[wrap(Some)]
+            set_child = &gtk::Box {
+                set_orientation: gtk::Orientation::Vertical,
+
+                adw::HeaderBar {
+                    #[wrap(Some)]
+                    set_title_widget = &adw::WindowTitle {
+                        set_title: &tr!("run-executable")
+                    }
+                },
+
+                adw::PreferencesPage {
+                    add = &adw::PreferencesGroup {
+                        set_description: Some(&tr!("run-executable-description")),
+
+                        #[watch]
+                        set_sensitive: !model.running,
+
+                        adw::ActionRow {
+                            set_title: &tr!("executable"),
+
+                            #[watch]
+                            set_subtitle: &model.executable.as_ref()
+                                .map(|path| path.to_string_lossy().to_string())
+                                .unwrap_or_else(|| tr!("executable-not-chosen")),
+
+                            add_suffix = &gtk::Button {
+                                set_icon_name: "document-open-symbolic",
+                                add_css_class: "flat",
+                                set_valign: gtk::Align::Center,
+
+                                connect_clicked => RunExecutablePageMsg::ChooseExecutable
+                            }
+                        },
+
+                        adw::EntryRow {
+                            set_title: &tr!("executable-arguments"),
+
+                            connect_changed[sender] => move |row| {
+                                sender.input(RunExecutablePageMsg::SetArguments(row.text().to_string()));
+                            }
+                        },
+
+                        adw::ActionRow {
+                            set_title: &tr!("working-directory"),
+
+                            #[watch]
+                            set_subtitle: &model.working_dir.as_ref()
+                                .map(|path| path.to_string_lossy().to_string())
+                                .unwrap_or_else(|| tr!("working-directory-default")),
+
+                            add_suffix = &gtk::Button {
+                                set_icon_name: "edit-clear-symbolic",
+                                add_css_class: "flat",
+                                set_valign: gtk::Align::Center,
+
+                                #[watch]
+                                set_visible: model.working_dir.is_some(),
+
+                                connect_clicked => RunExecutablePageMsg::ResetWorkingDir
+                            },
+
+                            add_suffix = &gtk::Button {
+                                set_icon_name: "folder-open-symbolic",
+                                add_css_class: "flat",
+                                set_valign: gtk::Align::Center,
+
+                                connect_clicked => RunExecutablePageMsg::ChooseWorkingDir
+                            }
+                        }
+                    },
+
+                    add = &adw::PreferencesGroup {
+                        gtk::Button {
+                            set_label: &tr!("run"),
+                            set_halign: gtk::Align::Center,
+
+                            add_css_class: "pill",
+                            add_css_class: "suggested-action",
+
+                            #[watch]
+                            set_sensitive: !model.running && model.executable.is_some(),
+
+                            connect_clicked => RunExecutablePageMsg::Run
+                        }
+                    },
+
+                    add = &adw::PreferencesGroup {
+                        set_title: &tr!("executable-output"),
+
+                        #[wrap(Some)]
+                        set_header_suffix = &gtk::Spinner {
+                            #[watch]
+                            set_spinning: model.running,
+
+                            #[watch]
+                            set_visible: model.running
+                        },
+
+                        gtk::ScrolledWindow {
+                            set_height_request: 320,
+                            add_css_class: "card",
+
+                            gtk::TextView {
+                                set_buffer: Some(&model.output),
+
+                                set_editable: false,
+                                set_cursor_visible: false,
+                                set_monospace: true,
+                                set_wrap_mode: gtk::WrapMode::WordChar,
+
+                                set_top_margin: 8,
+                                set_bottom_margin: 8,
+                                set_left_margin: 8,
+                                set_right_margin: 8
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    async fn init(_init: Self::Init, root: Self::Root, sender: AsyncComponentSender<Self>) -> AsyncComponentParts<Self> {
+        tracing::info!("Initializing general settings -> run executable page");
+
+        let model = Self {
+            executable: None,
+            arguments: String::new(),
+            working_dir: None,
+
+            output: gtk::TextBuffer::new(None),
+
+            running: false
+        };
+
+        let widgets = view_output!();
+
+        AsyncComponentParts { model, widgets }
+    }
+
+    async fn update(&mut self, msg: Self::Input, sender: AsyncComponentSender<Self>) {
+        match msg {
+            RunExecutablePageMsg::ChooseExecutable => {
+                let file = rfd::AsyncFileDialog::new()
+                    .add_filter(tr!("windows-executables"), &["exe"])
+                    .pick_file().await;
+
+                if let Some(file) = file {
+                    self.executable = Some(file.path().to_path_buf());
+                }
+            }
+
+            RunExecutablePageMsg::SetArguments(arguments) => self.arguments = arguments,
+
+            RunExecutablePageMsg::ChooseWorkingDir => {
+                if let Some(folder) = rfd::AsyncFileDialog::new().pick_folder().await {
+                    self.working_dir = Some(folder.path().to_path_buf());
+                }
+            }
+
+            RunExecutablePageMsg::ResetWorkingDir => self.working_dir = None,
+
+            RunExecutablePageMsg::Run => {
+                let Some(executable) = self.executable.clone() else {
+                    return;
+                };
+
+                if self.running {
+                    return;
+                }
+
+                self.running = true;
+                self.output.set_text("");
+
+                let arguments = self.arguments.clone();
+                let working_dir = self.working_dir.clone();
+
+                std::thread::spawn(move || {
+                    let output_sender = sender.clone();
+
+                    let name = executable.file_name()
+                        .map(|name| name.to_string_lossy().to_string())
+                        .unwrap_or_default();
+
+                    let result = crate::wine_run::run(&executable, arguments, working_dir, move |line| output_sender.input(RunExecutablePageMsg::Output(line)))
+                        .map(|status| tr!("executable-finished", {
+                            "executable" = name.clone(),
+                            "code" = status.code().unwrap_or(-1)
+                        }))
+                        .map_err(|err| format!("{name}: {err}"));
+
+                    sender.input(RunExecutablePageMsg::Finished(result));
+                });
+            }
+
+            RunExecutablePageMsg::Output(line) => {
+                self.output.insert(&mut self.output.end_iter(), &format!("{line}\n"));
+            }
+
+            RunExecutablePageMsg::Finished(result) => {
+                self.running = false;
+
+                let toast = match result {
+                    Ok(title) => GeneralAppMsg::Toast {
+                        title,
+                        description: None
+                    },
+
+                    Err(err) => {
+                        tracing::error!("Failed to run executable: {err}");
+
+                        GeneralAppMsg::Toast {
+                            title: tr!("executable-run-failed"),
+                            description: Some(err)
+                        }
+                    }
+                };
+
+                sender.output(toast).unwrap();
+            }
+        }
+    }
+}
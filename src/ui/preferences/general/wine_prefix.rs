@@ -0,0 +1,497 @@
+use relm4::prelude::*;
+use gtk::prelude::*;
+use adw::prelude::*;
+
+use super::GeneralAppMsg;
+
+use crate::snapshots::{self, Snapshot, SnapshotFormat};
+use crate::prefix_health::{self, HealthIssue};
+use crate::settings::prelude::*;
+use crate::*;
+
+pub struct WinePrefixPage {
+    /// Found prefix issues, or none if not checked yet
+    issues: Option<Vec<HealthIssue>>,
+
+    health_group: adw::PreferencesGroup,
+    health_rows: Vec<adw::ActionRow>,
+
+    checking_health: bool,
+
+    /// Snapshots with their sizes
+    snapshots: Vec<(Snapshot, u64)>,
+
+    snapshots_group: adw::PreferencesGroup,
+    snapshots_rows: Vec<adw::ActionRow>,
+
+    processing: bool
+}
+
+#[derive(Debug, Clone)]
+pub enum WinePrefixPageMsg {
+    CheckHealth,
+
+    /// Found issues or error message
+    HealthChecked(Result<Vec<HealthIssue>, String>),
+
+    RebuildPrefix,
+
+    /// Toast title or error message
+    RebuildFinished(Result<String, String>),
+
+    UpdateSnapshots,
+    SnapshotsUpdated(Vec<(Snapshot, u64)>),
+
+    CreateSnapshot(String),
+    RestoreSnapshot(Snapshot),
+    RemoveSnapshot(Snapshot),
+
+    /// Toast title or error message
+    Finished(Result<String, String>),
+
+    SetAutomatic(bool),
+    SetKeepAutomatic(usize)
+}
+
+impl WinePrefixPage {
+    fn update_health_rows(&mut self) {
+        for row in self.health_rows.drain(..) {
+            self.health_group.remove(&row);
+        }
+
+        let Some(issues) = &self.issues else {
+            return;
+        };
+
+        if issues.is_empty() {
+            let row = adw::ActionRow::new();
+
+            row.set_title(&tr!("wine-prefix-healthy"));
+            row.add_prefix(&gtk::Image::from_icon_name("emblem-ok-symbolic"));
+
+            self.health_group.add(&row);
+            self.health_rows.push(row);
+        }
+
+        for issue in issues {
+            let row = adw::ActionRow::new();
+
+            row.set_title(&match issue {
+                HealthIssue::PrefixNotExists => tr!("wine-prefix-not-exists"),
+                HealthIssue::WineMismatch { .. } => tr!("wine-prefix-wine-mismatch"),
+                HealthIssue::DxvkMissing(_) => tr!("wine-prefix-dxvk-missing"),
+                HealthIssue::RegistryBroken { .. } => tr!("wine-prefix-registry-broken"),
+                HealthIssue::DriveMapping { .. } => tr!("wine-prefix-drive-mapping")
+            });
+
+            row.set_subtitle(&issue.to_string());
+            row.add_prefix(&gtk::Image::from_icon_name("dialog-warning-symbolic"));
+
+            self.health_group.add(&row);
+            self.health_rows.push(row);
+        }
+    }
+
+    fn update_rows(&mut self, sender: &AsyncComponentSender<Self>) {
+        for row in self.snapshots_rows.drain(..) {
+            self.snapshots_group.remove(&row);
+        }
+
+        for (snapshot, size) in &self.snapshots {
+            let row = adw::ActionRow::new();
+
+            row.set_title(&if snapshot.automatic {
+                tr!("prefix-snapshot-automatic", {
+                    "component" = snapshot.name.clone()
+                })
+            } else {
+                snapshot.name.clone()
+            });
+
+            let time = gtk::glib::DateTime::from_unix_local(snapshot.created_at as i64)
+                .and_then(|time| time.format("%x %X"))
+                .map(|time| time.to_string())
+                .unwrap_or_default();
+
+            let format = match snapshot.format {
+                SnapshotFormat::Copy => tr!("prefix-snapshot-copy"),
+                SnapshotFormat::Archive => tr!("prefix-snapshot-archive")
+            };
+
+            row.set_subtitle(&format!("{time} · {} · {format}", prettify_bytes(*size)));
+
+            let buttons = [
+                ("edit-undo-symbolic", tr!("restore-prefix-snapshot"), WinePrefixPageMsg::RestoreSnapshot(snapshot.clone())),
+                ("user-trash-symbolic", tr!("remove-prefix-snapshot"), WinePrefixPageMsg::RemoveSnapshot(snapshot.clone()))
+            ];
+
+            for (icon, tooltip, msg) in buttons {
+                let button = gtk::Button::new();
+
+                button.set_icon_name(icon);
+                button.set_tooltip_text(Some(&tooltip));
+                button.set_valign(gtk::Align::Center);
+                button.add_css_class("flat");
+
+                let sender = sender.clone();
+
+                button.connect_clicked(move |_| {
+                    sender.input(msg.clone());
+                });
+
+                row.add_suffix(&button);
+            }
+
+            self.snapshots_group.add(&row);
+            self.snapshots_rows.push(row);
+        }
+    }
+}
+
+#[relm4::component(async, pub)]
+impl SimpleAsyncComponent for WinePrefixPage {
+    type Init = ();
+    type Input = WinePrefixPageMsg;
+    type Output = GeneralAppMsg;
+
+    view! {
+        adw::NavigationPage {
+            #[wrap(Some)]
+            set_child = &gtk::Box {
+                set_orientation: gtk::Orientation::Vertical,
+
+                adw::HeaderBar {
+                    #[wrap(Some)]
+                    set_title_widget = &adw::WindowTitle {
+                        set_title: &tr!("wine-prefix")
+                    }
+                },
+
+                adw::PreferencesPage {
+                    #[local_ref]
+                    add = health_group -> adw::PreferencesGroup {
+                        set_title: &tr!("wine-prefix-health"),
+
+                        #[watch]
+                        set_sensitive: !model.checking_health,
+
+                        #[wrap(Some)]
+                        set_header_suffix = &gtk::Box {
+                            set_spacing: 8,
+
+                            gtk::Spinner {
+                                #[watch]
+                                set_spinning: model.checking_health,
+
+                                #[watch]
+                                set_visible: model.checking_health
+                            },
+
+                            gtk::Button {
+                                set_icon_name: "view-refresh-symbolic",
+                                set_tooltip_text: Some(&tr!("check-wine-prefix")),
+                                add_css_class: "flat",
+
+                                connect_clicked => WinePrefixPageMsg::CheckHealth
+                            }
+                        },
+
+                        adw::ActionRow {
+                            set_title: &tr!("rebuild-wine-prefix"),
+                            set_subtitle: &tr!("rebuild-wine-prefix-description"),
+
+                            add_suffix = &gtk::Button {
+                                set_label: &tr!("rebuild"),
+                                set_valign: gtk::Align::Center,
+
+                                add_css_class: "destructive-action",
+
+                                connect_clicked => WinePrefixPageMsg::RebuildPrefix
+                            }
+                        }
+                    },
+
+                    #[local_ref]
+                    add = snapshots_group -> adw::PreferencesGroup {
+                        set_title: &tr!("prefix-snapshots"),
+                        set_description: Some(&tr!("prefix-snapshots-description")),
+
+                        #[watch]
+                        set_sensitive: !model.processing,
+
+                        #[wrap(Some)]
+                        set_header_suffix = &gtk::Spinner {
+                            #[watch]
+                            set_spinning: model.processing,
+
+                            #[watch]
+                            set_visible: model.processing
+                        },
+
+                        adw::EntryRow {
+                            set_title: &tr!("create-prefix-snapshot"),
+                            set_show_apply_button: true,
+
+                            connect_apply[sender] => move |row| {
+                                sender.input(WinePrefixPageMsg::CreateSnapshot(row.text().to_string()));
+
+                                row.set_text("");
+                            }
+                        }
+                    },
+
+                    add = &adw::PreferencesGroup {
+                        adw::ActionRow {
+                            set_title: &tr!("automatic-prefix-snapshots"),
+                            set_subtitle: &tr!("automatic-prefix-snapshots-description"),
+
+                            add_suffix = &gtk::Switch {
+                                set_valign: gtk::Align::Center,
+
+                                set_active: Settings::get()
+                                    .map(|settings| settings.prefix_snapshots.automatic)
+                                    .unwrap_or(true),
+
+                                connect_state_notify[sender] => move |switch| {
+                                    sender.input(WinePrefixPageMsg::SetAutomatic(switch.is_active()));
+                                }
+                            }
+                        },
+
+                        adw::SpinRow {
+                            set_title: &tr!("keep-automatic-prefix-snapshots"),
+
+                            set_adjustment: Some(&gtk::Adjustment::new(1.0, 1.0, 20.0, 1.0, 1.0, 0.0)),
+
+                            set_value: Settings::get()
+                                .map(|settings| settings.prefix_snapshots.keep_automatic)
+                                .unwrap_or(3) as f64,
+
+                            connect_changed[sender] => move |row| {
+                                sender.input(WinePrefixPageMsg::SetKeepAutomatic(row.value() as usize));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    async fn init(_init: Self::Init, root: Self::Root, sender: AsyncComponentSender<Self>) -> AsyncComponentParts<Self> {
+        tracing::info!("Initializing general settings -> wine prefix page");
+
+        let model = Self {
+            issues: None,
+
+            health_group: adw::PreferencesGroup::new(),
+            health_rows: Vec::new(),
+
+            checking_health: false,
+
+            snapshots: Vec::new(),
+
+            snapshots_group: adw::PreferencesGroup::new(),
+            snapshots_rows: Vec::new(),
+
+            processing: false
+        };
+
+        let health_group = &model.health_group;
+        let snapshots_group = &model.snapshots_group;
+
+        let widgets = view_output!();
+
+        sender.input(WinePrefixPageMsg::UpdateSnapshots);
+
+        AsyncComponentParts { model, widgets }
+    }
+
+    async fn update(&mut self, msg: Self::Input, sender: AsyncComponentSender<Self>) {
+        tracing::debug!("Called wine prefix settings event: {:?}", msg);
+
+        match msg {
+            WinePrefixPageMsg::CheckHealth => {
+                self.checking_health = true;
+
+                std::thread::spawn(move || {
+                    let result = prefix_health::check()
+                        .map_err(|err| err.to_string());
+
+                    sender.input(WinePrefixPageMsg::HealthChecked(result));
+                });
+            }
+
+            WinePrefixPageMsg::HealthChecked(result) => {
+                self.checking_health = false;
+
+                match result {
+                    Ok(issues) => {
+                        self.issues = Some(issues);
+
+                        self.update_health_rows();
+                    }
+
+                    Err(err) => {
+                        tracing::error!("Failed to check wine prefix: {err}");
+
+                        sender.output(GeneralAppMsg::Toast {
+                            title: tr!("wine-prefix-check-failed"),
+                            description: Some(err)
+                        }).unwrap();
+                    }
+                }
+            }
+
+            WinePrefixPageMsg::RebuildPrefix => {
+                self.checking_health = true;
+
+                std::thread::spawn(move || {
+                    if let Err(err) = snapshots::create_automatic("prefix") {
+                        sender.output(GeneralAppMsg::Toast {
+                            title: tr!("automatic-prefix-snapshot-failed"),
+                            description: Some(err.to_string())
+                        }).unwrap();
+                    }
+
+                    let result = prefix_health::rebuild()
+                        .map(|_| tr!("wine-prefix-rebuilt"))
+                        .map_err(|err| err.to_string());
+
+                    sender.input(WinePrefixPageMsg::RebuildFinished(result));
+                });
+            }
+
+            WinePrefixPageMsg::RebuildFinished(result) => {
+                let toast = match result {
+                    Ok(title) => GeneralAppMsg::Toast {
+                        title,
+                        description: None
+                    },
+
+                    Err(err) => {
+                        tracing::error!("Failed to rebuild wine prefix: {err}");
+
+                        GeneralAppMsg::Toast {
+                            title: tr!("wine-prefix-rebuild-failed"),
+                            description: Some(err)
+                        }
+                    }
+                };
+
+                sender.input(WinePrefixPageMsg::CheckHealth);
+                sender.input(WinePrefixPageMsg::UpdateSnapshots);
+
+                sender.output(toast).unwrap();
+            }
+
+            WinePrefixPageMsg::UpdateSnapshots => {
+                self.processing = true;
+
+                std::thread::spawn(move || {
+                    let snapshots = snapshots::list()
+                        .unwrap_or_else(|err| {
+                            tracing::error!("Failed to list wine prefix snapshots: {err}");
+
+                            vec![]
+                        })
+                        .into_iter()
+                        .map(|snapshot| {
+                            let size = snapshot.size();
+
+                            (snapshot, size)
+                        })
+                        .collect();
+
+                    sender.input(WinePrefixPageMsg::SnapshotsUpdated(snapshots));
+                });
+            }
+
+            WinePrefixPageMsg::SnapshotsUpdated(snapshots) => {
+                self.processing = false;
+                self.snapshots = snapshots;
+
+                self.update_rows(&sender);
+            }
+
+            WinePrefixPageMsg::CreateSnapshot(name) => {
+                let name = name.trim().to_string();
+
+                if name.is_empty() {
+                    return;
+                }
+
+                self.processing = true;
+
+                std::thread::spawn(move || {
+                    let result = snapshots::create(name, false)
+                        .map(|_| tr!("prefix-snapshot-created"))
+                        .map_err(|err| err.to_string());
+
+                    sender.input(WinePrefixPageMsg::Finished(result));
+                });
+            }
+
+            WinePrefixPageMsg::RestoreSnapshot(snapshot) => {
+                self.processing = true;
+
+                std::thread::spawn(move || {
+                    let result = snapshots::restore(&snapshot)
+                        .map(|_| tr!("prefix-snapshot-restored"))
+                        .map_err(|err| err.to_string());
+
+                    sender.input(WinePrefixPageMsg::Finished(result));
+                });
+            }
+
+            WinePrefixPageMsg::RemoveSnapshot(snapshot) => {
+                self.processing = true;
+
+                std::thread::spawn(move || {
+                    let result = snapshots::remove(&snapshot)
+                        .map(|_| tr!("prefix-snapshot-removed"))
+                        .map_err(|err| err.to_string());
+
+                    sender.input(WinePrefixPageMsg::Finished(result));
+                });
+            }
+
+            WinePrefixPageMsg::Finished(result) => {
+                let toast = match result {
+                    Ok(title) => GeneralAppMsg::Toast {
+                        title,
+                        description: None
+                    },
+
+                    Err(err) => {
+                        tracing::error!("Failed to process wine prefix snapshot: {err}");
+
+                        GeneralAppMsg::Toast {
+                            title: tr!("prefix-snapshot-failed"),
+                            description: Some(err)
+                        }
+                    }
+                };
+
+                sender.input(WinePrefixPageMsg::UpdateSnapshots);
+
+                sender.output(toast).unwrap();
+            }
+
+            WinePrefixPageMsg::SetAutomatic(automatic) => {
+                let result = Settings::modify(|settings| settings.prefix_snapshots.automatic = automatic);
+
+                if let Err(err) = result {
+                    tracing::error!("Failed to save prefix snapshots settings: {err}");
+                }
+            }
+
+            WinePrefixPageMsg::SetKeepAutomatic(keep) => {
+                let result = Settings::modify(|settings| settings.prefix_snapshots.keep_automatic = keep);
+
+                if let Err(err) = result {
+                    tracing::error!("Failed to save prefix snapshots settings: {err}");
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,284 @@
+use relm4::prelude::*;
+use gtk::prelude::*;
+use adw::prelude::*;
+
+use super::GeneralAppMsg;
+
+use crate::winetricks::{self, VerbCategory, COMMON_VERBS};
+use crate::settings::prelude::*;
+use crate::*;
+
+pub struct WinetricksPage {
+    installed: Vec<String>,
+
+    verbs_group: adw::PreferencesGroup,
+    verbs_rows: Vec<adw::ActionRow>,
+
+    output: gtk::TextBuffer,
+
+    /// Verb which is being installed now
+    installing: Option<String>
+}
+
+#[derive(Debug, Clone)]
+pub enum WinetricksPageMsg {
+    InstallVerb(String),
+    ReapplyVerbs,
+
+    /// Line of the winetricks output
+    Output(String),
+
+    /// Toast title or error message
+    Finished(Result<String, String>)
+}
+
+impl WinetricksPage {
+    fn update_rows(&mut self, sender: &AsyncComponentSender<Self>) {
+        for row in self.verbs_rows.drain(..) {
+            self.verbs_group.remove(&row);
+        }
+
+        let custom = self.installed.iter()
+            .filter(|verb| !COMMON_VERBS.iter().any(|(common, _)| common == verb))
+            .map(|verb| (verb.as_str(), None));
+
+        let verbs = COMMON_VERBS.iter()
+            .map(|(verb, category)| (*verb, Some(*category)))
+            .chain(custom);
+
+        for (verb, category) in verbs {
+            let row = adw::ActionRow::new();
+
+            row.set_title(verb);
+
+            row.set_subtitle(&match category {
+                Some(VerbCategory::Fonts) => tr!("winetricks-fonts"),
+                Some(VerbCategory::Runtime) => tr!("winetricks-runtime"),
+                Some(VerbCategory::DirectX) => tr!("winetricks-directx"),
+                None => tr!("winetricks-custom")
+            });
+
+            if self.installed.iter().any(|installed| installed == verb) {
+                row.add_suffix(&gtk::Image::from_icon_name("emblem-ok-symbolic"));
+            }
+
+            else {
+                let button = gtk::Button::new();
+
+                button.set_icon_name("document-save-symbolic");
+                button.set_tooltip_text(Some(&tr!("install-winetricks-verb")));
+                button.set_valign(gtk::Align::Center);
+                button.add_css_class("flat");
+
+                let sender = sender.clone();
+                let verb = verb.to_string();
+
+                button.connect_clicked(move |_| {
+                    sender.input(WinetricksPageMsg::InstallVerb(verb.clone()));
+                });
+
+                row.add_suffix(&button);
+            }
+
+            self.verbs_group.add(&row);
+            self.verbs_rows.push(row);
+        }
+    }
+}
+
+#[relm4::component(async, pub)]
+impl SimpleAsyncComponent for WinetricksPage {
+    type Init = ();
+    type Input = WinetricksPageMsg;
+    type Output = GeneralAppMsg;
+
+    view! {
+        adw::NavigationPage {
+            #[wrap(Some)]
+            set_child = &gtk::Box {
+                set_orientation: gtk::Orientation::Vertical,
+
+                adw::HeaderBar {
+                    #[wrap(Some)]
+                    set_title_widget = &adw::WindowTitle {
+                        set_title: "Winetricks"
+                    }
+                },
+
+                adw::PreferencesPage {
+                    #[local_ref]
+                    add = verbs_group -> adw::PreferencesGroup {
+                        set_title: &tr!("winetricks-verbs"),
+                        set_description: Some(&tr!("winetricks-verbs-description")),
+
+                        #[watch]
+                        set_sensitive: model.installing.is_none(),
+
+                        adw::EntryRow {
+                            set_title: &tr!("winetricks-verb-name"),
+                            set_show_apply_button: true,
+
+                            connect_apply[sender] => move |row| {
+                                sender.input(WinetricksPageMsg::InstallVerb(row.text().trim().to_string()));
+
+                                row.set_text("");
+                            }
+                        },
+
+                        adw::ActionRow {
+                            set_title: &tr!("reapply-winetricks-verbs"),
+                            set_subtitle: &tr!("reapply-winetricks-verbs-description"),
+
+                            #[watch]
+                            set_visible: !model.installed.is_empty(),
+
+                            add_suffix = &gtk::Button {
+                                set_icon_name: "view-refresh-symbolic",
+                                add_css_class: "flat",
+                                set_valign: gtk::Align::Center,
+
+                                connect_clicked => WinetricksPageMsg::ReapplyVerbs
+                            }
+                        }
+                    },
+
+                    add = &adw::PreferencesGroup {
+                        set_title: &tr!("winetricks-output"),
+
+                        #[watch]
+                        set_description: model.installing.as_ref()
+                            .map(|verb| tr!("installing-winetricks-verb", { "verb" = verb.clone() }))
+                            .as_deref(),
+
+                        #[wrap(Some)]
+                        set_header_suffix = &gtk::Spinner {
+                            #[watch]
+                            set_spinning: model.installing.is_some(),
+
+                            #[watch]
+                            set_visible: model.installing.is_some()
+                        },
+
+                        gtk::ScrolledWindow {
+                            set_height_request: 240,
+                            add_css_class: "card",
+
+                            gtk::TextView {
+                                set_buffer: Some(&model.output),
+
+                                set_editable: false,
+                                set_cursor_visible: false,
+                                set_monospace: true,
+                                set_wrap_mode: gtk::WrapMode::WordChar,
+
+                                set_top_margin: 8,
+                                set_bottom_margin: 8,
+                                set_left_margin: 8,
+                                set_right_margin: 8
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    async fn init(_init: Self::Init, root: Self::Root, sender: AsyncComponentSender<Self>) -> AsyncComponentParts<Self> {
+        tracing::info!("Initializing general settings -> winetricks page");
+
+        let mut model = Self {
+            installed: Settings::get()
+                .map(|settings| settings.winetricks)
+                .unwrap_or_default(),
+
+            verbs_group: adw::PreferencesGroup::new(),
+            verbs_rows: Vec::new(),
+
+            output: gtk::TextBuffer::new(None),
+
+            installing: None
+        };
+
+        let verbs_group = &model.verbs_group;
+
+        let widgets = view_output!();
+
+        model.update_rows(&sender);
+
+        AsyncComponentParts { model, widgets }
+    }
+
+    async fn update(&mut self, msg: Self::Input, sender: AsyncComponentSender<Self>) {
+        match msg {
+            WinetricksPageMsg::InstallVerb(verb) => {
+                if !winetricks::is_valid_verb(&verb) || self.installing.is_some() {
+                    return;
+                }
+
+                self.installing = Some(verb.clone());
+                self.output.set_text("");
+
+                std::thread::spawn(move || {
+                    let output_sender = sender.clone();
+
+                    let result = winetricks::install(&verb, move |line| output_sender.input(WinetricksPageMsg::Output(line)))
+                        .map(|_| tr!("winetricks-verb-installed", { "verb" = verb }))
+                        .map_err(|err| err.to_string());
+
+                    sender.input(WinetricksPageMsg::Finished(result));
+                });
+            }
+
+            WinetricksPageMsg::ReapplyVerbs => {
+                if self.installing.is_some() {
+                    return;
+                }
+
+                self.installing = Some(self.installed.join(", "));
+                self.output.set_text("");
+
+                std::thread::spawn(move || {
+                    let output_sender = sender.clone();
+
+                    let result = winetricks::reapply(move |line| output_sender.input(WinetricksPageMsg::Output(line)))
+                        .map(|_| tr!("winetricks-verbs-reapplied"))
+                        .map_err(|err| err.to_string());
+
+                    sender.input(WinetricksPageMsg::Finished(result));
+                });
+            }
+
+            WinetricksPageMsg::Output(line) => {
+                self.output.insert(&mut self.output.end_iter(), &format!("{line}\n"));
+            }
+
+            WinetricksPageMsg::Finished(result) => {
+                self.installing = None;
+
+                self.installed = Settings::get()
+                    .map(|settings| settings.winetricks)
+                    .unwrap_or_default();
+
+                self.update_rows(&sender);
+
+                let toast = match result {
+                    Ok(title) => GeneralAppMsg::Toast {
+                        title,
+                        description: None
+                    },
+
+                    Err(err) => {
+                        tracing::error!("Failed to install winetricks verb: {err}");
+
+                        GeneralAppMsg::Toast {
+                            title: tr!("winetricks-failed"),
+                            description: Some(err)
+                        }
+                    }
+                };
+
+                sender.output(toast).unwrap();
+            }
+        }
+    }
+}
@@ -34,9 +34,15 @@ pub enum PreferencesAppMsg {
 
     SetLauncherStyle(LauncherStyle),
 
+    /// Make the main window restart the background video
+    UpdateBackgroundVideo,
+
     UpdateLauncherState,
     RepairGame,
 
+    /// Make the enhancements page show the changed DirectX components
+    UpdateDirectX,
+
     Toast {
         title: String,
         description: Option<String>
@@ -122,6 +128,11 @@ impl SimpleAsyncComponent for PreferencesApp {
             }
 
             #[allow(unused_must_use)]
+            #[allow(unused_must_use)]
+            PreferencesAppMsg::UpdateBackgroundVideo => {
+                sender.output(Self::Output::UpdateBackgroundVideo);
+            }
+
             PreferencesAppMsg::UpdateLauncherState => {
                 sender.output(Self::Output::UpdateLauncherState {
                     perform_on_download_needed: false,
@@ -129,6 +140,10 @@ impl SimpleAsyncComponent for PreferencesApp {
                 });
             }
 
+            PreferencesAppMsg::UpdateDirectX => {
+                self.enhancements.emit(EnhancementsAppMsg::UpdateDirectX);
+            }
+
             #[allow(unused_must_use)]
             PreferencesAppMsg::RepairGame => unsafe {
                 PREFERENCES_WINDOW.as_ref().unwrap_unchecked().close();
@@ -0,0 +1,176 @@
+use anime_launcher_sdk::wincompatlib::prelude::*;
+
+use anime_launcher_sdk::components::*;
+
+use anime_launcher_sdk::config::ConfigExt;
+use anime_launcher_sdk::wuwa::config::Config;
+
+use crate::checksums::{self, ComponentKind};
+use crate::settings::prelude::*;
+
+/// New recommended builds from the groups of the selected ones
+#[derive(Debug, Clone, Default)]
+pub struct Upgrades {
+    pub wine: Option<wine::Version>,
+    pub dxvk: Option<dxvk::Version>
+}
+
+impl Upgrades {
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.wine.is_none() && self.dxvk.is_none()
+    }
+
+    /// Titles of the new builds
+    pub fn titles(&self) -> Vec<String> {
+        self.wine.iter().map(|wine| wine.title.clone())
+            .chain(self.dxvk.iter().map(|dxvk| dxvk.title.clone()))
+            .collect()
+    }
+}
+
+/// Update wine prefix with the given wine version and select it
+pub fn select_wine(version: &wine::Version) -> anyhow::Result<()> {
+    let mut config = Config::get()?;
+
+    let wine = version
+        .to_wine(&config.components.path, Some(&config.game.wine.builds.join(&version.name)))
+        .with_prefix(&config.game.wine.prefix)
+        .with_loader(WineLoader::Current)
+        .with_arch(WineArch::Win64);
+
+    wine.update_prefix(None::<&str>)?;
+
+    config.game.wine.selected = Some(version.name.clone());
+
+    Config::update(config);
+
+    Ok(())
+}
+
+/// Install the given DXVK version to the wine prefix using selected wine
+pub fn select_dxvk(version: &dxvk::Version) -> anyhow::Result<()> {
+    let config = Config::get()?;
+
+    let wine = crate::directx::prefix_wine(&config)?;
+
+    Dxvk::install(&wine, config.game.dxvk.builds.join(&version.name), InstallParams::default())?;
+
+    Ok(())
+}
+
+/// Find new recommended builds in the groups of the selected wine and DXVK
+pub fn find() -> anyhow::Result<Upgrades> {
+    let config = Config::get()?;
+
+    let wine = match &config.game.wine.selected {
+        Some(selected) => wine::get_groups(&config.components.path)?
+            .into_iter()
+            // Custom builds are not upgraded
+            .filter(|group| group.name != crate::custom_wine::GROUP_NAME)
+            .find(|group| group.versions.iter().any(|version| &version.name == selected))
+            .and_then(|group| group.versions.into_iter().find(|version| version.version_features().map(|features| features.recommended).unwrap_or(true)))
+            .filter(|version| &version.name != selected),
+
+        None => None
+    };
+
+    let dxvk = match config.get_selected_dxvk()? {
+        Some(selected) => dxvk::get_groups(&config.components.path)?
+            .into_iter()
+            .find(|group| group.versions.iter().any(|version| version.name == selected.name))
+            .and_then(|group| group.versions.into_iter().find(|version| version.version_features().map(|features| features.recommended).unwrap_or(true)))
+            .filter(|version| version.name != selected.name),
+
+        None => None
+    };
+
+    Ok(Upgrades { wine, dxvk })
+}
+
+/// Download and select new builds, remembering the previous ones for rollback
+///
+/// Builds remembered by the earlier upgrades are forgotten, so rollback
+/// reverts only the components changed by this one
+pub fn upgrade(upgrades: &Upgrades) -> anyhow::Result<()> {
+    if upgrades.is_empty() {
+        return Ok(());
+    }
+
+    let config = Config::get()?;
+
+    Settings::modify(|settings| {
+        settings.components_upgrade.previous_wine = None;
+        settings.components_upgrade.previous_dxvk = None;
+    })?;
+
+    if let Some(wine) = &upgrades.wine {
+        tracing::info!("Upgrading wine to {}", wine.name);
+
+        if !wine.is_downloaded_in(&config.game.wine.builds) {
+            checksums::install(ComponentKind::Wine, &wine.name, &wine.uri, &config.game.wine.builds, &config, |_| {})?;
+        }
+
+        let previous = config.game.wine.selected.clone();
+
+        select_wine(wine)?;
+
+        Settings::modify(|settings| settings.components_upgrade.previous_wine = previous)?;
+    }
+
+    if let Some(dxvk) = &upgrades.dxvk {
+        tracing::info!("Upgrading DXVK to {}", dxvk.name);
+
+        if !dxvk.is_downloaded_in(&config.game.dxvk.builds) {
+            checksums::install(ComponentKind::Dxvk, &dxvk.name, &dxvk.uri, &config.game.dxvk.builds, &config, |_| {})?;
+        }
+
+        let previous = config.get_selected_dxvk()?.map(|dxvk| dxvk.name);
+
+        select_dxvk(dxvk)?;
+
+        Settings::modify(|settings| settings.components_upgrade.previous_dxvk = previous)?;
+    }
+
+    Ok(())
+}
+
+/// Select wine and DXVK builds used before the last upgrade
+///
+/// Settings are saved after each component, so failed rollback
+/// can be retried for the components which weren't rolled back
+pub fn rollback() -> anyhow::Result<()> {
+    let config = Config::get()?;
+    let upgrade = Settings::get()?.components_upgrade;
+
+    if let Some(name) = upgrade.previous_wine {
+        tracing::info!("Rolling wine back to {name}");
+
+        let version = wine::get_downloaded(&config.components.path, &config.game.wine.builds)?
+            .into_iter()
+            .flat_map(|group| group.versions)
+            .chain(crate::custom_wine::versions(&config.game.wine.builds))
+            .find(|version| version.name == name)
+            .ok_or_else(|| anyhow::anyhow!("Wine build is not downloaded anymore: {name}"))?;
+
+        select_wine(&version)?;
+
+        Settings::modify(|settings| settings.components_upgrade.previous_wine = None)?;
+    }
+
+    if let Some(name) = upgrade.previous_dxvk {
+        tracing::info!("Rolling DXVK back to {name}");
+
+        let version = dxvk::get_downloaded(&config.components.path, &config.game.dxvk.builds)?
+            .into_iter()
+            .flat_map(|group| group.versions)
+            .find(|version| version.name == name)
+            .ok_or_else(|| anyhow::anyhow!("DXVK build is not downloaded anymore: {name}"))?;
+
+        select_dxvk(&version)?;
+
+        Settings::modify(|settings| settings.components_upgrade.previous_dxvk = None)?;
+    }
+
+    Ok(())
+}
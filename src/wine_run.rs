@@ -0,0 +1,178 @@
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, ExitStatus, Stdio};
+
+use anime_launcher_sdk::config::ConfigExt;
+use anime_launcher_sdk::wuwa::config::{Config, Schema};
+
+/// Get path to the selected wine build's binary
+pub fn wine_binary(config: &Schema) -> anyhow::Result<PathBuf> {
+    let Some(wine) = crate::custom_wine::get_selected(config)? else {
+        anyhow::bail!("Wine is not selected");
+    };
+
+    let build = config.game.wine.builds.join(&wine.name);
+
+    ["bin/wine64", "bin/wine", "files/bin/wine64", "files/bin/wine"]
+        .iter()
+        .map(|path| build.join(path))
+        .find(|path| path.exists())
+        .ok_or_else(|| anyhow::anyhow!("Wine binary not found in {build:?}"))
+}
+
+/// Send stdout and stderr lines of the child process to the given callback
+/// until both streams are closed
+pub fn stream_output(child: &mut Child, output: impl Fn(String) + Clone + Send + 'static) {
+    let stderr = child.stderr.take().map(|stderr| {
+        let output = output.clone();
+
+        std::thread::spawn(move || {
+            for line in BufReader::new(stderr).lines().map_while(Result::ok) {
+                output(line);
+            }
+        })
+    });
+
+    if let Some(stdout) = child.stdout.take() {
+        for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+            output(line);
+        }
+    }
+
+    if let Some(stderr) = stderr {
+        let _ = stderr.join();
+    }
+}
+
+/// Split arguments string like shell does, respecting quotes and backslash escapes
+pub fn split_args(args: &str) -> Vec<String> {
+    let mut result = Vec::new();
+
+    let mut current = String::new();
+    let mut quote = None;
+    let mut has_arg = false;
+
+    let mut chars = args.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (c, quote) {
+            ('\\', Some('\'')) => current.push(c),
+
+            // Only quotes and backslashes are escaped inside of double quotes,
+            // so windows paths keep their backslashes
+            ('\\', Some('"')) => match chars.next_if(|next| matches!(next, '"' | '\\')) {
+                Some(next) => current.push(next),
+                None => current.push(c)
+            }
+
+            ('\\', _) => {
+                if let Some(next) = chars.next() {
+                    current.push(next);
+                }
+
+                has_arg = true;
+            }
+
+            ('"' | '\'', None) => {
+                quote = Some(c);
+                has_arg = true;
+            }
+
+            (c, Some(q)) if c == q => quote = None,
+
+            (c, None) if c.is_whitespace() => {
+                if has_arg {
+                    result.push(std::mem::take(&mut current));
+
+                    has_arg = false;
+                }
+            }
+
+            (c, _) => {
+                current.push(c);
+                has_arg = true;
+            }
+        }
+    }
+
+    if has_arg {
+        result.push(current);
+    }
+
+    result
+}
+
+/// Run windows executable in the game's wine prefix, sending its output lines to the given callback
+///
+/// Executable is started with the selected wine build, wine sync and game environment variables.
+/// If working directory is not specified, the executable's folder is used
+pub fn run(
+    executable: impl AsRef<Path>,
+    args: impl AsRef<str>,
+    working_dir: Option<impl AsRef<Path>>,
+    output: impl Fn(String) + Clone + Send + 'static
+) -> anyhow::Result<ExitStatus> {
+    let executable = executable.as_ref();
+
+    if !executable.is_file() {
+        anyhow::bail!("Executable doesn't exist: {executable:?}");
+    }
+
+    let config = Config::get()?;
+    let binary = wine_binary(&config)?;
+
+    let working_dir = match working_dir {
+        Some(working_dir) => working_dir.as_ref().to_path_buf(),
+        None => executable.parent().map(Path::to_path_buf).unwrap_or_default()
+    };
+
+    tracing::info!("Running {executable:?} in {working_dir:?} using {binary:?}");
+
+    let mut child = Command::new(&binary)
+        .arg(executable)
+        .args(split_args(args.as_ref()))
+        .current_dir(working_dir)
+        .env("WINEPREFIX", &config.game.wine.prefix)
+        .env("WINESERVER", binary.with_file_name("wineserver"))
+        .envs(config.game.wine.sync.get_env_vars())
+        .envs(&config.game.environment)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    stream_output(&mut child, output);
+
+    Ok(child.wait()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_plain_args() {
+        assert_eq!(split_args("  -a   --b=c d "), ["-a", "--b=c", "d"]);
+        assert!(split_args("").is_empty());
+    }
+
+    #[test]
+    fn split_quoted_args() {
+        assert_eq!(split_args(r#"-path "C:\Program Files" 'single quoted'"#), ["-path", r"C:\Program Files", "single quoted"]);
+        assert_eq!(split_args(r#"--name="a b"c"#), ["--name=a bc"]);
+        assert_eq!(split_args(r#""" ''"#), ["", ""]);
+    }
+
+    #[test]
+    fn split_escaped_args() {
+        assert_eq!(split_args(r"a\ b c"), ["a b", "c"]);
+        assert_eq!(split_args(r#""say \"hi\"""#), [r#"say "hi""#]);
+        assert_eq!(split_args(r"'C:\Games\'"), [r"C:\Games\"]);
+    }
+
+    #[test]
+    fn split_dangling_args() {
+        assert_eq!(split_args(r"a b\"), ["a", "b"]);
+        assert_eq!(split_args(r#"a "b c"#), ["a", "b c"]);
+        assert_eq!(split_args("a 'b"), ["a", "b"]);
+    }
+}
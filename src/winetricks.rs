@@ -0,0 +1,93 @@
+use std::process::{Command, Stdio};
+
+use anime_launcher_sdk::wuwa::config::Config;
+
+use crate::settings::prelude::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VerbCategory {
+    Fonts,
+    Runtime,
+    DirectX
+}
+
+/// Commonly needed verbs shown in the preferences.
+/// Any other verb can be installed by its name
+pub const COMMON_VERBS: &[(&str, VerbCategory)] = &[
+    ("corefonts", VerbCategory::Fonts),
+    ("cjkfonts", VerbCategory::Fonts),
+    ("tahoma", VerbCategory::Fonts),
+    ("vcrun2019", VerbCategory::Runtime),
+    ("vcrun2022", VerbCategory::Runtime),
+    ("d3dcompiler_43", VerbCategory::DirectX),
+    ("d3dcompiler_47", VerbCategory::DirectX),
+    ("d3dx9", VerbCategory::DirectX)
+];
+
+/// Check that the verb name can be passed to winetricks
+///
+/// Verbs can't start with `-` so they aren't parsed as winetricks options
+#[inline]
+pub fn is_valid_verb(verb: &str) -> bool {
+    !verb.is_empty() && !verb.starts_with('-') && verb.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-' || c == '=')
+}
+
+/// Install winetricks verb to the wine prefix with the selected wine build,
+/// sending output lines to the given callback
+///
+/// Installed verb is remembered in the settings to be reapplied after prefix rebuild
+pub fn install(verb: impl AsRef<str>, output: impl Fn(String) + Clone + Send + 'static) -> anyhow::Result<()> {
+    let verb = verb.as_ref();
+
+    if !is_valid_verb(verb) {
+        anyhow::bail!("Invalid winetricks verb: {verb:?}");
+    }
+
+    if !anime_launcher_sdk::is_available("winetricks") {
+        anyhow::bail!("winetricks is not installed");
+    }
+
+    let config = Config::get()?;
+
+    let binary = crate::wine_run::wine_binary(&config)?;
+
+    let wineserver = binary.with_file_name("wineserver");
+
+    tracing::info!("Installing winetricks verb {verb} using {binary:?}");
+
+    let mut child = Command::new("winetricks")
+        .arg("--unattended")
+        .arg(verb)
+        .env("WINEPREFIX", &config.game.wine.prefix)
+        .env("WINE", &binary)
+        .env("WINESERVER", &wineserver)
+        .envs(config.game.wine.sync.get_env_vars())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    crate::wine_run::stream_output(&mut child, output);
+
+    let status = child.wait()?;
+
+    if !status.success() {
+        anyhow::bail!("winetricks exited with {status}");
+    }
+
+    Settings::modify(|settings| {
+        if !settings.winetricks.iter().any(|installed| installed == verb) {
+            settings.winetricks.push(verb.to_string());
+        }
+    })?;
+
+    Ok(())
+}
+
+/// Install all the remembered verbs again, e.g. after prefix rebuild
+pub fn reapply(output: impl Fn(String) + Clone + Send + 'static) -> anyhow::Result<()> {
+    for verb in Settings::get()?.winetricks {
+        install(&verb, output.clone())?;
+    }
+
+    Ok(())
+}